@@ -6,6 +6,8 @@ use crate::sys;
 use crate::{Error, NixPath, Result};
 use cfg_if::cfg_if;
 use std::ffi;
+#[cfg(target_os = "linux")]
+use std::os::unix::io::AsFd;
 use std::os::unix::io::{AsRawFd, IntoRawFd, RawFd};
 use std::ptr;
 
@@ -188,6 +190,114 @@ impl IntoIterator for Dir {
     }
 }
 
+/// Iterate over the entries of the directory open at `fd`, using
+/// `getdents64(2)` directly.
+///
+/// Unlike [`Dir`], this doesn't take ownership of the file descriptor or
+/// allocate a libc `DIR` stream, which is convenient for directories the
+/// caller already holds a descriptor for, like `/proc/<pid>/task`.  Entries
+/// are read starting at the descriptor's current offset, which is advanced
+/// as the iterator goes.  Like [`Dir`], the `.` and `..` entries are
+/// returned.
+///
+/// # Example
+///
+/// ```
+/// use nix::dir::getdents;
+/// use nix::fcntl::{open, OFlag};
+/// use nix::sys::stat::Mode;
+/// use std::os::unix::io::{FromRawFd, OwnedFd};
+///
+/// let flags = OFlag::O_RDONLY | OFlag::O_DIRECTORY;
+/// let fd = open("/proc/self/task", flags, Mode::empty()).unwrap();
+/// let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+/// for entry in getdents(&fd) {
+///     println!("{:?}", entry.unwrap().file_name());
+/// }
+/// ```
+#[cfg(target_os = "linux")]
+#[cfg_attr(docsrs, doc(cfg(all())))]
+pub fn getdents<Fd: AsFd>(fd: Fd) -> GetDents<Fd> {
+    GetDents {
+        fd,
+        buf: vec![0; GETDENTS_BUF_SIZE],
+        pos: 0,
+        len: 0,
+        done: false,
+    }
+}
+
+#[cfg(target_os = "linux")]
+const GETDENTS_BUF_SIZE: usize = 32 * 1024;
+
+/// Return type of [`getdents`].
+#[cfg(target_os = "linux")]
+#[cfg_attr(docsrs, doc(cfg(all())))]
+#[derive(Debug)]
+pub struct GetDents<Fd: AsFd> {
+    fd: Fd,
+    buf: Vec<u8>,
+    pos: usize,
+    len: usize,
+    done: bool,
+}
+
+#[cfg(target_os = "linux")]
+impl<Fd: AsFd> Iterator for GetDents<Fd> {
+    type Item = Result<Entry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if self.pos >= self.len {
+            let res = unsafe {
+                libc::syscall(
+                    libc::SYS_getdents64,
+                    self.fd.as_fd().as_raw_fd(),
+                    self.buf.as_mut_ptr(),
+                    self.buf.len(),
+                )
+            };
+            match Errno::result(res) {
+                Ok(0) => {
+                    self.done = true;
+                    return None;
+                }
+                Ok(n) => {
+                    self.pos = 0;
+                    self.len = n as usize;
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+
+        // The kernel's `struct linux_dirent64` has the same layout as glibc's
+        // `struct dirent64`, but each record is only `d_reclen` bytes long.
+        let size = std::mem::size_of::<dirent>();
+        let mut ent = std::mem::MaybeUninit::<dirent>::zeroed();
+        let reclen = unsafe {
+            let dst = ent.as_mut_ptr() as *mut u8;
+            ptr::copy_nonoverlapping(
+                self.buf.as_ptr().add(self.pos),
+                dst,
+                size.min(self.len - self.pos),
+            );
+            let reclen = (*ent.as_ptr()).d_reclen as usize;
+            // Don't let the start of the next record leak into this entry
+            if reclen < size {
+                ptr::write_bytes(dst.add(reclen), 0, size - reclen);
+            }
+            reclen
+        };
+        self.pos += reclen;
+        Some(Ok(Entry(unsafe { ent.assume_init() })))
+    }
+}
+
 /// A directory entry, similar to `std::fs::DirEntry`.
 ///
 /// Note that unlike the std version, this may represent the `.` or `..` entries.
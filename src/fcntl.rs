@@ -5,6 +5,11 @@ use std::ffi::OsString;
 use std::os::raw;
 use std::os::unix::ffi::OsStringExt;
 use std::os::unix::io::RawFd;
+#[cfg(all(
+    any(target_os = "android", target_os = "linux"),
+    feature = "fs"
+))]
+use std::os::unix::io::{FromRawFd, OwnedFd};
 
 #[cfg(feature = "fs")]
 use crate::{sys::stat::Mode, NixPath, Result};
@@ -220,6 +225,113 @@ pub fn openat<P: ?Sized + NixPath>(
     Errno::result(fd)
 }
 
+#[cfg(target_os = "linux")]
+libc_bitflags! {
+    /// Path resolution flags for [`openat2`].
+    ///
+    /// See [path resolution(7)](https://man7.org/linux/man-pages/man7/path_resolution.7.html)
+    /// for details of the resolution process.
+    pub struct ResolveFlag: libc::c_ulonglong {
+        /// Do not permit the path resolution to succeed if any component of
+        /// the resolution is not a descendant of the directory indicated by
+        /// `dirfd`.  This causes absolute symbolic links (and absolute values
+        /// of `pathname`) to be rejected.
+        RESOLVE_BENEATH;
+        /// Treat the directory referred to by `dirfd` as the root directory
+        /// while resolving `pathname`.
+        RESOLVE_IN_ROOT;
+        /// Disallow all magic-link resolution during path resolution, such
+        /// as the links found in `/proc/<pid>/fd`.
+        RESOLVE_NO_MAGICLINKS;
+        /// Disallow resolution of symbolic links during path resolution.
+        /// This option implies `RESOLVE_NO_MAGICLINKS`.
+        RESOLVE_NO_SYMLINKS;
+        /// Disallow traversal of mount points during path resolution
+        /// (including all bind mounts).
+        RESOLVE_NO_XDEV;
+    }
+}
+
+/// How to open a file, as passed to [`openat2`].
+///
+/// This is a wrapper around `struct open_how`; see
+/// [openat2(2)](https://man7.org/linux/man-pages/man2/openat2.2.html).
+#[cfg(target_os = "linux")]
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct OpenHow(libc::open_how);
+
+#[cfg(target_os = "linux")]
+impl OpenHow {
+    /// Create a new zero-filled `open_how`.
+    pub fn new() -> Self {
+        // safety: according to the man page, open_how MUST be zero-initialized
+        // on init so that unknown fields are also zeroed.
+        Self(unsafe { std::mem::zeroed() })
+    }
+
+    /// Set the open flags used to open a file, completely overwriting any
+    /// existing flags.
+    pub fn flags(mut self, flags: OFlag) -> Self {
+        self.0.flags = flags.bits() as libc::c_ulonglong;
+        self
+    }
+
+    /// Set the file mode new files will be created with, overwriting any
+    /// existing flags.
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.0.mode = mode.bits() as libc::c_ulonglong;
+        self
+    }
+
+    /// Set resolve flags, completely overwriting any existing flags.
+    ///
+    /// See [`ResolveFlag`] for more detail.
+    pub fn resolve(mut self, resolve: ResolveFlag) -> Self {
+        self.0.resolve = resolve.bits();
+        self
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Default for OpenHow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Open or create a file for reading, writing or executing, with finer
+/// control over path resolution than [`openat`].
+///
+/// `openat2` is an extension of [`openat`]: the flags and mode are passed in
+/// an [`OpenHow`], which additionally carries [`ResolveFlag`]s restricting
+/// how the path may be resolved.  This is useful for safely opening paths
+/// that are controlled by an untrusted process.
+///
+/// Requires Linux 5.6 or later.
+///
+/// See also [openat2(2)](https://man7.org/linux/man-pages/man2/openat2.2.html)
+#[cfg(target_os = "linux")]
+pub fn openat2<P: ?Sized + NixPath>(
+    dirfd: RawFd,
+    path: &P,
+    mut how: OpenHow,
+) -> Result<OwnedFd> {
+    let fd = path.with_nix_path(|cstr| unsafe {
+        libc::syscall(
+            libc::SYS_openat2,
+            dirfd,
+            cstr.as_ptr(),
+            &mut how as *mut OpenHow,
+            std::mem::size_of::<libc::open_how>(),
+        )
+    })?;
+    Errno::result(fd)?;
+
+    // Safe because openat2 returned a new file descriptor on success
+    Ok(unsafe { OwnedFd::from_raw_fd(fd as RawFd) })
+}
+
 #[cfg(not(target_os = "redox"))]
 pub fn renameat<P1: ?Sized + NixPath, P2: ?Sized + NixPath>(
     old_dirfd: Option<RawFd>,
@@ -276,6 +388,157 @@ pub fn renameat2<P1: ?Sized + NixPath, P2: ?Sized + NixPath>(
     Errno::result(res).map(drop)
 }
 
+/// An opaque, persistent identifier for a file, as returned by
+/// [`name_to_handle_at`].
+///
+/// Unlike a path, a handle keeps referring to the same file across renames,
+/// and unlike a file descriptor, it can be stored and used by another
+/// process later on.  Use [`open_by_handle_at`] to turn it back into a file
+/// descriptor.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[cfg_attr(docsrs, doc(cfg(all())))]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct FileHandle {
+    handle_type: c_int,
+    handle: Vec<u8>,
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl FileHandle {
+    /// Size of the fixed part of `struct file_handle`: `handle_bytes` and
+    /// `handle_type`.
+    const HEADER_SIZE: usize =
+        std::mem::size_of::<c_uint>() + std::mem::size_of::<c_int>();
+
+    /// Reconstruct a handle from the parts returned by
+    /// [`FileHandle::handle_type`] and [`FileHandle::as_bytes`], for example
+    /// after they have been persisted.
+    pub fn from_raw_parts(handle_type: c_int, handle: &[u8]) -> Self {
+        FileHandle {
+            handle_type,
+            handle: handle.to_vec(),
+        }
+    }
+
+    /// Returns the file system specific type of the handle (`handle_type`).
+    pub const fn handle_type(&self) -> c_int {
+        self.handle_type
+    }
+
+    /// Returns the opaque handle data (`f_handle`).
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.handle
+    }
+
+    /// Lay out the handle as a `struct file_handle`.
+    fn to_raw(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity(Self::HEADER_SIZE + self.handle.len());
+        raw.extend_from_slice(&(self.handle.len() as c_uint).to_ne_bytes());
+        raw.extend_from_slice(&self.handle_type.to_ne_bytes());
+        raw.extend_from_slice(&self.handle);
+        raw
+    }
+}
+
+/// Identifier of the mount containing a file, as returned by
+/// [`name_to_handle_at`].
+///
+/// This corresponds to the first field of `/proc/self/mountinfo`, and can be
+/// used to find a file descriptor suitable for [`open_by_handle_at`].
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[cfg_attr(docsrs, doc(cfg(all())))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct MountId(c_int);
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl MountId {
+    /// Returns the raw mount ID.
+    pub const fn as_raw(self) -> c_int {
+        self.0
+    }
+}
+
+/// Obtain a [`FileHandle`] for the file at `path`, relative to `dirfd`,
+/// along with the ID of the mount containing it.
+///
+/// Supported `flags` are `AT_SYMLINK_FOLLOW` and `AT_EMPTY_PATH`.  Not every
+/// file system supports file handles; those that don't fail with
+/// `EOPNOTSUPP`.
+///
+/// See also [name_to_handle_at(2)](https://man7.org/linux/man-pages/man2/name_to_handle_at.2.html)
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub fn name_to_handle_at<P: ?Sized + NixPath>(
+    dirfd: Option<RawFd>,
+    path: &P,
+    flags: AtFlags,
+) -> Result<(FileHandle, MountId)> {
+    const HEADER_SIZE: usize = FileHandle::HEADER_SIZE;
+
+    // Start with an empty handle, and let the kernel tell us how large it
+    // needs to be.
+    let mut raw = vec![0u8; HEADER_SIZE];
+    let mut mount_id: c_int = 0;
+    loop {
+        let capacity = (raw.len() - HEADER_SIZE) as c_uint;
+        raw[..4].copy_from_slice(&capacity.to_ne_bytes());
+        let res = path.with_nix_path(|cstr| unsafe {
+            libc::syscall(
+                libc::SYS_name_to_handle_at,
+                at_rawfd(dirfd),
+                cstr.as_ptr(),
+                raw.as_mut_ptr(),
+                &mut mount_id as *mut c_int,
+                flags.bits(),
+            )
+        })?;
+        let handle_bytes =
+            c_uint::from_ne_bytes([raw[0], raw[1], raw[2], raw[3]]);
+        match Errno::result(res) {
+            Ok(_) => {
+                let handle_type =
+                    c_int::from_ne_bytes([raw[4], raw[5], raw[6], raw[7]]);
+                raw.truncate(HEADER_SIZE + handle_bytes as usize);
+                raw.drain(..HEADER_SIZE);
+                let handle = FileHandle { handle_type, handle: raw };
+                return Ok((handle, MountId(mount_id)));
+            }
+            // The kernel has updated handle_bytes with the required size
+            Err(Errno::EOVERFLOW) if handle_bytes > capacity => {
+                raw.resize(HEADER_SIZE + handle_bytes as usize, 0);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Open the file identified by `handle`, as returned by
+/// [`name_to_handle_at`].
+///
+/// `mount_fd` is any file descriptor on the mount containing the file.  The
+/// caller needs the `CAP_DAC_READ_SEARCH` capability.
+///
+/// See also [open_by_handle_at(2)](https://man7.org/linux/man-pages/man2/open_by_handle_at.2.html)
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub fn open_by_handle_at(
+    mount_fd: RawFd,
+    handle: &FileHandle,
+    flags: OFlag,
+) -> Result<OwnedFd> {
+    let raw = handle.to_raw();
+    let res = unsafe {
+        libc::syscall(
+            libc::SYS_open_by_handle_at,
+            mount_fd,
+            raw.as_ptr(),
+            flags.bits(),
+        )
+    };
+    Errno::result(res)?;
+
+    // Safe because open_by_handle_at returned a new file descriptor
+    Ok(unsafe { OwnedFd::from_raw_fd(res as RawFd) })
+}
+
 fn wrap_readlink_result(mut v: Vec<u8>, len: ssize_t) -> Result<OsString> {
     unsafe { v.set_len(len as usize) }
     v.shrink_to_fit();
@@ -393,6 +656,16 @@ pub fn readlink<P: ?Sized + NixPath>(path: &P) -> Result<OsString> {
     inner_readlink(None, path)
 }
 
+/// Read the target of the symbolic link `path`, relative to the directory
+/// `dirfd`.
+///
+/// The buffer grows until the whole target fits, so arbitrarily long targets
+/// are returned in full.  On Linux, an empty `path` reads the link referred
+/// to by `dirfd` itself, which must then have been opened with `O_PATH |
+/// O_NOFOLLOW`.  This is handy for resolving links like
+/// `/proc/<pid>/fd/<n>` or `/proc/<pid>/exe`.
+///
+/// See also [readlinkat(2)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/readlinkat.html)
 #[cfg(not(target_os = "redox"))]
 pub fn readlinkat<P: ?Sized + NixPath>(
     dirfd: RawFd,
@@ -425,6 +698,12 @@ libc_bitflags!(
         F_SEAL_GROW;
         /// The file contents cannot be modified.
         F_SEAL_WRITE;
+        /// The file contents cannot be modified through new writable
+        /// mappings or `write` calls, but existing writable shared mappings
+        /// keep working.  Linux 5.1 or later.
+        #[cfg(target_os = "linux")]
+        #[cfg_attr(docsrs, doc(cfg(all())))]
+        F_SEAL_FUTURE_WRITE;
     }
 );
 
@@ -544,6 +823,26 @@ pub fn fcntl(fd: RawFd, arg: FcntlArg) -> Result<c_int> {
     Errno::result(res)
 }
 
+/// Get the capacity of the pipe referred to by `fd`, in bytes.
+///
+/// This is a convenience wrapper around `fcntl(fd, F_GETPIPE_SZ)`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn get_pipe_size(fd: RawFd) -> Result<usize> {
+    fcntl(fd, F_GETPIPE_SZ).map(|size| size as usize)
+}
+
+/// Change the capacity of the pipe referred to by `fd` to at least `size`
+/// bytes.
+///
+/// The kernel may round the capacity up, so the capacity actually set is
+/// returned.  Unprivileged processes can't exceed `/proc/sys/fs/pipe-max-size`.
+/// This is a convenience wrapper around `fcntl(fd, F_SETPIPE_SZ, size)`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn set_pipe_size(fd: RawFd, size: usize) -> Result<usize> {
+    let size = c_int::try_from(size).map_err(|_| Errno::EINVAL)?;
+    fcntl(fd, F_SETPIPE_SZ(size)).map(|size| size as usize)
+}
+
 // TODO: convert to libc_enum
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[non_exhaustive]
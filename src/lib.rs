@@ -16,6 +16,7 @@
 //! * `fs` - File system functionality
 //! * `hostname` - Get and set the system's hostname
 //! * `inotify` - Linux's `inotify` file system notification API
+//! * `io_uring` - Linux's `io_uring` asynchronous I/O API
 //! * `ioctl` - The `ioctl` syscall, and wrappers for my specific instances
 //! * `kmod` - Load and unload kernel modules
 //! * `mman` - Stuff relating to memory management
@@ -124,6 +125,11 @@ feature! {
     #![feature = "poll"]
     pub mod poll;
 }
+#[cfg(any(target_os = "android", target_os = "linux"))]
+feature! {
+    #![feature = "process"]
+    pub mod proc;
+}
 #[cfg(not(any(target_os = "redox", target_os = "fuchsia")))]
 feature! {
     #![feature = "term"]
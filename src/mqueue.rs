@@ -134,6 +134,14 @@ impl MqAttr {
     pub const fn curmsgs(&self) -> mq_attr_member_t {
         self.mq_attr.mq_curmsgs
     }
+
+    /// Whether the queue descriptor is in non-blocking mode, as decoded from
+    /// [`MqAttr::flags`].
+    #[allow(clippy::useless_conversion)] // Not useless on all OSes
+    pub fn is_nonblocking(&self) -> bool {
+        let nonblock = mq_attr_member_t::from(MQ_OFlag::O_NONBLOCK.bits());
+        self.flags() & nonblock != 0
+    }
 }
 
 /// Open a message queue
@@ -221,6 +229,31 @@ feature! {
         };
         Errno::result(res).map(|r| r as usize)
     }
+
+    /// Send a message to a message queue with a timeout
+    ///
+    /// If the queue is full, wait until there is room for the message or
+    /// until the absolute time `abstime`, measured against
+    /// `CLOCK_REALTIME`, passes, in which case `ETIMEDOUT` is returned.
+    ///
+    /// See also [`mq_timedsend(2)`](https://pubs.opengroup.org/onlinepubs/9699919799/functions/mq_send.html)
+    pub fn mq_timedsend(
+        mqdes: &MqdT,
+        message: &[u8],
+        msq_prio: u32,
+        abstime: &TimeSpec,
+    ) -> Result<()> {
+        let res = unsafe {
+            libc::mq_timedsend(
+                mqdes.0,
+                message.as_ptr() as *const c_char,
+                message.len(),
+                msq_prio,
+                abstime.as_ref(),
+            )
+        };
+        Errno::result(res).map(drop)
+    }
 }
 
 /// Send a message to a message queue
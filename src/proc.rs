@@ -0,0 +1,285 @@
+//! Information about processes from the `/proc` file system
+//!
+//! For more documentation, please read
+//! [proc(5)](https://man7.org/linux/man-pages/man5/proc.5.html).
+
+use crate::errno::Errno;
+use crate::unistd::Pid;
+use crate::Result;
+use std::ffi::OsString;
+use std::os::unix::ffi::OsStringExt;
+use std::path::PathBuf;
+
+/// Read a `/proc/<pid>/` file made of NUL-terminated strings.
+fn read_strings(pid: Pid, file: &str) -> Result<Vec<OsString>> {
+    let mut data = std::fs::read(format!("/proc/{}/{}", pid, file))
+        .map_err(|e| Errno::try_from(e).unwrap_or(Errno::EIO))?;
+    // A process may overwrite its own strings, dropping the final NUL
+    if data.last() == Some(&0) {
+        data.pop();
+    }
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(data
+        .split(|&b| b == 0)
+        .map(|s| OsString::from_vec(s.to_vec()))
+        .collect())
+}
+
+/// Get the command line arguments of process `pid`.
+///
+/// These are the `argv` strings the process was started with, unless it
+/// has overwritten them since.  The list is empty for kernel threads and
+/// zombies.
+pub fn cmdline(pid: Pid) -> Result<Vec<OsString>> {
+    read_strings(pid, "cmdline")
+}
+
+/// Get the environment of process `pid`, as `NAME=value` strings.
+///
+/// This is the environment the process was started with; later changes
+/// made with `setenv` aren't reflected.  Reading the environment of another
+/// process requires the same permissions as attaching to it with `ptrace`
+/// and fails with `EACCES` otherwise.
+pub fn environ(pid: Pid) -> Result<Vec<OsString>> {
+    read_strings(pid, "environ")
+}
+
+/// Where an address in a process's memory comes from, as found by
+/// [`addr_to_module`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ModuleMapping {
+    /// The file the mapping containing the address is backed by.  If the
+    /// file was deleted after it was mapped, the kernel appends
+    /// `" (deleted)"` to its name.
+    pub path: PathBuf,
+    /// The start address of the mapping.
+    pub start: u64,
+    /// The end address of the mapping, exclusive.
+    pub end: u64,
+    /// The offset in the file of the address itself.
+    pub file_offset: u64,
+}
+
+/// Find the file and the offset in it that address `addr` of process `pid`
+/// is mapped from.
+///
+/// This searches `/proc/<pid>/maps`.  Returns `None` if `addr` isn't mapped,
+/// or if it is mapped anonymously or from a pseudo-file such as `[vdso]`.
+/// Reading the mappings of another process requires the same permissions as
+/// attaching to it with `ptrace` and fails with `EACCES` otherwise.
+pub fn addr_to_module(pid: Pid, addr: u64) -> Result<Option<ModuleMapping>> {
+    let maps = std::fs::read(format!("/proc/{}/maps", pid))
+        .map_err(|e| Errno::try_from(e).unwrap_or(Errno::EIO))?;
+    let hex = |s: &[u8]| {
+        std::str::from_utf8(s)
+            .ok()
+            .and_then(|s| u64::from_str_radix(s, 16).ok())
+            .ok_or(Errno::EINVAL)
+    };
+
+    for line in maps.split(|&b| b == b'\n').filter(|l| !l.is_empty()) {
+        // start-end perms offset dev inode [path]
+        let mut fields = line.splitn(6, |&b| b == b' ');
+        let mut next = || fields.next().ok_or(Errno::EINVAL);
+        let range = next()?;
+        let dash = range.iter().position(|&b| b == b'-');
+        let (start, end) = match dash {
+            Some(i) => (hex(&range[..i])?, hex(&range[i + 1..])?),
+            None => return Err(Errno::EINVAL),
+        };
+        if !(start..end).contains(&addr) {
+            continue;
+        }
+        let _perms = next()?;
+        let offset = hex(next()?)?;
+        let _dev = next()?;
+        let _inode = next()?;
+        let path = fields.next().unwrap_or_default();
+        let path = &path[path.iter().take_while(|&&b| b == b' ').count()..];
+        if !path.starts_with(b"/") {
+            return Ok(None);
+        }
+        return Ok(Some(ModuleMapping {
+            path: PathBuf::from(OsString::from_vec(path.to_vec())),
+            start,
+            end,
+            file_offset: addr - start + offset,
+        }));
+    }
+    Ok(None)
+}
+
+/// The real, effective, saved set and file system IDs of a process, as in
+/// [`ProcStatus::uid`] and [`ProcStatus::gid`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ProcIds<T> {
+    /// The real ID.
+    pub real: T,
+    /// The effective ID, used for most permission checks.
+    pub effective: T,
+    /// The saved set ID.
+    pub saved: T,
+    /// The file system ID, used for file access permission checks.
+    pub fs: T,
+}
+
+impl<T> ProcIds<T> {
+    fn map<U>(self, f: impl Fn(T) -> U) -> ProcIds<U> {
+        ProcIds {
+            real: f(self.real),
+            effective: f(self.effective),
+            saved: f(self.saved),
+            fs: f(self.fs),
+        }
+    }
+}
+
+/// The scheduling state of a process, as in [`ProcStatus::state`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ProcState {
+    /// Running or runnable (`R`).
+    Running,
+    /// In an interruptible sleep (`S`).
+    Sleeping,
+    /// In an uninterruptible sleep, usually waiting for I/O (`D`).
+    DiskSleep,
+    /// Stopped by a signal (`T`).
+    Stopped,
+    /// Stopped by a tracer, in a ptrace-stop (`t`).
+    TracingStop,
+    /// Exited but not yet waited for (`Z`).
+    Zombie,
+    /// Being torn down (`X`).
+    Dead,
+    /// An idle kernel thread (`I`).
+    Idle,
+    /// A state this version of nix doesn't know.
+    Other(char),
+}
+
+impl From<char> for ProcState {
+    fn from(c: char) -> Self {
+        match c {
+            'R' => ProcState::Running,
+            'S' => ProcState::Sleeping,
+            'D' => ProcState::DiskSleep,
+            'T' => ProcState::Stopped,
+            't' => ProcState::TracingStop,
+            'Z' => ProcState::Zombie,
+            'X' => ProcState::Dead,
+            'I' => ProcState::Idle,
+            c => ProcState::Other(c),
+        }
+    }
+}
+
+/// The credentials and signal state of a process, as returned by
+/// [`status`].
+///
+/// The signal masks have bit `n - 1` set for signal number `n`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ProcStatus {
+    /// The scheduling state (`State`).
+    pub state: ProcState,
+    /// The thread group ID, that is the process ID (`Tgid`).
+    pub tgid: Pid,
+    /// The parent process ID (`PPid`), or `None` for the init process and
+    /// kernel threads.
+    pub ppid: Option<Pid>,
+    /// The thread that traces this process (`TracerPid`), if any.  Despite
+    /// the name, this is a thread ID, as returned by
+    /// [`gettid`](crate::unistd::gettid), which differs from the tracer's
+    /// process ID unless the main thread attached.
+    pub tracer_pid: Option<Pid>,
+    /// The user IDs (`Uid`).
+    pub uid: ProcIds<crate::unistd::Uid>,
+    /// The group IDs (`Gid`).
+    pub gid: ProcIds<crate::unistd::Gid>,
+    /// The inheritable capabilities (`CapInh`).
+    pub cap_inh: crate::sys::capability::CapSet,
+    /// The permitted capabilities (`CapPrm`).
+    pub cap_prm: crate::sys::capability::CapSet,
+    /// The effective capabilities (`CapEff`).
+    pub cap_eff: crate::sys::capability::CapSet,
+    /// The signals pending for the thread (`SigPnd`).
+    pub sig_pnd: u64,
+    /// The signals pending for the whole process (`ShdPnd`).
+    pub shd_pnd: u64,
+    /// The blocked signals (`SigBlk`).
+    pub sig_blk: u64,
+    /// The ignored signals (`SigIgn`).
+    pub sig_ign: u64,
+    /// The signals with a handler installed (`SigCgt`).
+    pub sig_cgt: u64,
+}
+
+/// Get the credentials and signal state of process `pid`, as found in
+/// `/proc/<pid>/status`.
+///
+/// The per-thread fields, such as the blocked signals, are those of the
+/// thread `pid` names, which is the main thread for a process ID.  Fails
+/// with `ESRCH` if the process doesn't exist.
+pub fn status(pid: Pid) -> Result<ProcStatus> {
+    use crate::sys::capability::CapSet;
+    use crate::unistd::{Gid, Uid};
+
+    let path = format!("/proc/{}/status", pid);
+    let data = std::fs::read(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => Errno::ESRCH,
+        _ => Errno::try_from(e).unwrap_or(Errno::EIO),
+    })?;
+    let fields: std::collections::HashMap<&str, &str> = data
+        .split(|&b| b == b'\n')
+        // The name may not be valid UTF-8, but isn't needed
+        .filter_map(|line| std::str::from_utf8(line).ok())
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key, value.trim()))
+        .collect();
+
+    let field = |key: &str| fields.get(key).copied().ok_or(Errno::EINVAL);
+    let get_pid = |key: &str| {
+        field(key)?
+            .parse()
+            .map(Pid::from_raw)
+            .map_err(|_| Errno::EINVAL)
+    };
+    let nonzero = |pid: Pid| (pid.as_raw() != 0).then_some(pid);
+    let mask = |key: &str| {
+        u64::from_str_radix(field(key)?, 16).map_err(|_| Errno::EINVAL)
+    };
+    let ids = |key: &str| {
+        let ids = field(key)?
+            .split_whitespace()
+            .map(|id| id.parse::<u32>().map_err(|_| Errno::EINVAL))
+            .collect::<Result<Vec<_>>>()?;
+        match ids[..] {
+            [real, effective, saved, fs] => Ok(ProcIds {
+                real,
+                effective,
+                saved,
+                fs,
+            }),
+            _ => Err(Errno::EINVAL),
+        }
+    };
+
+    Ok(ProcStatus {
+        state: field("State")?.chars().next().ok_or(Errno::EINVAL)?.into(),
+        tgid: get_pid("Tgid")?,
+        ppid: nonzero(get_pid("PPid")?),
+        tracer_pid: nonzero(get_pid("TracerPid")?),
+        uid: ids("Uid")?.map(Uid::from_raw),
+        gid: ids("Gid")?.map(Gid::from_raw),
+        cap_inh: CapSet::from_bits(mask("CapInh")?),
+        cap_prm: CapSet::from_bits(mask("CapPrm")?),
+        cap_eff: CapSet::from_bits(mask("CapEff")?),
+        sig_pnd: mask("SigPnd")?,
+        shd_pnd: mask("ShdPnd")?,
+        sig_blk: mask("SigBlk")?,
+        sig_ign: mask("SigIgn")?,
+        sig_cgt: mask("SigCgt")?,
+    })
+}
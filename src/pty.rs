@@ -348,4 +348,43 @@ pub unsafe fn forkpty<'a, 'b, T: Into<Option<&'a Winsize>>, U: Into<Option<&'b T
         fork_result,
     })
 }
+
+/// Like [`forkpty`], but make the child a tracee of the calling process, as
+/// with [`traceme`](crate::sys::ptrace::traceme).
+///
+/// The child then typically calls one of the `exec` functions, which stops it
+/// with a `SIGTRAP` for the parent to collect with `waitpid` before it runs
+/// any code of the new program.  If `traceme` fails, the error is returned
+/// in the child, which should then `_exit`.
+///
+/// # Safety
+///
+/// The same as for [`forkpty`].
+#[cfg(all(
+    feature = "ptrace",
+    any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )
+))]
+#[cfg_attr(docsrs, doc(cfg(feature = "ptrace")))]
+pub unsafe fn forkpty_traced<'a, 'b, T, U>(
+    winsize: T,
+    termios: U,
+) -> Result<ForkptyResult>
+where
+    T: Into<Option<&'a Winsize>>,
+    U: Into<Option<&'b Termios>>,
+{
+    let pty = forkpty(winsize, termios)?;
+    if pty.fork_result.is_child() {
+        crate::sys::ptrace::traceme()?;
+    }
+    Ok(pty)
+}
 }
@@ -79,6 +79,12 @@ mod sched_linux_like {
             CLONE_NEWPID;
             /// Create the process in a new network namespace.
             CLONE_NEWNET;
+            /// Create the process in a new time namespace.
+            ///
+            /// With [`unshare`], only children created afterwards are placed
+            /// in the new namespace, not the caller itself.
+            #[cfg(target_os = "linux")]
+            CLONE_NEWTIME;
             /// The new process shares an I/O context with the calling process.
             CLONE_IO;
         }
@@ -133,14 +139,224 @@ mod sched_linux_like {
         Errno::result(res).map(drop)
     }
 
+    /// Like [`unshare`], but check that every namespace selected by `flags`
+    /// was actually replaced.
+    ///
+    /// The namespaces' inode numbers are read from `/proc/thread-self/ns`
+    /// before and after the call.  If one of them didn't change, `EINVAL` is
+    /// returned, though the other parts of the execution context have been
+    /// unshared anyway.  Flags that don't create a namespace aren't checked.
+    pub fn unshare_checked(flags: CloneFlags) -> Result<()> {
+        use std::os::unix::fs::MetadataExt;
+
+        let mut names = vec![
+            (CloneFlags::CLONE_NEWNS, "mnt"),
+            (CloneFlags::CLONE_NEWUTS, "uts"),
+            (CloneFlags::CLONE_NEWIPC, "ipc"),
+            (CloneFlags::CLONE_NEWNET, "net"),
+            (CloneFlags::CLONE_NEWPID, "pid_for_children"),
+            (CloneFlags::CLONE_NEWUSER, "user"),
+            (CloneFlags::CLONE_NEWCGROUP, "cgroup"),
+        ];
+        #[cfg(target_os = "linux")]
+        names.push((CloneFlags::CLONE_NEWTIME, "time_for_children"));
+        names.retain(|&(flag, _)| flags.contains(flag));
+
+        let inodes = || {
+            names
+                .iter()
+                .map(|(_, name)| {
+                    std::fs::metadata(format!("/proc/thread-self/ns/{}", name))
+                        .map(|md| md.ino())
+                        .map_err(|e| Errno::try_from(e).unwrap_or(Errno::EIO))
+                })
+                .collect::<Result<Vec<_>>>()
+        };
+        let before = inodes()?;
+        unshare(flags)?;
+        let after = inodes()?;
+        if before.iter().zip(&after).any(|(b, a)| b == a) {
+            return Err(Errno::EINVAL);
+        }
+        Ok(())
+    }
+
     /// reassociate thread with a namespace
     ///
+    /// `fd` is either a namespace file, such as `/proc/<pid>/ns/time`, or,
+    /// since Linux 5.8, a [pidfd](crate::sys::pidfd) of a process whose
+    /// namespaces, as selected by `nstype`, to join.  Joining a time
+    /// namespace requires the caller to be single-threaded.
+    ///
     /// See also [setns(2)](https://man7.org/linux/man-pages/man2/setns.2.html)
     pub fn setns<Fd: AsFd>(fd: Fd, nstype: CloneFlags) -> Result<()> {
         let res = unsafe { libc::setns(fd.as_fd().as_raw_fd(), nstype.bits()) };
 
         Errno::result(res).map(drop)
     }
+
+    /// Set the offset of `clock` in the time namespace of the children of
+    /// process `pid`, or of the calling process if `pid` is `None`.
+    ///
+    /// Processes in a time namespace see `CLOCK_MONOTONIC` and
+    /// `CLOCK_BOOTTIME`, the only clocks with an offset, shifted by `offset`
+    /// relative to the initial namespace.  The offsets can only be set after
+    /// [`unshare`] with [`CloneFlags::CLONE_NEWTIME`] and before the first
+    /// child is created in the new namespace, which freezes them.  This
+    /// requires `CAP_SYS_TIME` in the namespace's user namespace.
+    ///
+    /// See also [time_namespaces(7)](https://man7.org/linux/man-pages/man7/time_namespaces.7.html)
+    #[cfg(all(target_os = "linux", feature = "time"))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+    pub fn set_timens_offset(
+        pid: Option<Pid>,
+        clock: crate::time::ClockId,
+        offset: crate::sys::time::TimeSpec,
+    ) -> Result<()> {
+        use std::io::Write;
+
+        let path = match pid {
+            Some(pid) => format!("/proc/{}/timens_offsets", pid),
+            None => "/proc/self/timens_offsets".to_owned(),
+        };
+        // The kernel wants the whole line in a single write
+        let line = format!(
+            "{} {} {}\n",
+            clock.as_raw(),
+            offset.tv_sec(),
+            offset.tv_nsec()
+        );
+        std::fs::OpenOptions::new()
+            .write(true)
+            .open(path)
+            .and_then(|mut f| f.write_all(line.as_bytes()))
+            .map_err(|e| Errno::try_from(e).unwrap_or(Errno::EIO))
+    }
+
+    libc_enum! {
+        /// A scheduling policy, for [`sched_setscheduler`].
+        ///
+        /// See [sched(7)](https://man7.org/linux/man-pages/man7/sched.7.html)
+        /// for what each one means.
+        #[repr(i32)]
+        #[non_exhaustive]
+        pub enum SchedPolicy {
+            /// The default time-sharing policy.
+            SCHED_OTHER,
+            /// First in, first out real-time policy.
+            SCHED_FIFO,
+            /// Round-robin real-time policy.
+            SCHED_RR,
+            /// Like `SCHED_OTHER`, for CPU-bound batch jobs.
+            SCHED_BATCH,
+            /// For jobs of even lower priority than `SCHED_OTHER` with the
+            /// highest nice value.
+            SCHED_IDLE,
+        }
+        impl TryFrom<i32>
+    }
+
+    /// The scheduling parameters of a thread.
+    #[repr(transparent)]
+    #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+    pub struct SchedParam(libc::sched_param);
+
+    impl SchedParam {
+        /// Parameters with the given static priority, which must be 0 for
+        /// the non-real-time policies and between
+        /// [`sched_get_priority_min`] and [`sched_get_priority_max`] for the
+        /// others.
+        pub fn new(priority: c_int) -> Self {
+            // musl's sched_param has more, unused, fields
+            let mut param: libc::sched_param = unsafe { mem::zeroed() };
+            param.sched_priority = priority;
+            SchedParam(param)
+        }
+
+        /// The static priority.
+        pub const fn priority(&self) -> c_int {
+            self.0.sched_priority
+        }
+    }
+
+    /// Set the scheduling policy and parameters of the thread `pid`, or of
+    /// the calling thread if `pid` is 0.
+    ///
+    /// Switching to a real-time policy, or raising the priority, requires
+    /// `CAP_SYS_NICE` or a sufficient `RLIMIT_RTPRIO`.
+    ///
+    /// See also [sched_setscheduler(2)](https://man7.org/linux/man-pages/man2/sched_setscheduler.2.html)
+    pub fn sched_setscheduler(
+        pid: Pid,
+        policy: SchedPolicy,
+        param: &SchedParam,
+    ) -> Result<()> {
+        let res = unsafe {
+            libc::sched_setscheduler(pid.into(), policy as c_int, &param.0)
+        };
+
+        Errno::result(res).map(drop)
+    }
+
+    /// Get the scheduling policy of the thread `pid`, or of the calling
+    /// thread if `pid` is 0.
+    ///
+    /// The `SCHED_RESET_ON_FORK` flag is ignored.  Policies unknown to
+    /// [`SchedPolicy`], such as `SCHED_DEADLINE`, are reported as `EINVAL`.
+    ///
+    /// See also [sched_getscheduler(2)](https://man7.org/linux/man-pages/man2/sched_getscheduler.2.html)
+    pub fn sched_getscheduler(pid: Pid) -> Result<SchedPolicy> {
+        let res = unsafe { libc::sched_getscheduler(pid.into()) };
+
+        SchedPolicy::try_from(Errno::result(res)? & !libc::SCHED_RESET_ON_FORK)
+    }
+
+    /// Get the scheduling parameters of the thread `pid`, or of the calling
+    /// thread if `pid` is 0.
+    ///
+    /// See also [sched_getparam(2)](https://man7.org/linux/man-pages/man2/sched_getparam.2.html)
+    pub fn sched_getparam(pid: Pid) -> Result<SchedParam> {
+        let mut param = SchedParam::new(0);
+        let res = unsafe { libc::sched_getparam(pid.into(), &mut param.0) };
+
+        Errno::result(res).map(|_| param)
+    }
+
+    /// The lowest static priority allowed for `policy`.
+    pub fn sched_get_priority_min(policy: SchedPolicy) -> Result<c_int> {
+        let res = unsafe { libc::sched_get_priority_min(policy as c_int) };
+
+        Errno::result(res)
+    }
+
+    /// The highest static priority allowed for `policy`.
+    pub fn sched_get_priority_max(policy: SchedPolicy) -> Result<c_int> {
+        let res = unsafe { libc::sched_get_priority_max(policy as c_int) };
+
+        Errno::result(res)
+    }
+
+    /// Determine the CPU and NUMA node the calling thread is running on,
+    /// as a `(cpu, node)` pair.
+    ///
+    /// The result may already be out of date when it is returned, unless the
+    /// thread's affinity is restricted to a single CPU.
+    ///
+    /// See also [getcpu(2)](https://man7.org/linux/man-pages/man2/getcpu.2.html)
+    pub fn getcpu() -> Result<(u32, u32)> {
+        let mut cpu: libc::c_uint = 0;
+        let mut node: libc::c_uint = 0;
+        let res = unsafe {
+            libc::syscall(
+                libc::SYS_getcpu,
+                &mut cpu as *mut libc::c_uint,
+                &mut node as *mut libc::c_uint,
+                std::ptr::null_mut::<c_void>(),
+            )
+        };
+
+        Errno::result(res).map(|_| (cpu, node))
+    }
 }
 
 #[cfg(any(
@@ -162,6 +378,7 @@ mod sched_affinity {
     use crate::unistd::Pid;
     use crate::Result;
     use std::mem;
+    use std::ops::Range;
 
     /// CpuSet represent a bit-mask of CPUs.
     /// CpuSets are used by sched_setaffinity and
@@ -185,6 +402,38 @@ mod sched_affinity {
             }
         }
 
+        /// Create a CpuSet containing only the CPU `cpu`.
+        pub fn from_cpu(cpu: usize) -> Result<CpuSet> {
+            let mut cpuset = CpuSet::new();
+            cpuset.set(cpu)?;
+            Ok(cpuset)
+        }
+
+        /// Create a CpuSet containing every CPU in `cpus`.
+        ///
+        /// Fails with `EINVAL` if the range extends past
+        /// [`CpuSet::count`].
+        pub fn from_range(cpus: Range<usize>) -> Result<CpuSet> {
+            if cpus.end > CpuSet::count() {
+                return Err(Errno::EINVAL);
+            }
+            let mut cpuset = CpuSet::new();
+            for cpu in cpus {
+                cpuset.set(cpu)?;
+            }
+            Ok(cpuset)
+        }
+
+        /// Return the number of CPUs in the CpuSet.
+        ///
+        /// Not to be confused with [`CpuSet::count`], which is the capacity
+        /// of the set.
+        pub fn num_set(&self) -> usize {
+            (0..CpuSet::count())
+                .filter(|&cpu| unsafe { libc::CPU_ISSET(cpu, &self.cpu_set) })
+                .count()
+        }
+
         /// Test to see if a CPU is in the CpuSet.
         /// `field` is the CPU id to test
         pub fn is_set(&self, field: usize) -> Result<bool> {
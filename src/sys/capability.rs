@@ -0,0 +1,213 @@
+//! Get and set the capabilities of threads
+//!
+//! Each thread has three sets of [`Capability`]s: the effective set the
+//! kernel checks, the permitted set that limits the effective set, and the
+//! inheritable set that is carried across `execve`.  For more documentation,
+//! please read
+//! [capabilities(7)](https://man7.org/linux/man-pages/man7/capabilities.7.html).
+
+use crate::errno::Errno;
+use crate::unistd::Pid;
+use crate::Result;
+use std::fmt;
+
+/// `_LINUX_CAPABILITY_VERSION_3`, for 64-bit capability sets.
+const LINUX_CAPABILITY_VERSION_3: u32 = 0x2008_0522;
+
+/// `struct __user_cap_header_struct` from `linux/capability.h`.
+#[repr(C)]
+struct CapHeader {
+    version: u32,
+    pid: libc::c_int,
+}
+
+/// `struct __user_cap_data_struct` from `linux/capability.h`.  Version 3
+/// uses two of them: one for the low and one for the high 32 bits.
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+struct CapData {
+    effective: u32,
+    permitted: u32,
+    inheritable: u32,
+}
+
+/// A capability, as described in
+/// [capabilities(7)](https://man7.org/linux/man-pages/man7/capabilities.7.html).
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[repr(u8)]
+#[non_exhaustive]
+pub enum Capability {
+    CAP_CHOWN = 0,
+    CAP_DAC_OVERRIDE = 1,
+    CAP_DAC_READ_SEARCH = 2,
+    CAP_FOWNER = 3,
+    CAP_FSETID = 4,
+    CAP_KILL = 5,
+    CAP_SETGID = 6,
+    CAP_SETUID = 7,
+    CAP_SETPCAP = 8,
+    CAP_LINUX_IMMUTABLE = 9,
+    CAP_NET_BIND_SERVICE = 10,
+    CAP_NET_BROADCAST = 11,
+    CAP_NET_ADMIN = 12,
+    CAP_NET_RAW = 13,
+    CAP_IPC_LOCK = 14,
+    CAP_IPC_OWNER = 15,
+    CAP_SYS_MODULE = 16,
+    CAP_SYS_RAWIO = 17,
+    CAP_SYS_CHROOT = 18,
+    CAP_SYS_PTRACE = 19,
+    CAP_SYS_PACCT = 20,
+    CAP_SYS_ADMIN = 21,
+    CAP_SYS_BOOT = 22,
+    CAP_SYS_NICE = 23,
+    CAP_SYS_RESOURCE = 24,
+    CAP_SYS_TIME = 25,
+    CAP_SYS_TTY_CONFIG = 26,
+    CAP_MKNOD = 27,
+    CAP_LEASE = 28,
+    CAP_AUDIT_WRITE = 29,
+    CAP_AUDIT_CONTROL = 30,
+    CAP_SETFCAP = 31,
+    CAP_MAC_OVERRIDE = 32,
+    CAP_MAC_ADMIN = 33,
+    CAP_SYSLOG = 34,
+    CAP_WAKE_ALARM = 35,
+    CAP_BLOCK_SUSPEND = 36,
+    CAP_AUDIT_READ = 37,
+    CAP_PERFMON = 38,
+    CAP_BPF = 39,
+    CAP_CHECKPOINT_RESTORE = 40,
+}
+
+/// A set of [`Capability`]s.
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+pub struct CapSet(u64);
+
+impl CapSet {
+    /// The empty set.
+    pub const fn empty() -> Self {
+        CapSet(0)
+    }
+
+    /// The set with the given bits, bit `n` standing for the capability
+    /// numbered `n`.  Bits the running kernel doesn't know are preserved.
+    pub const fn from_bits(bits: u64) -> Self {
+        CapSet(bits)
+    }
+
+    /// The bits of the set, as in [`CapSet::from_bits`].
+    pub const fn bits(&self) -> u64 {
+        self.0
+    }
+
+    /// Add a capability to the set.
+    pub fn add(&mut self, cap: Capability) {
+        self.0 |= 1 << cap as u8;
+    }
+
+    /// Remove a capability from the set.
+    pub fn remove(&mut self, cap: Capability) {
+        self.0 &= !(1 << cap as u8);
+    }
+
+    /// Whether the set contains a capability.
+    pub const fn contains(&self, cap: Capability) -> bool {
+        self.0 & (1 << cap as u8) != 0
+    }
+
+    /// Whether the set is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl fmt::Debug for CapSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CapSet({:#x})", self.0)
+    }
+}
+
+impl FromIterator<Capability> for CapSet {
+    fn from_iter<T: IntoIterator<Item = Capability>>(caps: T) -> Self {
+        let mut set = CapSet::empty();
+        for cap in caps {
+            set.add(cap);
+        }
+        set
+    }
+}
+
+/// The capability sets of a thread.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CapState {
+    /// The capabilities the kernel checks permissions against.
+    pub effective: CapSet,
+    /// The capabilities the thread may add to the effective and inheritable
+    /// sets.
+    pub permitted: CapSet,
+    /// The capabilities preserved across `execve`, subject to the file
+    /// capabilities and ambient set of the new program.
+    pub inheritable: CapSet,
+}
+
+fn header(pid: Option<Pid>) -> CapHeader {
+    CapHeader {
+        version: LINUX_CAPABILITY_VERSION_3,
+        pid: pid.map_or(0, Pid::as_raw),
+    }
+}
+
+/// Get the capability sets of the thread `pid`, or of the calling thread if
+/// `pid` is `None`.
+///
+/// See also [capget(2)](https://man7.org/linux/man-pages/man2/capget.2.html)
+pub fn capget(pid: Option<Pid>) -> Result<CapState> {
+    let mut hdr = header(pid);
+    let mut data = [CapData::default(); 2];
+    let res = unsafe {
+        libc::syscall(
+            libc::SYS_capget,
+            &mut hdr as *mut CapHeader,
+            data.as_mut_ptr(),
+        )
+    };
+    Errno::result(res)?;
+
+    let join = |f: fn(&CapData) -> u32| {
+        CapSet(u64::from(f(&data[0])) | u64::from(f(&data[1])) << 32)
+    };
+    Ok(CapState {
+        effective: join(|d| d.effective),
+        permitted: join(|d| d.permitted),
+        inheritable: join(|d| d.inheritable),
+    })
+}
+
+/// Set the capability sets of the thread `pid`, or of the calling thread if
+/// `pid` is `None`.
+///
+/// Only the calling thread's capabilities can be changed; any other `pid`
+/// fails with `EPERM`.  Capabilities may be dropped freely, but adding one to
+/// the permitted set, or adding one that isn't permitted to the other sets,
+/// also fails with `EPERM`.
+///
+/// See also [capset(2)](https://man7.org/linux/man-pages/man2/capset.2.html)
+pub fn capset(pid: Option<Pid>, state: &CapState) -> Result<()> {
+    let mut hdr = header(pid);
+    let split = |set: CapSet, i: usize| (set.0 >> (32 * i)) as u32;
+    let data = [0, 1].map(|i| CapData {
+        effective: split(state.effective, i),
+        permitted: split(state.permitted, i),
+        inheritable: split(state.inheritable, i),
+    });
+    let res = unsafe {
+        libc::syscall(
+            libc::SYS_capset,
+            &mut hdr as *mut CapHeader,
+            data.as_ptr(),
+        )
+    };
+    Errno::result(res).map(drop)
+}
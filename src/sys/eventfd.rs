@@ -1,6 +1,6 @@
 use crate::errno::Errno;
 use crate::Result;
-use std::os::unix::io::{FromRawFd, OwnedFd};
+use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd};
 
 libc_bitflags! {
     pub struct EfdFlags: libc::c_int {
@@ -15,3 +15,58 @@ pub fn eventfd(initval: libc::c_uint, flags: EfdFlags) -> Result<OwnedFd> {
 
     Errno::result(res).map(|r| unsafe { OwnedFd::from_raw_fd(r) })
 }
+
+/// An event counter, as created by [`eventfd`].
+///
+/// [`EventFd::write`] adds to the counter, and [`EventFd::read`] waits for
+/// it to be nonzero and takes it: all of it, or just 1 with
+/// `EFD_SEMAPHORE`.  The descriptor is readable whenever the counter is
+/// nonzero, so one thread can wake another one blocked in `poll` or
+/// `epoll_wait` by writing to it.
+#[derive(Debug)]
+pub struct EventFd(OwnedFd);
+
+impl EventFd {
+    /// Create an event counter with the initial value `initval`.
+    pub fn new(initval: libc::c_uint, flags: EfdFlags) -> Result<Self> {
+        eventfd(initval, flags).map(EventFd)
+    }
+
+    /// Take the counter's value, resetting it to 0, or, with
+    /// `EFD_SEMAPHORE`, decrement it and return 1.
+    ///
+    /// Blocks while the counter is 0, unless created with `EFD_NONBLOCK`, in
+    /// which case `EAGAIN` is returned.
+    pub fn read(&self) -> Result<u64> {
+        let mut buf = [0u8; 8];
+        crate::unistd::read(self.0.as_raw_fd(), &mut buf)?;
+        Ok(u64::from_ne_bytes(buf))
+    }
+
+    /// Add `value` to the counter.
+    ///
+    /// Blocks if that would overflow the maximum value of `u64::MAX - 1`,
+    /// unless created with `EFD_NONBLOCK`, in which case `EAGAIN` is
+    /// returned.  Writing `u64::MAX` fails with `EINVAL`.
+    pub fn write(&self, value: u64) -> Result<()> {
+        crate::unistd::write(self.0.as_raw_fd(), &value.to_ne_bytes()).map(drop)
+    }
+}
+
+impl AsFd for EventFd {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.0.as_fd()
+    }
+}
+
+impl AsRawFd for EventFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
+
+impl From<EventFd> for OwnedFd {
+    fn from(efd: EventFd) -> Self {
+        efd.0
+    }
+}
@@ -0,0 +1,149 @@
+//! Fast user-space locking
+//!
+//! For more documentation, please read
+//! [futex(2)](https://man7.org/linux/man-pages/man2/futex.2.html) and
+//! [get_robust_list(2)](https://man7.org/linux/man-pages/man2/get_robust_list.2.html).
+
+use crate::errno::Errno;
+use crate::sys::time::TimeSpec;
+use crate::unistd::Pid;
+use crate::Result;
+use libc::{c_int, c_void};
+use std::ptr;
+use std::sync::atomic::AtomicU32;
+
+libc_enum! {
+    /// Operations performed by [`futex`].
+    #[repr(i32)]
+    #[non_exhaustive]
+    pub enum FutexOp {
+        /// Sleep as long as the futex word still holds `val`, or until
+        /// the [`FutexArg::Timeout`] (a relative time) has passed.
+        FUTEX_WAIT,
+        /// Wake up at most `val` waiters of the futex word.
+        FUTEX_WAKE,
+        /// Wake up at most `val` waiters of `uaddr`, and requeue at most
+        /// [`FutexArg::Val2`] of the others onto `uaddr2`.
+        FUTEX_REQUEUE,
+        /// Like `FUTEX_REQUEUE`, but only if the word at `uaddr` still holds
+        /// `val3`.
+        FUTEX_CMP_REQUEUE,
+        /// Atomically modify `uaddr2` as encoded in `val3`, wake up at most
+        /// `val` waiters of `uaddr`, and, depending on the old value of
+        /// `uaddr2`, at most [`FutexArg::Val2`] waiters of `uaddr2` too.
+        FUTEX_WAKE_OP,
+        /// Like `FUTEX_WAIT`, but only woken by wake-ups whose bitset
+        /// intersects `val3`, and with an absolute timeout.
+        FUTEX_WAIT_BITSET,
+        /// Like `FUTEX_WAKE`, but only waking waiters whose bitset intersects
+        /// `val3`.
+        FUTEX_WAKE_BITSET,
+    }
+}
+
+libc_bitflags! {
+    /// Flags modifying a [`FutexOp`].
+    pub struct FutexFlags: c_int {
+        /// The futex is only used by threads of the calling process, which
+        /// lets the kernel skip some work.  Both sides of a handoff must
+        /// agree on this flag.
+        FUTEX_PRIVATE_FLAG;
+        /// Measure the timeout against `CLOCK_REALTIME` rather than
+        /// `CLOCK_MONOTONIC`.
+        FUTEX_CLOCK_REALTIME;
+    }
+}
+
+/// The fourth argument of [`futex`], whose meaning depends on the operation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FutexArg<'a> {
+    /// The timeout of the wait operations.
+    Timeout(&'a TimeSpec),
+    /// The integer `val2`: the number of waiters to requeue for
+    /// `FUTEX_REQUEUE` and `FUTEX_CMP_REQUEUE`, or to wake up on `uaddr2`
+    /// for `FUTEX_WAKE_OP`.
+    Val2(u32),
+}
+
+/// Wait on or wake up waiters of a futex word, as with `futex(2)`.
+///
+/// The meaning of `val`, `arg`, `uaddr2` and `val3` depends on `op`; see
+/// [`FutexOp`] and the man page.  The futex words are taken as atomics
+/// because other threads, or processes sharing the memory, may change them
+/// concurrently.  On success the return value is also specific to `op`: `0`
+/// for the wait operations, the number of woken waiters for the wake ones.
+///
+/// A `FUTEX_WAIT` whose word didn't hold `val` fails with `EAGAIN`, and one
+/// that timed out with `ETIMEDOUT`; both are part of normal operation.
+pub fn futex(
+    uaddr: &AtomicU32,
+    op: FutexOp,
+    flags: FutexFlags,
+    val: u32,
+    arg: Option<FutexArg<'_>>,
+    uaddr2: Option<&AtomicU32>,
+    val3: u32,
+) -> Result<i64> {
+    let res = unsafe {
+        libc::syscall(
+            libc::SYS_futex,
+            uaddr as *const AtomicU32,
+            op as c_int | flags.bits(),
+            val,
+            match arg {
+                None => ptr::null(),
+                Some(FutexArg::Timeout(t)) => {
+                    t.as_ref() as *const libc::timespec
+                }
+                // The kernel reads val2 from the pointer argument itself
+                Some(FutexArg::Val2(n)) => n as usize as *const libc::timespec,
+            },
+            uaddr2.map_or(ptr::null(), |u| u as *const AtomicU32),
+            val3,
+        )
+    };
+
+    Errno::result(res).map(|r| r as i64)
+}
+
+/// Get the robust futex list of a thread, as with `get_robust_list(2)`.
+///
+/// Returns the address of the thread's `struct robust_list_head` and the
+/// size the thread registered for it.  A `pid` of `0` means the calling
+/// thread.  The head lives in the target's address space; for another
+/// process it must be read with `ptrace` or
+/// [`process_vm_readv`](crate::sys::uio::process_vm_readv), and its entries
+/// walked from there.
+///
+/// Reading another thread's list requires `PTRACE_MODE_READ_REALCREDS`
+/// access to it.
+pub fn get_robust_list(pid: Pid) -> Result<(*mut c_void, usize)> {
+    let mut head: *mut c_void = ptr::null_mut();
+    let mut len: libc::size_t = 0;
+    let res = unsafe {
+        libc::syscall(
+            libc::SYS_get_robust_list,
+            libc::pid_t::from(pid),
+            &mut head as *mut *mut c_void,
+            &mut len as *mut libc::size_t,
+        )
+    };
+
+    Errno::result(res).map(|_| (head, len))
+}
+
+/// Register the calling thread's robust futex list, as with
+/// `set_robust_list(2)`.
+///
+/// # Safety
+///
+/// `head` must point to a `struct robust_list_head` of `len` bytes that stays
+/// valid for as long as the thread lives, as the kernel walks it when the
+/// thread exits.  The C library normally registers such a list for every
+/// thread already, and replacing it breaks robust mutexes.
+pub unsafe fn set_robust_list(head: *mut c_void, len: usize) -> Result<()> {
+    let res =
+        libc::syscall(libc::SYS_set_robust_list, head, len as libc::size_t);
+
+    Errno::result(res).map(drop)
+}
@@ -29,6 +29,7 @@ use crate::NixPath;
 use crate::Result;
 use cfg_if::cfg_if;
 use libc::{c_char, c_int};
+use std::collections::VecDeque;
 use std::ffi::{CStr, OsStr, OsString};
 use std::mem::{size_of, MaybeUninit};
 use std::os::unix::ffi::OsStrExt;
@@ -233,6 +234,45 @@ impl Inotify {
 
         Ok(events)
     }
+
+    /// Returns an iterator over the events of this inotify instance.
+    ///
+    /// Events are fetched with [`Inotify::read_events`] as needed.  If the
+    /// instance was initialized with IN_NONBLOCK, the iterator ends once no
+    /// more events are available; otherwise it blocks waiting for the next
+    /// one.
+    pub fn events(&self) -> InotifyEvents<'_> {
+        InotifyEvents {
+            inotify: self,
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+/// Iterator over the events of an [`Inotify`] instance.
+///
+/// Returned by [`Inotify::events`].
+#[derive(Debug)]
+pub struct InotifyEvents<'a> {
+    inotify: &'a Inotify,
+    pending: VecDeque<InotifyEvent>,
+}
+
+impl<'a> Iterator for InotifyEvents<'a> {
+    type Item = Result<InotifyEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(Ok(event));
+            }
+            match self.inotify.read_events() {
+                Ok(events) => self.pending.extend(events),
+                Err(Errno::EAGAIN) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
 }
 
 impl FromRawFd for Inotify {
@@ -0,0 +1,412 @@
+//! Asynchronous I/O through submission and completion rings shared with the
+//! kernel
+//!
+//! This module provides the raw [`io_uring_setup`] and [`io_uring_enter`]
+//! system calls, the structures they exchange with the kernel, and a minimal
+//! [`IoUring`] type that maps the rings and moves entries in and out of them.
+//! Building requests is left to the caller: fill in an [`IoUringSqe`] as
+//! described in [io_uring_enter(2)].
+//!
+//! For more documentation, please read [io_uring(7)].
+//!
+//! [io_uring(7)]: https://man7.org/linux/man-pages/man7/io_uring.7.html
+//! [io_uring_enter(2)]: https://man7.org/linux/man-pages/man2/io_uring_enter.2.html
+
+use crate::errno::Errno;
+use crate::Result;
+use libc::{c_int, c_uint, c_void};
+use std::mem;
+use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd};
+use std::ptr;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// `mmap` offset of the submission queue ring.
+const IORING_OFF_SQ_RING: libc::off_t = 0;
+/// `mmap` offset of the completion queue ring.
+const IORING_OFF_CQ_RING: libc::off_t = 0x8000000;
+/// `mmap` offset of the submission queue entries.
+const IORING_OFF_SQES: libc::off_t = 0x10000000;
+
+/// Opcode of the request that does nothing, see [`IoUringSqe::nop`].
+pub const IORING_OP_NOP: u8 = 0;
+
+bitflags::bitflags! {
+    /// Flags for [`io_uring_enter`].
+    pub struct EnterFlags: c_uint {
+        /// Wait for `min_complete` completions before returning.
+        const IORING_ENTER_GETEVENTS = 1 << 0;
+        /// Wake up the kernel submission thread of an `IORING_SETUP_SQPOLL`
+        /// ring.
+        const IORING_ENTER_SQ_WAKEUP = 1 << 1;
+        /// Wait for room in the submission queue of an `IORING_SETUP_SQPOLL`
+        /// ring.
+        const IORING_ENTER_SQ_WAIT = 1 << 2;
+    }
+}
+
+/// Offsets of the submission queue fields within the ring mapped at
+/// `IORING_OFF_SQ_RING`, as filled in by [`io_uring_setup`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[allow(missing_docs)]
+pub struct IoSqringOffsets {
+    pub head: u32,
+    pub tail: u32,
+    pub ring_mask: u32,
+    pub ring_entries: u32,
+    pub flags: u32,
+    pub dropped: u32,
+    pub array: u32,
+    pub resv1: u32,
+    pub user_addr: u64,
+}
+
+/// Offsets of the completion queue fields within the ring mapped at
+/// `IORING_OFF_CQ_RING`, as filled in by [`io_uring_setup`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[allow(missing_docs)]
+pub struct IoCqringOffsets {
+    pub head: u32,
+    pub tail: u32,
+    pub ring_mask: u32,
+    pub ring_entries: u32,
+    pub overflow: u32,
+    pub cqes: u32,
+    pub flags: u32,
+    pub resv1: u32,
+    pub user_addr: u64,
+}
+
+/// Parameters of a ring, passed to and filled in by [`io_uring_setup`].
+///
+/// This is `struct io_uring_params` from `linux/io_uring.h`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[allow(missing_docs)]
+pub struct IoUringParams {
+    pub sq_entries: u32,
+    pub cq_entries: u32,
+    pub flags: u32,
+    pub sq_thread_cpu: u32,
+    pub sq_thread_idle: u32,
+    pub features: u32,
+    pub wq_fd: u32,
+    pub resv: [u32; 3],
+    pub sq_off: IoSqringOffsets,
+    pub cq_off: IoCqringOffsets,
+}
+
+/// A submission queue entry.
+///
+/// This is `struct io_uring_sqe` from `linux/io_uring.h`, with each union
+/// represented by its first member.  The meaning of the fields depends on
+/// `opcode`; see [io_uring_enter(2)].
+///
+/// [io_uring_enter(2)]: https://man7.org/linux/man-pages/man2/io_uring_enter.2.html
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[allow(missing_docs)]
+pub struct IoUringSqe {
+    pub opcode: u8,
+    pub flags: u8,
+    pub ioprio: u16,
+    pub fd: i32,
+    pub off: u64,
+    pub addr: u64,
+    pub len: u32,
+    pub op_flags: u32,
+    pub user_data: u64,
+    pub buf_index: u16,
+    pub personality: u16,
+    pub splice_fd_in: i32,
+    pub addr3: u64,
+    pub __pad2: [u64; 1],
+}
+
+impl IoUringSqe {
+    /// A request that does nothing, and completes with the given
+    /// `user_data`.
+    pub fn nop(user_data: u64) -> Self {
+        IoUringSqe {
+            opcode: IORING_OP_NOP,
+            user_data,
+            ..Default::default()
+        }
+    }
+}
+
+/// A completion queue entry.
+///
+/// This is `struct io_uring_cqe` from `linux/io_uring.h`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct IoUringCqe {
+    /// The `user_data` of the completed request.
+    pub user_data: u64,
+    /// The result of the request: what the corresponding system call would
+    /// have returned, or a negated errno value.
+    pub res: i32,
+    /// Per-request flags.
+    pub flags: u32,
+}
+
+static_assertions::assert_eq_size!(IoUringParams, [u8; 120]);
+static_assertions::assert_eq_size!(IoUringSqe, [u8; 64]);
+static_assertions::assert_eq_size!(IoUringCqe, [u8; 16]);
+
+/// Create a ring with room for at least `entries` submissions, as with
+/// `io_uring_setup(2)`.
+///
+/// `params` selects the ring's configuration, and receives the actual queue
+/// sizes and the offsets needed to map the rings.
+///
+/// See also [io_uring_setup(2)](https://man7.org/linux/man-pages/man2/io_uring_setup.2.html)
+pub fn io_uring_setup(
+    entries: u32,
+    params: &mut IoUringParams,
+) -> Result<OwnedFd> {
+    let res = unsafe {
+        libc::syscall(
+            libc::SYS_io_uring_setup,
+            entries,
+            params as *mut IoUringParams,
+        )
+    };
+
+    Errno::result(res).map(|fd| unsafe { OwnedFd::from_raw_fd(fd as c_int) })
+}
+
+/// Submit `to_submit` queued entries and/or wait for `min_complete`
+/// completions, as with `io_uring_enter(2)`.
+///
+/// Returns the number of entries the kernel consumed from the submission
+/// queue.
+///
+/// See also [io_uring_enter(2)](https://man7.org/linux/man-pages/man2/io_uring_enter.2.html)
+pub fn io_uring_enter<Fd: AsFd>(
+    fd: Fd,
+    to_submit: u32,
+    min_complete: u32,
+    flags: EnterFlags,
+) -> Result<u32> {
+    let res = unsafe {
+        libc::syscall(
+            libc::SYS_io_uring_enter,
+            fd.as_fd().as_raw_fd(),
+            to_submit,
+            min_complete,
+            flags.bits(),
+            ptr::null::<libc::sigset_t>(),
+            0usize,
+        )
+    };
+
+    Errno::result(res).map(|r| r as u32)
+}
+
+/// A memory mapping that is unmapped on drop.
+#[derive(Debug)]
+struct Mmap {
+    addr: *mut c_void,
+    len: usize,
+}
+
+impl Mmap {
+    fn new(fd: BorrowedFd, len: usize, offset: libc::off_t) -> Result<Self> {
+        let addr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED | libc::MAP_POPULATE,
+                fd.as_raw_fd(),
+                offset,
+            )
+        };
+        if addr == libc::MAP_FAILED {
+            Err(Errno::last())
+        } else {
+            Ok(Mmap { addr, len })
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `offset` must be the offset of a suitably aligned `T` within the
+    /// mapping.
+    unsafe fn at<T>(&self, offset: u32) -> *mut T {
+        debug_assert!(offset as usize + mem::size_of::<T>() <= self.len);
+        (self.addr as *mut u8).add(offset as usize) as *mut T
+    }
+}
+
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(self.addr, self.len);
+        }
+    }
+}
+
+/// An io_uring instance with its rings mapped into memory.
+///
+/// ```no_run
+/// # use nix::sys::io_uring::{IoUring, IoUringSqe};
+/// # fn main() -> nix::Result<()> {
+/// let mut ring = IoUring::new(8)?;
+/// ring.push(IoUringSqe::nop(42))?;
+/// ring.submit_and_wait(1)?;
+/// let cqe = ring.pop().unwrap();
+/// assert_eq!(cqe.user_data, 42);
+/// assert_eq!(cqe.res, 0);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct IoUring {
+    fd: OwnedFd,
+    params: IoUringParams,
+    sq_ring: Mmap,
+    cq_ring: Mmap,
+    sqes: Mmap,
+    /// Entries pushed into the submission queue but not yet submitted.
+    pending: u32,
+}
+
+impl IoUring {
+    /// Create a ring with room for at least `entries` submissions and
+    /// default parameters, and map it.
+    pub fn new(entries: u32) -> Result<Self> {
+        Self::with_params(entries, IoUringParams::default())
+    }
+
+    /// Create a ring configured by `params` and map it.
+    pub fn with_params(
+        entries: u32,
+        mut params: IoUringParams,
+    ) -> Result<Self> {
+        let fd = io_uring_setup(entries, &mut params)?;
+        let sq_len = params.sq_off.array as usize
+            + params.sq_entries as usize * mem::size_of::<u32>();
+        let cq_len = params.cq_off.cqes as usize
+            + params.cq_entries as usize * mem::size_of::<IoUringCqe>();
+        let sqes_len =
+            params.sq_entries as usize * mem::size_of::<IoUringSqe>();
+
+        let sq_ring = Mmap::new(fd.as_fd(), sq_len, IORING_OFF_SQ_RING)?;
+        let cq_ring = Mmap::new(fd.as_fd(), cq_len, IORING_OFF_CQ_RING)?;
+        let sqes = Mmap::new(fd.as_fd(), sqes_len, IORING_OFF_SQES)?;
+
+        Ok(IoUring {
+            fd,
+            params,
+            sq_ring,
+            cq_ring,
+            sqes,
+            pending: 0,
+        })
+    }
+
+    /// The parameters the kernel reported for this ring.
+    pub fn params(&self) -> &IoUringParams {
+        &self.params
+    }
+
+    fn sq_atomic(&self, offset: u32) -> &AtomicU32 {
+        unsafe { &*self.sq_ring.at::<AtomicU32>(offset) }
+    }
+
+    fn cq_atomic(&self, offset: u32) -> &AtomicU32 {
+        unsafe { &*self.cq_ring.at::<AtomicU32>(offset) }
+    }
+
+    /// Queue a request, to be handed to the kernel by the next
+    /// [`IoUring::submit`].
+    ///
+    /// Fails with `EBUSY` if the submission queue is full.
+    ///
+    /// # Safety
+    ///
+    /// Any memory the request refers to, for example the buffer of a read,
+    /// must stay valid until its completion has been reaped.
+    pub unsafe fn push_unchecked(&mut self, sqe: IoUringSqe) -> Result<()> {
+        let off = self.params.sq_off;
+        let head = self.sq_atomic(off.head).load(Ordering::Acquire);
+        let tail = self.sq_atomic(off.tail).load(Ordering::Relaxed);
+        if tail.wrapping_sub(head) >= self.params.sq_entries {
+            return Err(Errno::EBUSY);
+        }
+        let mask = *self.sq_ring.at::<u32>(off.ring_mask);
+        let index = tail & mask;
+        *self.sqes.at::<IoUringSqe>(0).add(index as usize) = sqe;
+        *self.sq_ring.at::<u32>(off.array).add(index as usize) = index;
+        self.sq_atomic(off.tail)
+            .store(tail.wrapping_add(1), Ordering::Release);
+        self.pending += 1;
+        Ok(())
+    }
+
+    /// Queue a request that touches neither memory nor file descriptors.
+    ///
+    /// Only [`IORING_OP_NOP`] requests, as made by [`IoUringSqe::nop`],
+    /// qualify: most other operations read or write memory through one of
+    /// the fields, or, like `IORING_OP_CLOSE`, act on file descriptors the
+    /// caller may not own.  Fails with `EINVAL` for any other opcode, which
+    /// needs [`IoUring::push_unchecked`], and with `EBUSY` if the submission
+    /// queue is full.
+    pub fn push(&mut self, sqe: IoUringSqe) -> Result<()> {
+        if sqe.opcode != IORING_OP_NOP {
+            return Err(Errno::EINVAL);
+        }
+        unsafe { self.push_unchecked(sqe) }
+    }
+
+    /// Hand the queued requests to the kernel, without waiting.
+    ///
+    /// Returns the number of requests submitted.
+    pub fn submit(&mut self) -> Result<u32> {
+        self.submit_and_wait(0)
+    }
+
+    /// Hand the queued requests to the kernel, and wait until at least
+    /// `want` completions are available.
+    ///
+    /// Returns the number of requests submitted.
+    pub fn submit_and_wait(&mut self, want: u32) -> Result<u32> {
+        let flags = if want > 0 {
+            EnterFlags::IORING_ENTER_GETEVENTS
+        } else {
+            EnterFlags::empty()
+        };
+        let submitted = io_uring_enter(&self.fd, self.pending, want, flags)?;
+        self.pending -= submitted.min(self.pending);
+        Ok(submitted)
+    }
+
+    /// Take the oldest completion off the completion queue, if any.
+    pub fn pop(&mut self) -> Option<IoUringCqe> {
+        let off = self.params.cq_off;
+        let head = self.cq_atomic(off.head).load(Ordering::Relaxed);
+        let tail = self.cq_atomic(off.tail).load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+        let cqe = unsafe {
+            let mask = *self.cq_ring.at::<u32>(off.ring_mask);
+            *self
+                .cq_ring
+                .at::<IoUringCqe>(off.cqes)
+                .add((head & mask) as usize)
+        };
+        self.cq_atomic(off.head)
+            .store(head.wrapping_add(1), Ordering::Release);
+        Some(cqe)
+    }
+}
+
+impl AsFd for IoUring {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.fd.as_fd()
+    }
+}
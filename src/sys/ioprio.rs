@@ -0,0 +1,91 @@
+//! Get and set the I/O scheduling class and priority of processes
+//!
+//! For more documentation, please read
+//! [ioprio_set(2)](https://man7.org/linux/man-pages/man2/ioprio_set.2.html).
+
+use crate::errno::Errno;
+use crate::Result;
+use libc::c_int;
+
+const IOPRIO_CLASS_SHIFT: c_int = 13;
+const IOPRIO_PRIO_MASK: c_int = (1 << IOPRIO_CLASS_SHIFT) - 1;
+
+/// The kind of ID given to [`ioprio_get`] and [`ioprio_set`].
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum IoPrioWho {
+    /// A thread ID, or `0` for the calling thread.
+    IOPRIO_WHO_PROCESS = 1,
+    /// A process group ID, or `0` for the calling process's group.
+    IOPRIO_WHO_PGRP = 2,
+    /// A real user ID.
+    IOPRIO_WHO_USER = 3,
+}
+
+/// An I/O scheduling class.
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum IoPrioClass {
+    /// No class was set; the I/O priority is derived from the nice value.
+    IOPRIO_CLASS_NONE = 0,
+    /// Real time: always served first.  Setting it needs `CAP_SYS_ADMIN`.
+    IOPRIO_CLASS_RT = 1,
+    /// Best effort, the default.
+    IOPRIO_CLASS_BE = 2,
+    /// Only served when no other process wants the disk.
+    IOPRIO_CLASS_IDLE = 3,
+}
+
+impl IoPrioClass {
+    fn from_raw(class: c_int) -> Result<Self> {
+        match class {
+            0 => Ok(IoPrioClass::IOPRIO_CLASS_NONE),
+            1 => Ok(IoPrioClass::IOPRIO_CLASS_RT),
+            2 => Ok(IoPrioClass::IOPRIO_CLASS_BE),
+            3 => Ok(IoPrioClass::IOPRIO_CLASS_IDLE),
+            _ => Err(Errno::EINVAL),
+        }
+    }
+}
+
+/// Get the I/O scheduling class and priority level of `who`, as with
+/// `ioprio_get(2)`.
+///
+/// For a process group or user, this is the highest priority among its
+/// processes.  Levels range from `0` (highest) to `7` (lowest), and are only
+/// meaningful for the real time and best effort classes.
+pub fn ioprio_get(
+    which: IoPrioWho,
+    who: c_int,
+) -> Result<(IoPrioClass, c_int)> {
+    let res =
+        unsafe { libc::syscall(libc::SYS_ioprio_get, which as c_int, who) };
+    let prio = Errno::result(res)? as c_int;
+
+    let class = IoPrioClass::from_raw(prio >> IOPRIO_CLASS_SHIFT)?;
+    Ok((class, prio & IOPRIO_PRIO_MASK))
+}
+
+/// Set the I/O scheduling class and priority level of `who`, as with
+/// `ioprio_set(2)`.
+///
+/// `level` ranges from `0` (highest) to `7` (lowest) for the real time and
+/// best effort classes, and should be `0` otherwise.
+pub fn ioprio_set(
+    which: IoPrioWho,
+    who: c_int,
+    class: IoPrioClass,
+    level: c_int,
+) -> Result<()> {
+    if level & !IOPRIO_PRIO_MASK != 0 {
+        return Err(Errno::EINVAL);
+    }
+    let prio = (class as c_int) << IOPRIO_CLASS_SHIFT | level;
+    let res = unsafe {
+        libc::syscall(libc::SYS_ioprio_set, which as c_int, who, prio)
+    };
+
+    Errno::result(res).map(drop)
+}
@@ -0,0 +1,97 @@
+//! Issue memory barriers on a set of threads
+//!
+//! For more documentation, please read
+//! [membarrier(2)](https://man7.org/linux/man-pages/man2/membarrier.2.html).
+
+use crate::errno::Errno;
+use crate::Result;
+use bitflags::bitflags;
+use libc::c_int;
+
+/// Commands accepted by [`membarrier`].
+///
+/// The numeric values come from the kernel's `linux/membarrier.h`.
+#[allow(non_camel_case_types)]
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum MembarrierCmd {
+    /// Check which commands the running kernel supports.  Succeeds if
+    /// `membarrier` is available at all.
+    MEMBARRIER_CMD_QUERY = 0,
+    /// Make sure every thread of every process on the system has passed
+    /// through a state where all memory accesses to user-space addresses
+    /// match program order.  Slow, but needs no registration.
+    MEMBARRIER_CMD_GLOBAL = 1,
+    /// Like `MEMBARRIER_CMD_GLOBAL`, but much faster, and only affects
+    /// processes that registered with
+    /// `MEMBARRIER_CMD_REGISTER_GLOBAL_EXPEDITED`.
+    MEMBARRIER_CMD_GLOBAL_EXPEDITED = 2,
+    /// Register the calling process for `MEMBARRIER_CMD_GLOBAL_EXPEDITED`.
+    MEMBARRIER_CMD_REGISTER_GLOBAL_EXPEDITED = 4,
+    /// Issue a memory barrier on every running thread of the calling
+    /// process.  The process must have registered with
+    /// `MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED`.
+    MEMBARRIER_CMD_PRIVATE_EXPEDITED = 8,
+    /// Register the calling process for `MEMBARRIER_CMD_PRIVATE_EXPEDITED`.
+    MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED = 16,
+    /// Like `MEMBARRIER_CMD_PRIVATE_EXPEDITED`, but also make every running
+    /// thread of the calling process execute a core serializing instruction,
+    /// so that they observe code that was modified in memory.
+    MEMBARRIER_CMD_PRIVATE_EXPEDITED_SYNC_CORE = 32,
+    /// Register the calling process for
+    /// `MEMBARRIER_CMD_PRIVATE_EXPEDITED_SYNC_CORE`.
+    MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED_SYNC_CORE = 64,
+    /// Restart the restartable sequence critical sections of every running
+    /// thread of the calling process.
+    MEMBARRIER_CMD_PRIVATE_EXPEDITED_RSEQ = 128,
+    /// Register the calling process for
+    /// `MEMBARRIER_CMD_PRIVATE_EXPEDITED_RSEQ`.
+    MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED_RSEQ = 256,
+}
+
+bitflags! {
+    /// Flags accepted by [`membarrier`].
+    pub struct MembarrierFlags: libc::c_uint {
+        /// Only interrupt the CPU given by the `cpu_id` argument.  Only valid
+        /// with `MEMBARRIER_CMD_PRIVATE_EXPEDITED_RSEQ`.
+        const MEMBARRIER_CMD_FLAG_CPU = 1;
+    }
+}
+
+/// Issue a memory barrier, as with `membarrier(2)`.
+///
+/// `cpu_id` is only used with [`MembarrierFlags::MEMBARRIER_CMD_FLAG_CPU`].
+///
+/// A tracer that writes instructions into a multi-threaded tracee should
+/// keep in mind that this only affects the *calling* process; the tracee
+/// itself must register and issue `MEMBARRIER_CMD_PRIVATE_EXPEDITED_SYNC_CORE`
+/// (for example through an injected system call) to serialize its own cores.
+pub fn membarrier(
+    cmd: MembarrierCmd,
+    flags: MembarrierFlags,
+    cpu_id: c_int,
+) -> Result<()> {
+    let res = unsafe {
+        libc::syscall(libc::SYS_membarrier, cmd as c_int, flags.bits(), cpu_id)
+    };
+
+    Errno::result(res).map(drop)
+}
+
+/// Return the set of commands supported by the running kernel, as with
+/// `membarrier(MEMBARRIER_CMD_QUERY, 0, 0)`.
+///
+/// Each bit of the result corresponds to the value of a [`MembarrierCmd`].
+pub fn membarrier_query() -> Result<c_int> {
+    let res = unsafe {
+        libc::syscall(
+            libc::SYS_membarrier,
+            MembarrierCmd::MEMBARRIER_CMD_QUERY as c_int,
+            0,
+            0,
+        )
+    };
+
+    Errno::result(res).map(|r| r as c_int)
+}
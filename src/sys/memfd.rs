@@ -1,9 +1,10 @@
 //! Interfaces for managing memory-backed files.
 
 use cfg_if::cfg_if;
-use std::os::unix::io::{FromRawFd, OwnedFd, RawFd};
+use std::os::unix::io::{AsFd, AsRawFd, FromRawFd, OwnedFd, RawFd};
 
 use crate::errno::Errno;
+use crate::fcntl::{fcntl, FcntlArg, SealFlag};
 use crate::Result;
 use std::ffi::CStr;
 
@@ -62,3 +63,43 @@ pub fn memfd_create(name: &CStr, flags: MemFdCreateFlag) -> Result<OwnedFd> {
 
     Errno::result(res).map(|r| unsafe { OwnedFd::from_raw_fd(r as RawFd) })
 }
+
+/// Get the seals applied to a memfd (see
+/// [`memfd_create(2)`](https://man7.org/linux/man-pages/man2/memfd_create.2.html)).
+///
+/// A memfd created without
+/// [`MFD_ALLOW_SEALING`](MemFdCreateFlag::MFD_ALLOW_SEALING) reports
+/// `F_SEAL_SEAL`.
+pub fn memfd_get_seals<Fd: AsFd>(fd: Fd) -> Result<SealFlag> {
+    fcntl(fd.as_fd().as_raw_fd(), FcntlArg::F_GET_SEALS)
+        .map(SealFlag::from_bits_truncate)
+}
+
+/// Add `seals` to a memfd, restricting the operations allowed on it from
+/// now on.
+///
+/// Returns `EINVAL` if the memfd can't be sealed any further, either because
+/// it was created without
+/// [`MFD_ALLOW_SEALING`](MemFdCreateFlag::MFD_ALLOW_SEALING) or because
+/// `F_SEAL_SEAL` was added to it, as well as if `fd` isn't a memfd.
+/// `F_SEAL_WRITE` fails with `EBUSY` while writable shared mappings of the
+/// memfd exist; `F_SEAL_FUTURE_WRITE` doesn't have that restriction.
+///
+/// # Example
+///
+/// ```
+/// # use nix::fcntl::SealFlag;
+/// # use nix::sys::memfd::*;
+/// # use std::ffi::CStr;
+/// let name = CStr::from_bytes_with_nul(b"ring\0").unwrap();
+/// let fd = memfd_create(name, MemFdCreateFlag::MFD_ALLOW_SEALING).unwrap();
+/// memfd_add_seals(&fd, SealFlag::F_SEAL_SHRINK | SealFlag::F_SEAL_GROW)
+///     .unwrap();
+/// ```
+pub fn memfd_add_seals<Fd: AsFd>(fd: Fd, seals: SealFlag) -> Result<()> {
+    let fd = fd.as_fd();
+    if memfd_get_seals(fd)?.contains(SealFlag::F_SEAL_SEAL) {
+        return Err(Errno::EINVAL);
+    }
+    fcntl(fd.as_raw_fd(), FcntlArg::F_ADD_SEALS(seals)).map(drop)
+}
@@ -12,6 +12,12 @@ feature! {
     pub mod aio;
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+feature! {
+    #![feature = "process"]
+    pub mod capability;
+}
+
 feature! {
     #![feature = "event"]
 
@@ -32,6 +38,18 @@ feature! {
     pub mod eventfd;
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+feature! {
+    #![feature = "process"]
+    pub mod futex;
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+feature! {
+    #![feature = "io_uring"]
+    pub mod io_uring;
+}
+
 #[cfg(any(
     target_os = "android",
     target_os = "dragonfly",
@@ -49,6 +67,18 @@ feature! {
 #[macro_use]
 pub mod ioctl;
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+feature! {
+    #![feature = "resource"]
+    pub mod ioprio;
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+feature! {
+    #![feature = "process"]
+    pub mod membarrier;
+}
+
 #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
 feature! {
     #![feature = "fs"]
@@ -72,6 +102,18 @@ feature! {
     pub mod pthread;
 }
 
+#[cfg(target_os = "linux")]
+feature! {
+    #![feature = "process"]
+    pub mod pidfd;
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+feature! {
+    #![feature = "process"]
+    pub mod prctl;
+}
+
 #[cfg(any(
     target_os = "android",
     target_os = "dragonfly",
@@ -168,6 +210,12 @@ feature! {
     pub mod statvfs;
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+feature! {
+    #![feature = "fs"]
+    pub mod statx;
+}
+
 #[cfg(any(target_os = "android", target_os = "linux"))]
 #[cfg_attr(docsrs, doc(cfg(all())))]
 #[allow(missing_docs)]
@@ -0,0 +1,56 @@
+//! Refer to processes through file descriptors
+//!
+//! A pidfd keeps referring to the same process for as long as it is open,
+//! even after the process exits and its pid is reused.  For more
+//! documentation, please read
+//! [pidfd_open(2)](https://man7.org/linux/man-pages/man2/pidfd_open.2.html).
+
+use crate::errno::Errno;
+use crate::unistd::Pid;
+use crate::Result;
+use bitflags::bitflags;
+use std::os::unix::io::{AsFd, AsRawFd, FromRawFd, OwnedFd};
+
+bitflags! {
+    /// Flags accepted by [`pidfd_open`].
+    pub struct PidfdFlags: libc::c_uint {
+        /// Open the pidfd in non-blocking mode, so that `waitid` with
+        /// [`Id::PIDFd`](crate::sys::wait::Id::PIDFd) fails with `EAGAIN`
+        /// instead of blocking if the process hasn't exited yet.
+        const PIDFD_NONBLOCK = libc::O_NONBLOCK as libc::c_uint;
+    }
+}
+
+/// Obtain a file descriptor that refers to the process `pid`.
+///
+/// The pidfd has the close-on-exec flag set.  Requires Linux 5.3 or later.
+pub fn pidfd_open(pid: Pid, flags: PidfdFlags) -> Result<OwnedFd> {
+    let res = unsafe {
+        libc::syscall(libc::SYS_pidfd_open, pid.as_raw(), flags.bits())
+    };
+    Errno::result(res).map(|fd| unsafe { OwnedFd::from_raw_fd(fd as _) })
+}
+
+/// Get the pid of the process `pidfd` refers to, as shown in the pid
+/// namespace of the calling process.
+///
+/// The pid is read from the `Pid:` line of `/proc/self/fdinfo/<pidfd>`.
+/// Returns `ESRCH` if the process has exited and been reaped, or isn't
+/// visible in the caller's pid namespace, and `EINVAL` if `pidfd` isn't a
+/// pidfd.  Reporting `ESRCH` requires Linux 5.15 or later; older kernels
+/// keep returning the pid.
+pub fn pidfd_get_pid<Fd: AsFd>(pidfd: Fd) -> Result<Pid> {
+    let path = format!("/proc/self/fdinfo/{}", pidfd.as_fd().as_raw_fd());
+    let fdinfo = std::fs::read_to_string(path)
+        .map_err(|e| Errno::try_from(e).unwrap_or(Errno::EIO))?;
+    let pid = fdinfo
+        .lines()
+        .find_map(|line| line.strip_prefix("Pid:"))
+        .and_then(|pid| pid.trim().parse::<libc::pid_t>().ok())
+        .ok_or(Errno::EINVAL)?;
+    match pid {
+        // The process is gone, or lives in a pid namespace we can't see
+        -1 | 0 => Err(Errno::ESRCH),
+        pid => Ok(Pid::from_raw(pid)),
+    }
+}
@@ -0,0 +1,166 @@
+//! prctl is a Linux-only API for performing operations on a process or thread.
+//!
+//! Note that careless use of some prctl() operations can confuse the user-space run-time
+//! environment, so these operations should be used with care.
+//!
+//! For more documentation, please read [prctl(2)](https://man7.org/linux/man-pages/man2/prctl.2.html).
+
+use crate::errno::Errno;
+use crate::sys::signal::Signal;
+use crate::Result;
+use libc::{c_int, c_ulong};
+
+macro_rules! prctl_set_bool {
+    ($set:expr, $value:expr) => {{
+        let res = unsafe { libc::prctl($set, $value as c_ulong, 0, 0, 0) };
+        Errno::result(res).map(drop)
+    }};
+}
+
+macro_rules! prctl_get_bool {
+    ($get:expr) => {{
+        let res = unsafe { libc::prctl($get, 0, 0, 0, 0) };
+        Errno::result(res).map(|res| res != 0)
+    }};
+}
+
+/// Set the "dumpable" attribute of the calling process.
+///
+/// The attribute decides whether the process produces a core dump and,
+/// more importantly for debuggers, whether a process with the same UID may
+/// `ptrace(PTRACE_ATTACH)` it.  The kernel clears it on its own when the
+/// process changes credentials, for example by executing a set-user-ID
+/// program, so a launcher that wants its child to stay traceable should set
+/// it again after adjusting privileges.
+pub fn set_dumpable(attribute: bool) -> Result<()> {
+    prctl_set_bool!(libc::PR_SET_DUMPABLE, attribute)
+}
+
+/// Get the "dumpable" attribute of the calling process.
+///
+/// See [`set_dumpable`].
+pub fn get_dumpable() -> Result<bool> {
+    prctl_get_bool!(libc::PR_GET_DUMPABLE)
+}
+
+/// Set the "no_new_privs" attribute of the calling thread.
+///
+/// Once set, `execve` no longer grants privileges: set-user-ID and
+/// set-group-ID bits and file capabilities are ignored.  This is required
+/// before an unprivileged thread can install a seccomp filter.  The attribute
+/// is inherited by children and preserved across `execve`, and it can't be
+/// unset.
+pub fn set_no_new_privs() -> Result<()> {
+    prctl_set_bool!(libc::PR_SET_NO_NEW_PRIVS, 1)
+}
+
+/// Get the "no_new_privs" attribute of the calling thread.
+///
+/// See [`set_no_new_privs`].
+pub fn get_no_new_privs() -> Result<bool> {
+    prctl_get_bool!(libc::PR_GET_NO_NEW_PRIVS)
+}
+
+/// Set the "child subreaper" attribute of the calling process.
+///
+/// When a process with the attribute set has a descendant orphaned, the
+/// orphan is reparented to it, rather than to `init`, so that it can collect
+/// the orphan's exit status with `waitpid`.  This lets a supervisor keep
+/// track of the whole tree of processes it launched, even if intermediate
+/// processes exit early.
+pub fn set_child_subreaper(attribute: bool) -> Result<()> {
+    prctl_set_bool!(libc::PR_SET_CHILD_SUBREAPER, attribute)
+}
+
+/// Get the "child subreaper" attribute of the calling process.
+///
+/// See [`set_child_subreaper`].
+pub fn get_child_subreaper() -> Result<bool> {
+    let mut attribute: c_int = 0;
+    let res = unsafe {
+        libc::prctl(libc::PR_GET_CHILD_SUBREAPER, &mut attribute, 0, 0, 0)
+    };
+    Errno::result(res).map(|_| attribute != 0)
+}
+
+/// Set the signal the calling process receives when its parent dies, or
+/// clear it with `None`.
+///
+/// A child that should not outlive the process that launched it calls this
+/// right after `fork`.  The signal is sent when the thread that forked the
+/// child exits, which may be before the parent process as a whole does.  The
+/// setting is cleared by `fork` and by executing a set-user-ID program.  If
+/// the parent already died before the call, no signal is sent, so check
+/// [`getppid`](crate::unistd::getppid) afterwards.
+pub fn set_pdeathsig<T: Into<Option<Signal>>>(signal: T) -> Result<()> {
+    let sig = signal.into().map_or(0, |sig| sig as c_int);
+    let res =
+        unsafe { libc::prctl(libc::PR_SET_PDEATHSIG, sig as c_ulong, 0, 0, 0) };
+    Errno::result(res).map(drop)
+}
+
+/// Get the signal the calling process receives when its parent dies.
+///
+/// See [`set_pdeathsig`].
+pub fn get_pdeathsig() -> Result<Option<Signal>> {
+    let mut sig: c_int = 0;
+    let res = unsafe { libc::prctl(libc::PR_GET_PDEATHSIG, &mut sig, 0, 0, 0) };
+    Errno::result(res)?;
+    match sig {
+        0 => Ok(None),
+        sig => Signal::try_from(sig).map(Some),
+    }
+}
+
+/// Get the `clear_child_tid` address of the calling thread.
+///
+/// When the thread exits, the kernel writes zero to this address and wakes
+/// futex waiters on it, which is how thread libraries implement joining.
+/// It is set by `clone` with `CLONE_CHILD_CLEARTID` or by
+/// `set_tid_address(2)`.
+///
+/// This requires a kernel built with `CONFIG_CHECKPOINT_RESTORE` and fails
+/// with `EINVAL` otherwise.  Some configurations also restrict it to callers
+/// with `CAP_SYS_ADMIN`.
+pub fn get_tid_address() -> Result<*mut libc::c_void> {
+    let mut addr: *mut libc::c_void = std::ptr::null_mut();
+    let res =
+        unsafe { libc::prctl(libc::PR_GET_TID_ADDRESS, &mut addr, 0, 0, 0) };
+    Errno::result(res).map(|_| addr)
+}
+
+/// The size of a thread name, including the terminating NUL byte.
+const TASK_COMM_LEN: usize = 16;
+
+/// Set the name of the calling thread, as shown in `/proc/self/task/*/comm`
+/// and by tools such as `ps` and `top`.
+///
+/// Names are limited to 15 bytes; a longer `name` is truncated to the
+/// longest prefix that fits and ends on a character boundary.  Returns
+/// `EINVAL` if `name` contains a NUL byte.
+pub fn set_name(name: &str) -> Result<()> {
+    if name.contains('\0') {
+        return Err(Errno::EINVAL);
+    }
+    let mut len = name.len().min(TASK_COMM_LEN - 1);
+    while !name.is_char_boundary(len) {
+        len -= 1;
+    }
+    let mut buf = [0u8; TASK_COMM_LEN];
+    buf[..len].copy_from_slice(&name.as_bytes()[..len]);
+    let res = unsafe { libc::prctl(libc::PR_SET_NAME, buf.as_ptr(), 0, 0, 0) };
+    Errno::result(res).map(drop)
+}
+
+/// Get the name of the calling thread.
+///
+/// See [`set_name`].  A name that isn't valid UTF-8, as can be set by other
+/// means, is converted lossily.
+pub fn get_name() -> Result<String> {
+    let mut buf = [0u8; TASK_COMM_LEN];
+    let res =
+        unsafe { libc::prctl(libc::PR_GET_NAME, buf.as_mut_ptr(), 0, 0, 0) };
+    Errno::result(res)?;
+    let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    Ok(String::from_utf8_lossy(&buf[..len]).into_owned())
+}
@@ -2,11 +2,15 @@
 
 use crate::errno::Errno;
 use crate::sys::signal::Signal;
+use crate::sys::wait::{waitpid, WaitPidFlag, WaitStatus};
 use crate::unistd::Pid;
 use crate::Result;
 use cfg_if::cfg_if;
 use libc::{self, c_long, c_void, siginfo_t};
-use std::{mem, ptr};
+use std::convert::TryFrom;
+use std::os::unix::io::{AsRawFd, OwnedFd};
+use std::time::{Duration, Instant};
+use std::{fmt, mem, ptr, thread};
 
 pub type AddressType = *mut ::libc::c_void;
 
@@ -155,6 +159,15 @@ libc_enum! {
         /// or when a new child is attached.
         PTRACE_EVENT_STOP,
     }
+    impl TryFrom<i32>
+}
+
+impl Event {
+    /// The event reported by `status`, if it is a
+    /// [`WaitStatus::PtraceEvent`] for a known event.
+    pub fn from_wait_status(status: &WaitStatus) -> Option<Event> {
+        status.ptrace_event().map(|(_, _, event)| event)
+    }
 }
 
 #[cfg(all(target_os = "linux", target_env = "gnu"))]
@@ -261,6 +274,85 @@ libc_bitflags! {
     }
 }
 
+/// A ptrace failure, along with the request that caused it.
+///
+/// This is returned by the `_ctx` variants of the ptrace functions, such as
+/// [`cont_ctx`].  It distinguishes the most common failure modes, so that
+/// tracers can report something more useful than a bare [`Errno`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum TracerError {
+    /// The tracee doesn't exist, isn't traced by the caller, or isn't
+    /// stopped as required by the request (`ESRCH`).
+    NotStopped {
+        /// The request that failed.
+        request: Request,
+    },
+    /// The caller isn't allowed to trace the process (`EPERM`).
+    PermissionDenied {
+        /// The request that failed.
+        request: Request,
+    },
+    /// The request, an option, or a signal was invalid (`EINVAL`).
+    InvalidArgument {
+        /// The request that failed.
+        request: Request,
+    },
+    /// Any other failure.
+    Other {
+        /// The request that failed.
+        request: Request,
+        /// The underlying error.
+        errno: Errno,
+    },
+}
+
+impl TracerError {
+    /// Classify `errno`, as returned by `request`.
+    pub const fn new(request: Request, errno: Errno) -> Self {
+        match errno {
+            Errno::ESRCH => TracerError::NotStopped { request },
+            Errno::EPERM => TracerError::PermissionDenied { request },
+            Errno::EINVAL => TracerError::InvalidArgument { request },
+            errno => TracerError::Other { request, errno },
+        }
+    }
+
+    /// Returns the request that failed.
+    pub const fn request(&self) -> Request {
+        match *self {
+            TracerError::NotStopped { request }
+            | TracerError::PermissionDenied { request }
+            | TracerError::InvalidArgument { request }
+            | TracerError::Other { request, .. } => request,
+        }
+    }
+
+    /// Returns the underlying [`Errno`].
+    pub const fn errno(&self) -> Errno {
+        match *self {
+            TracerError::NotStopped { .. } => Errno::ESRCH,
+            TracerError::PermissionDenied { .. } => Errno::EPERM,
+            TracerError::InvalidArgument { .. } => Errno::EINVAL,
+            TracerError::Other { errno, .. } => errno,
+        }
+    }
+}
+
+impl fmt::Display for TracerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} failed: {}", self.request(), self.errno())
+    }
+}
+
+impl std::error::Error for TracerError {}
+
+impl From<TracerError> for Errno {
+    fn from(err: TracerError) -> Self {
+        err.errno()
+    }
+}
+
 fn ptrace_peek(
     request: Request,
     pid: Pid,
@@ -315,6 +407,314 @@ pub fn setregs(pid: Pid, regs: user_regs_struct) -> Result<()> {
     Errno::result(res).map(drop)
 }
 
+/// A tracee's user registers, with accessors that don't depend on the
+/// architecture's register names.
+///
+/// This is a wrapper around [`libc::user_regs_struct`].  The raw struct is
+/// still reachable through [`UserRegs::as_raw`] and the `From` conversions,
+/// for example to hand modified registers back to [`setregs`].
+#[cfg(all(
+    target_os = "linux",
+    any(
+        all(
+            target_arch = "x86_64",
+            any(target_env = "gnu", target_env = "musl")
+        ),
+        all(target_arch = "x86", target_env = "gnu")
+    )
+))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[repr(transparent)]
+pub struct UserRegs(user_regs_struct);
+
+#[cfg(all(
+    target_os = "linux",
+    any(
+        all(
+            target_arch = "x86_64",
+            any(target_env = "gnu", target_env = "musl")
+        ),
+        all(target_arch = "x86", target_env = "gnu")
+    )
+))]
+impl UserRegs {
+    /// The instruction pointer (`rip` or `eip`).
+    pub fn ip(&self) -> u64 {
+        #[cfg(target_arch = "x86_64")]
+        let ip = self.0.rip;
+        #[cfg(target_arch = "x86")]
+        let ip = self.0.eip as u32 as u64;
+        ip
+    }
+
+    /// Set the instruction pointer (`rip` or `eip`).
+    pub fn set_ip(&mut self, ip: u64) {
+        #[cfg(target_arch = "x86_64")]
+        {
+            self.0.rip = ip;
+        }
+        #[cfg(target_arch = "x86")]
+        {
+            self.0.eip = ip as u32 as libc::c_long;
+        }
+    }
+
+    /// The stack pointer (`rsp` or `esp`).
+    pub fn sp(&self) -> u64 {
+        #[cfg(target_arch = "x86_64")]
+        let sp = self.0.rsp;
+        #[cfg(target_arch = "x86")]
+        let sp = self.0.esp as u32 as u64;
+        sp
+    }
+
+    /// Set the stack pointer (`rsp` or `esp`).
+    pub fn set_sp(&mut self, sp: u64) {
+        #[cfg(target_arch = "x86_64")]
+        {
+            self.0.rsp = sp;
+        }
+        #[cfg(target_arch = "x86")]
+        {
+            self.0.esp = sp as u32 as libc::c_long;
+        }
+    }
+
+    /// The number of the system call the tracee is in (`orig_rax` or
+    /// `orig_eax`), or `-1` if it isn't in one.
+    pub fn syscall_number(&self) -> i64 {
+        #[cfg(target_arch = "x86_64")]
+        let nr = self.0.orig_rax as i64;
+        #[cfg(target_arch = "x86")]
+        let nr = self.0.orig_eax as i64;
+        nr
+    }
+
+    /// Change the number of the system call the tracee is about to make.
+    ///
+    /// At a syscall-entry-stop, this makes the kernel run system call `nr`
+    /// instead.  Setting it to `-1` skips the call.
+    pub fn set_syscall_number(&mut self, nr: i64) {
+        #[cfg(target_arch = "x86_64")]
+        {
+            self.0.orig_rax = nr as u64;
+        }
+        #[cfg(target_arch = "x86")]
+        {
+            self.0.orig_eax = nr as libc::c_long;
+        }
+    }
+
+    /// The arguments of the system call the tracee is in, in order.
+    ///
+    /// These are `rdi`, `rsi`, `rdx`, `r10`, `r8` and `r9` on x86_64, and
+    /// `ebx`, `ecx`, `edx`, `esi`, `edi` and `ebp` on x86, where they are
+    /// zero-extended.
+    pub fn syscall_args(&self) -> [u64; 6] {
+        #[cfg(target_arch = "x86_64")]
+        let args = [
+            self.0.rdi, self.0.rsi, self.0.rdx, self.0.r10, self.0.r8,
+            self.0.r9,
+        ];
+        #[cfg(target_arch = "x86")]
+        let args = [
+            self.0.ebx, self.0.ecx, self.0.edx, self.0.esi, self.0.edi,
+            self.0.ebp,
+        ]
+        .map(|r| r as u32 as u64);
+        args
+    }
+
+    /// Change the arguments of the system call the tracee is about to make;
+    /// see [`UserRegs::syscall_args`].  On x86, the arguments are truncated
+    /// to 32 bits.
+    pub fn set_syscall_args(&mut self, args: [u64; 6]) {
+        #[cfg(target_arch = "x86_64")]
+        {
+            self.0.rdi = args[0];
+            self.0.rsi = args[1];
+            self.0.rdx = args[2];
+            self.0.r10 = args[3];
+            self.0.r8 = args[4];
+            self.0.r9 = args[5];
+        }
+        #[cfg(target_arch = "x86")]
+        {
+            let arg = |i: usize| args[i] as u32 as libc::c_long;
+            self.0.ebx = arg(0);
+            self.0.ecx = arg(1);
+            self.0.edx = arg(2);
+            self.0.esi = arg(3);
+            self.0.edi = arg(4);
+            self.0.ebp = arg(5);
+        }
+    }
+
+    /// The system call return value register (`rax` or `eax`).
+    ///
+    /// At a syscall-exit-stop, values from `-4095` to `-1` are negated
+    /// errno values.
+    pub fn return_value(&self) -> i64 {
+        #[cfg(target_arch = "x86_64")]
+        let ret = self.0.rax as i64;
+        #[cfg(target_arch = "x86")]
+        let ret = self.0.eax as i64;
+        ret
+    }
+
+    /// Set the system call return value register (`rax` or `eax`).
+    ///
+    /// At a syscall-exit-stop, this changes the value the tracee sees
+    /// returned; pass a negated errno value to make the call fail.
+    pub fn set_return_value(&mut self, ret: i64) {
+        #[cfg(target_arch = "x86_64")]
+        {
+            self.0.rax = ret as u64;
+        }
+        #[cfg(target_arch = "x86")]
+        {
+            self.0.eax = ret as libc::c_long;
+        }
+    }
+
+    /// Borrow the underlying `libc::user_regs_struct`.
+    pub fn as_raw(&self) -> &user_regs_struct {
+        &self.0
+    }
+}
+
+#[cfg(all(
+    target_os = "linux",
+    any(
+        all(
+            target_arch = "x86_64",
+            any(target_env = "gnu", target_env = "musl")
+        ),
+        all(target_arch = "x86", target_env = "gnu")
+    )
+))]
+impl From<user_regs_struct> for UserRegs {
+    fn from(regs: user_regs_struct) -> Self {
+        UserRegs(regs)
+    }
+}
+
+#[cfg(all(
+    target_os = "linux",
+    any(
+        all(
+            target_arch = "x86_64",
+            any(target_env = "gnu", target_env = "musl")
+        ),
+        all(target_arch = "x86", target_env = "gnu")
+    )
+))]
+impl From<UserRegs> for user_regs_struct {
+    fn from(regs: UserRegs) -> Self {
+        regs.0
+    }
+}
+
+/// Get user registers as a [`UserRegs`], as with `ptrace(PTRACE_GETREGS, ...)`
+#[cfg(all(
+    target_os = "linux",
+    any(
+        all(
+            target_arch = "x86_64",
+            any(target_env = "gnu", target_env = "musl")
+        ),
+        all(target_arch = "x86", target_env = "gnu")
+    )
+))]
+pub fn getregs_portable(pid: Pid) -> Result<UserRegs> {
+    getregs(pid).map(UserRegs)
+}
+
+/// Change the number of the system call the tracee is about to make, as with
+/// [`UserRegs::set_syscall_number`].
+///
+/// This only has an effect at a syscall-entry-stop, before the kernel looks
+/// at the number; use `getsyscallinfo` or keep track of entries and exits
+/// to tell the stops apart.  Together with [`set_syscall_args`], this lets a
+/// tracer run a different system call than the one the tracee asked for.
+#[cfg(all(
+    target_os = "linux",
+    any(
+        all(
+            target_arch = "x86_64",
+            any(target_env = "gnu", target_env = "musl")
+        ),
+        all(target_arch = "x86", target_env = "gnu")
+    )
+))]
+pub fn set_syscall_number(pid: Pid, nr: i64) -> Result<()> {
+    let mut regs = getregs_portable(pid)?;
+    regs.set_syscall_number(nr);
+    setregs(pid, regs.into())
+}
+
+/// Change the arguments of the system call the tracee is about to make, as
+/// with [`UserRegs::set_syscall_args`].
+///
+/// Like [`set_syscall_number`], this is only meaningful at a
+/// syscall-entry-stop.  Arguments that are pointers must point into the
+/// tracee's memory.
+#[cfg(all(
+    target_os = "linux",
+    any(
+        all(
+            target_arch = "x86_64",
+            any(target_env = "gnu", target_env = "musl")
+        ),
+        all(target_arch = "x86", target_env = "gnu")
+    )
+))]
+pub fn set_syscall_args(pid: Pid, args: [u64; 6]) -> Result<()> {
+    let mut regs = getregs_portable(pid)?;
+    regs.set_syscall_args(args);
+    setregs(pid, regs.into())
+}
+
+/// Skip the system call the tracee is about to make, making it return
+/// `return_value` instead.
+///
+/// The tracee must be at a syscall-entry-stop.  Its system call number is
+/// replaced with `-1`, which the kernel doesn't run, and it is resumed with
+/// [`syscall`] up to the matching syscall-exit-stop, where its return value
+/// is set.  The tracee is left in that stop.  Pass a negated errno value,
+/// such as `-(libc::EPERM as i64)`, to make the call fail.
+///
+/// Returns `EIO` if the tracee stops for another reason, or exits, before
+/// reaching the syscall-exit-stop.
+#[cfg(all(
+    target_os = "linux",
+    any(
+        all(
+            target_arch = "x86_64",
+            any(target_env = "gnu", target_env = "musl")
+        ),
+        all(target_arch = "x86", target_env = "gnu")
+    )
+))]
+pub fn skip_syscall(pid: Pid, return_value: i64) -> Result<()> {
+    set_syscall_number(pid, -1)?;
+    syscall(pid, None)?;
+    let status = loop {
+        match waitpid(pid, Some(WaitPidFlag::__WALL)) {
+            Err(Errno::EINTR) => continue,
+            res => break res?,
+        }
+    };
+    match status {
+        WaitStatus::PtraceSyscall(_)
+        | WaitStatus::Stopped(_, Signal::SIGTRAP) => (),
+        _ => return Err(Errno::EIO),
+    }
+    let mut regs = getregs_portable(pid)?;
+    regs.set_return_value(return_value);
+    setregs(pid, regs.into())
+}
+
 /// Function for ptrace requests that return values from the data field.
 /// Some ptrace get requests populate structs or larger elements than `c_long`
 /// and therefore use the data field to return values. This function handles these
@@ -366,6 +766,72 @@ pub fn getevent(pid: Pid) -> Result<c_long> {
     ptrace_get_data::<c_long>(Request::PTRACE_GETEVENTMSG, pid)
 }
 
+/// The final state of a tracee, as returned by [`get_exit_state`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ExitState {
+    status: WaitStatus,
+    #[cfg(all(
+        target_os = "linux",
+        any(
+            all(
+                target_arch = "x86_64",
+                any(target_env = "gnu", target_env = "musl")
+            ),
+            all(target_arch = "x86", target_env = "gnu")
+        )
+    ))]
+    regs: UserRegs,
+}
+
+impl ExitState {
+    /// How the tracee is exiting: either [`WaitStatus::Exited`] or
+    /// [`WaitStatus::Signaled`].
+    pub fn status(&self) -> WaitStatus {
+        self.status
+    }
+
+    /// The tracee's registers at the moment it began exiting.
+    #[cfg(all(
+        target_os = "linux",
+        any(
+            all(
+                target_arch = "x86_64",
+                any(target_env = "gnu", target_env = "musl")
+            ),
+            all(target_arch = "x86", target_env = "gnu")
+        )
+    ))]
+    pub fn regs(&self) -> &UserRegs {
+        &self.regs
+    }
+}
+
+/// Inspect a tracee that is about to exit.
+///
+/// The tracee must be in the `PTRACE_EVENT_EXIT` stop that
+/// [`Options::PTRACE_O_TRACEEXIT`] produces, reported by `waitpid` as
+/// `WaitStatus::PtraceEvent(pid, SIGTRAP, Event::PTRACE_EVENT_EXIT as i32)`.
+/// At that point the exit status is available through [`getevent`] and,
+/// unlike after the process has been reaped, its registers can still be
+/// read.  Continue the tracee afterwards to let it finish exiting.
+pub fn get_exit_state(pid: Pid) -> Result<ExitState> {
+    let status = WaitStatus::from_raw(pid, getevent(pid)? as libc::c_int)?;
+    Ok(ExitState {
+        status,
+        #[cfg(all(
+            target_os = "linux",
+            any(
+                all(
+                    target_arch = "x86_64",
+                    any(target_env = "gnu", target_env = "musl")
+                ),
+                all(target_arch = "x86", target_env = "gnu")
+            )
+        ))]
+        regs: getregs_portable(pid)?,
+    })
+}
+
 /// Get siginfo as with `ptrace(PTRACE_GETSIGINFO,...)`
 pub fn getsiginfo(pid: Pid) -> Result<siginfo_t> {
     ptrace_get_data::<siginfo_t>(Request::PTRACE_GETSIGINFO, pid)
@@ -420,6 +886,36 @@ pub fn traceme() -> Result<()> {
     }
 }
 
+/// Create a child process that will be traced by the calling process.
+///
+/// This is [`fork`](crate::unistd::fork) followed by [`traceme`] in the
+/// child.  If `mask` is given, the child also sets its signal mask to `mask`
+/// first, so that it starts, and keeps across `execve`, a known set of blocked
+/// signals no matter what the parent's thread had blocked.  Once the child
+/// calls `execve`, it stops with `SIGTRAP` for the parent to collect.
+///
+/// Errors in the child, which are unlikely, are returned in the child.
+///
+/// # Safety
+///
+/// The same as for [`fork`](crate::unistd::fork).
+#[cfg(feature = "signal")]
+#[cfg_attr(docsrs, doc(cfg(feature = "signal")))]
+pub unsafe fn fork_traced(
+    mask: Option<&crate::sys::signal::SigSet>,
+) -> Result<crate::unistd::ForkResult> {
+    use crate::sys::signal::{pthread_sigmask, SigmaskHow};
+
+    let res = crate::unistd::fork()?;
+    if res.is_child() {
+        if let Some(mask) = mask {
+            pthread_sigmask(SigmaskHow::SIG_SETMASK, Some(mask), None)?;
+        }
+        traceme()?;
+    }
+    Ok(res)
+}
+
 /// Continue execution until the next syscall, as with `ptrace(PTRACE_SYSCALL, ...)`
 ///
 /// Arranges for the tracee to be stopped at the next entry to or exit from a system call,
@@ -519,6 +1015,193 @@ pub fn cont<T: Into<Option<Signal>>>(pid: Pid, sig: T) -> Result<()> {
     }
 }
 
+/// List the threads of process `pid`, as found in `/proc/<pid>/task`.
+fn threads(pid: Pid) -> Result<Vec<Pid>> {
+    let io_err = |e: std::io::Error| Errno::try_from(e).unwrap_or(Errno::EIO);
+
+    let mut tids = Vec::new();
+    for entry in
+        std::fs::read_dir(format!("/proc/{}/task", pid)).map_err(io_err)?
+    {
+        let entry = entry.map_err(io_err)?;
+        if let Some(tid) =
+            entry.file_name().to_str().and_then(|s| s.parse().ok())
+        {
+            tids.push(Pid::from_raw(tid));
+        }
+    }
+    Ok(tids)
+}
+
+/// Restart every stopped thread of the tracee `pid`, as with
+/// `ptrace(PTRACE_CONT, ...)`.
+///
+/// ptrace operates on individual threads, so resuming a multithreaded
+/// tracee that is group-stopped means continuing each of its threads.  This
+/// reads the thread list from `/proc/<pid>/task` and continues every thread
+/// in it.  `sig`, if any, is only delivered to the thread `pid` itself.
+///
+/// Threads that exit while the list is being walked are silently skipped.
+/// However, a thread created after the list was read is missed; if the
+/// tracee may be cloning, use `PTRACE_O_TRACECLONE` so that new threads
+/// report a stop of their own.
+pub fn cont_all<T: Into<Option<Signal>>>(pid: Pid, sig: T) -> Result<()> {
+    let sig = sig.into();
+
+    for tid in threads(pid)? {
+        let res = if tid == pid {
+            cont(tid, sig)
+        } else {
+            cont(tid, None)
+        };
+        match res {
+            // The thread exited after the list was read
+            Err(Errno::ESRCH) if tid != pid => (),
+            res => res?,
+        }
+    }
+    Ok(())
+}
+
+/// Restart the stopped tracee and wait for its next state change, giving up
+/// after `timeout`.
+///
+/// This is [`cont`] followed by a [`waitpid`](crate::sys::wait::waitpid)
+/// that doesn't block forever.  Returns `Ok(None)` if the tracee hasn't
+/// stopped or exited by the time `timeout` elapses, which lets supervisors
+/// detect a hung tracee.  In that case the tracee is still running, and a
+/// later `waitpid` will pick up its next stop.
+///
+/// On Linux, the tracee's exit is waited for on a pidfd, so it is reported
+/// as soon as it happens.  A pidfd doesn't report stops though, so the
+/// child is also polled with `WNOHANG` at increasing intervals, and a stop
+/// may be reported a few milliseconds after it happened.  Where pidfds are
+/// unavailable, such as before Linux 5.3 or if `pid` isn't a thread group
+/// leader, exits are only picked up by this polling too.
+pub fn cont_wait_timeout<T: Into<Option<Signal>>>(
+    pid: Pid,
+    sig: T,
+    timeout: Duration,
+) -> Result<Option<WaitStatus>> {
+    const MAX_INTERVAL: Duration = Duration::from_millis(50);
+
+    let deadline = Instant::now() + timeout;
+    let mut interval = Duration::from_millis(1);
+
+    #[cfg(target_os = "linux")]
+    let pidfd = crate::sys::pidfd::pidfd_open(
+        pid,
+        crate::sys::pidfd::PidfdFlags::empty(),
+    )
+    .ok();
+    #[cfg(not(target_os = "linux"))]
+    let pidfd: Option<OwnedFd> = None;
+
+    cont(pid, sig)?;
+    loop {
+        match waitpid(pid, Some(WaitPidFlag::WNOHANG | WaitPidFlag::__WALL))? {
+            WaitStatus::StillAlive => (),
+            status => return Ok(Some(status)),
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(None);
+        }
+        let pause = interval.min(deadline - now);
+        match pidfd {
+            Some(ref pidfd) => wait_pidfd(pidfd, pause)?,
+            None => thread::sleep(pause),
+        }
+        interval = (interval * 2).min(MAX_INTERVAL);
+    }
+}
+
+/// Wait up to `timeout` for the process `pidfd` refers to to exit.
+fn wait_pidfd(pidfd: &OwnedFd, timeout: Duration) -> Result<()> {
+    let mut fd = libc::pollfd {
+        fd: pidfd.as_raw_fd(),
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    // Round up, so that a sub-millisecond remainder doesn't busy-loop
+    let ms = ((timeout.as_micros() + 999) / 1000).min(libc::c_int::MAX as u128)
+        as libc::c_int;
+    match Errno::result(unsafe { libc::poll(&mut fd, 1, ms) }) {
+        Ok(_) | Err(Errno::EINTR) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Resume the stopped tracee until it reports the ptrace event `target`,
+/// and return that event's message, as with [`getevent`].
+///
+/// Other stops are continued past: signals are delivered to the tracee,
+/// while other ptrace events and syscall-stops are resumed without a signal.
+/// The tracee is first resumed without a signal too, so a signal it is
+/// currently stopped with is suppressed.  On success, the tracee is left in
+/// the `target` event stop.
+///
+/// The event must have been enabled with [`setoptions`] beforehand, or this
+/// waits until the tracee exits, in which case `ESRCH` is returned.
+pub fn wait_for_event(pid: Pid, target: Event) -> Result<c_long> {
+    let mut sig = None;
+    loop {
+        cont(pid, sig)?;
+        let status = loop {
+            match waitpid(pid, Some(WaitPidFlag::__WALL)) {
+                Err(Errno::EINTR) => continue,
+                res => break res?,
+            }
+        };
+        sig = match status {
+            WaitStatus::PtraceEvent(_, _, event) if event == target as i32 => {
+                return getevent(pid)
+            }
+            WaitStatus::Stopped(_, signal) => Some(signal),
+            WaitStatus::Exited(..) | WaitStatus::Signaled(..) => {
+                return Err(Errno::ESRCH)
+            }
+            _ => None,
+        };
+    }
+}
+
+/// Run the stopped tracee until it exits, calling `on_stop` on every stop
+/// along the way, and return its exit status.
+///
+/// The tracee is resumed with [`syscall`], so syscall-stops are reported
+/// too; set [`Options::PTRACE_O_TRACESYSGOOD`] beforehand to tell them apart
+/// from a `SIGTRAP`.  For signal-delivery stops, `on_stop` returns the
+/// signal to deliver to the tracee, or `None` to suppress it; its result is
+/// ignored for other stops.  As with [`wait_for_event`], the tracee is first
+/// resumed without a signal.
+///
+/// `on_stop` is also called on the final [`WaitStatus::Exited`] or
+/// [`WaitStatus::Signaled`] status, which is then returned.
+pub fn run_to_exit<F>(pid: Pid, mut on_stop: F) -> Result<WaitStatus>
+where
+    F: FnMut(&WaitStatus) -> Option<Signal>,
+{
+    let mut sig = None;
+    loop {
+        syscall(pid, sig)?;
+        let status = loop {
+            match waitpid(pid, Some(WaitPidFlag::__WALL)) {
+                Err(Errno::EINTR) => continue,
+                res => break res?,
+            }
+        };
+        let forward = on_stop(&status);
+        sig = match status {
+            WaitStatus::Exited(..) | WaitStatus::Signaled(..) => {
+                return Ok(status)
+            }
+            WaitStatus::Stopped(..) => forward,
+            _ => None,
+        };
+    }
+}
+
 /// Stop a tracee, as with `ptrace(PTRACE_INTERRUPT, ...)`
 ///
 /// This request is equivalent to `ptrace(PTRACE_INTERRUPT, ...)`
@@ -631,12 +1314,353 @@ pub unsafe fn write(
     ptrace_other(Request::PTRACE_POKEDATA, pid, addr, data).map(drop)
 }
 
+/// Read `buf.len()` bytes of the tracee's memory at `addr`, one aligned word
+/// at a time.
+fn read_bytes(pid: Pid, addr: AddressType, buf: &mut [u8]) -> Result<()> {
+    let word = mem::size_of::<c_long>();
+    let mut pos = 0;
+    while pos < buf.len() {
+        let at = (addr as usize).wrapping_add(pos);
+        let skip = at % word;
+        let n = std::cmp::min(word - skip, buf.len() - pos);
+        let w = read(pid, (at - skip) as AddressType)?.to_ne_bytes();
+        buf[pos..pos + n].copy_from_slice(&w[skip..skip + n]);
+        pos += n;
+    }
+    Ok(())
+}
+
+/// Write `bytes` into the tracee's memory at `addr`, one aligned word at a
+/// time, preserving the rest of each word.
+fn write_bytes(pid: Pid, addr: AddressType, bytes: &[u8]) -> Result<()> {
+    let word = mem::size_of::<c_long>();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let at = (addr as usize).wrapping_add(pos);
+        let skip = at % word;
+        let n = std::cmp::min(word - skip, bytes.len() - pos);
+        let base = (at - skip) as AddressType;
+        let mut w = if n == word {
+            [0; mem::size_of::<c_long>()]
+        } else {
+            read(pid, base)?.to_ne_bytes()
+        };
+        w[skip..skip + n].copy_from_slice(&bytes[pos..pos + n]);
+        unsafe { write(pid, base, c_long::from_ne_bytes(w) as *mut c_void)? };
+        pos += n;
+    }
+    Ok(())
+}
+
+/// Reads a byte from a processes memory at the given address
+///
+/// Unlike [`read`], which reads a whole word, this only accesses the word
+/// containing `addr`, so it can't fail because the following bytes aren't
+/// mapped.
+pub fn read_u8(pid: Pid, addr: AddressType) -> Result<u8> {
+    let mut buf = [0; 1];
+    read_bytes(pid, addr, &mut buf).map(|_| u8::from_ne_bytes(buf))
+}
+
+/// Reads a `u16`, in native byte order, from a processes memory at the
+/// given address, which needn't be aligned
+pub fn read_u16(pid: Pid, addr: AddressType) -> Result<u16> {
+    let mut buf = [0; 2];
+    read_bytes(pid, addr, &mut buf).map(|_| u16::from_ne_bytes(buf))
+}
+
+/// Reads a `u32`, in native byte order, from a processes memory at the
+/// given address, which needn't be aligned
+pub fn read_u32(pid: Pid, addr: AddressType) -> Result<u32> {
+    let mut buf = [0; 4];
+    read_bytes(pid, addr, &mut buf).map(|_| u32::from_ne_bytes(buf))
+}
+
+/// Writes a byte into a processes memory at the given address
+///
+/// `ptrace` can only write whole words, so this reads the word containing
+/// `addr`, replaces the one byte and writes the word back, leaving the
+/// neighbouring bytes as they were.  The tracee must stay stopped in the
+/// meantime, or changes it makes to those bytes may be lost.
+pub fn write_u8(pid: Pid, addr: AddressType, value: u8) -> Result<()> {
+    write_bytes(pid, addr, &value.to_ne_bytes())
+}
+
+/// Writes a `u16`, in native byte order, into a processes memory at the
+/// given address, preserving the neighbouring bytes as with [`write_u8`]
+///
+/// If `addr` isn't aligned the value may straddle two words, which are then
+/// written one after the other.
+pub fn write_u16(pid: Pid, addr: AddressType, value: u16) -> Result<()> {
+    write_bytes(pid, addr, &value.to_ne_bytes())
+}
+
+/// Writes a `u32`, in native byte order, into a processes memory at the
+/// given address, preserving the neighbouring bytes as with [`write_u8`]
+///
+/// If `addr` isn't aligned the value may straddle two words, which are then
+/// written one after the other.
+pub fn write_u32(pid: Pid, addr: AddressType, value: u32) -> Result<()> {
+    write_bytes(pid, addr, &value.to_ne_bytes())
+}
+
 /// Reads a word from a user area at `offset`.
 /// The user struct definition can be found in `/usr/include/sys/user.h`.
 pub fn read_user(pid: Pid, offset: AddressType) -> Result<c_long> {
     ptrace_peek(Request::PTRACE_PEEKUSER, pid, offset, ptr::null_mut())
 }
 
+/// Reads the whole user area, word by word, as with repeated [`read_user`].
+///
+/// The result holds `size_of::<libc::user>() / size_of::<c_long>()` words;
+/// word `i` is the one at byte offset `i * size_of::<c_long>()`.  This gives
+/// access to fields such as `u_debugreg` that have no typed accessor.
+#[cfg(all(
+    target_os = "linux",
+    any(
+        all(
+            target_arch = "x86_64",
+            any(target_env = "gnu", target_env = "musl")
+        ),
+        all(target_arch = "x86", target_env = "gnu")
+    )
+))]
+pub fn read_user_area(pid: Pid) -> Result<Vec<c_long>> {
+    let word = mem::size_of::<c_long>();
+    (0..mem::size_of::<libc::user>() / word)
+        .map(|i| read_user(pid, (i * word) as AddressType))
+        .collect()
+}
+
+/// Read the field of the tracee's `user_regs_struct` at `field`, whose
+/// address is taken relative to `regs`, through `PTRACE_PEEKUSER`.
+#[cfg(all(
+    target_os = "linux",
+    target_arch = "x86_64",
+    any(target_env = "gnu", target_env = "musl")
+))]
+fn read_user_reg(
+    pid: Pid,
+    regs: &mem::MaybeUninit<user_regs_struct>,
+    field: *const u64,
+) -> Result<u64> {
+    // The registers are at the start of `struct user`.
+    let offset = field as usize - regs.as_ptr() as usize;
+    read_user(pid, offset as AddressType).map(|w| w as u64)
+}
+
+/// Get the tracee's FS base, the address of its thread control block.
+///
+/// On x86_64 this is where thread-local storage, such as glibc's `errno`, is
+/// found.
+#[cfg(all(
+    target_os = "linux",
+    target_arch = "x86_64",
+    any(target_env = "gnu", target_env = "musl")
+))]
+pub fn get_fs_base(pid: Pid) -> Result<u64> {
+    let regs = mem::MaybeUninit::<user_regs_struct>::uninit();
+    let field = unsafe { ptr::addr_of!((*regs.as_ptr()).fs_base) };
+    read_user_reg(pid, &regs, field)
+}
+
+/// Get the tracee's GS base.  It is usually zero for user space programs.
+#[cfg(all(
+    target_os = "linux",
+    target_arch = "x86_64",
+    any(target_env = "gnu", target_env = "musl")
+))]
+pub fn get_gs_base(pid: Pid) -> Result<u64> {
+    let regs = mem::MaybeUninit::<user_regs_struct>::uninit();
+    let field = unsafe { ptr::addr_of!((*regs.as_ptr()).gs_base) };
+    read_user_reg(pid, &regs, field)
+}
+
+/// Read `depth` words of the tracee's stack, starting at the stack pointer
+/// `sp` and moving towards the base of the stack.
+///
+/// This is a building block for frame-pointer based backtraces.  Words have
+/// the tracee's native size, that of a `c_long`, and are zero-extended to
+/// `u64`.  The words are fetched with a single `process_vm_readv` call where
+/// the kernel supports it, falling back to one [`read`] per word.  Returns
+/// `EFAULT` if fewer than `depth` words are mapped.
+// The cast is not unnecessary on all platforms.
+#[allow(clippy::unnecessary_cast)]
+pub fn read_stack(pid: Pid, sp: u64, depth: usize) -> Result<Vec<u64>> {
+    let word = mem::size_of::<c_long>();
+    let widen = |w: c_long| w as libc::c_ulong as u64;
+
+    #[cfg(not(target_env = "uclibc"))]
+    {
+        let mut buf: Vec<c_long> = vec![0; depth];
+        let len = depth * word;
+        let local = libc::iovec {
+            iov_base: buf.as_mut_ptr() as *mut c_void,
+            iov_len: len,
+        };
+        let remote = libc::iovec {
+            iov_base: sp as usize as *mut c_void,
+            iov_len: len,
+        };
+        let res = unsafe {
+            libc::process_vm_readv(pid.into(), &local, 1, &remote, 1, 0)
+        };
+        match Errno::result(res) {
+            Ok(n) if n as usize == len => {
+                return Ok(buf.into_iter().map(widen).collect())
+            }
+            Ok(_) => return Err(Errno::EFAULT),
+            Err(Errno::ENOSYS) => (),
+            Err(e) => return Err(e),
+        }
+    }
+
+    (0..depth)
+        .map(|i| {
+            let addr = (sp as usize).wrapping_add(i * word) as AddressType;
+            match read(pid, addr) {
+                Ok(w) => Ok(widen(w)),
+                Err(Errno::EIO) => Err(Errno::EFAULT),
+                Err(e) => Err(e),
+            }
+        })
+        .collect()
+}
+
+/// Read a value of type `T` out of the tracee's memory at `addr`.
+///
+/// The `size_of::<T>()` bytes at `addr` are fetched with a single
+/// `process_vm_readv` call where the kernel supports it, falling back to
+/// [`read`]s of the aligned words they span, and reinterpreted as a `T`.  `addr` needn't be aligned
+/// for `T`.  Returns `EFAULT` if part of the range isn't mapped.
+///
+/// # Safety
+///
+/// The bytes must form a valid `T`.  In particular, the layout of `T` must
+/// match that of the tracee's type, which can differ from the tracer's own
+/// ABI, for example if the tracee is a 32-bit program.  Types with padding,
+/// `bool`s, enums and references are hard to get right; prefer `repr(C)`
+/// structs of integers.
+pub unsafe fn read_struct<T: Copy>(pid: Pid, addr: AddressType) -> Result<T> {
+    let len = mem::size_of::<T>();
+    let mut buf = vec![0u8; len];
+
+    #[cfg(not(target_env = "uclibc"))]
+    let fallback = {
+        let local = libc::iovec {
+            iov_base: buf.as_mut_ptr() as *mut c_void,
+            iov_len: len,
+        };
+        let remote = libc::iovec {
+            iov_base: addr,
+            iov_len: len,
+        };
+        let res = libc::process_vm_readv(pid.into(), &local, 1, &remote, 1, 0);
+        match Errno::result(res) {
+            Ok(n) if n as usize == len => false,
+            Ok(_) => return Err(Errno::EFAULT),
+            Err(Errno::ENOSYS) => true,
+            Err(e) => return Err(e),
+        }
+    };
+    #[cfg(target_env = "uclibc")]
+    let fallback = true;
+
+    if fallback {
+        // Aligned words, so that nothing past the end of the value is read
+        match read_bytes(pid, addr, &mut buf) {
+            Err(Errno::EIO) => return Err(Errno::EFAULT),
+            res => res?,
+        }
+    }
+
+    Ok(ptr::read_unaligned(buf.as_ptr() as *const T))
+}
+
+/// Whether the tracee runs a 32-bit program, such as an i386 program traced
+/// by an x86_64 tracer, in which case pointers in its memory are 4 bytes
+/// wide.
+///
+/// This inspects the ELF class of the tracee's executable, through
+/// `/proc/<pid>/exe`, so the tracee needn't be stopped.  Returns `ENOEXEC`
+/// if the executable isn't an ELF file.
+pub fn tracee_is_32bit(pid: Pid) -> Result<bool> {
+    use std::io::Read;
+
+    const ELFCLASS32: u8 = 1;
+    const ELFCLASS64: u8 = 2;
+
+    let io_err = |e: std::io::Error| match e.kind() {
+        std::io::ErrorKind::UnexpectedEof => Errno::ENOEXEC,
+        _ => Errno::try_from(e).unwrap_or(Errno::EIO),
+    };
+    // The magic number, followed by EI_CLASS
+    let mut ident = [0u8; 5];
+    std::fs::File::open(format!("/proc/{}/exe", pid))
+        .and_then(|mut exe| exe.read_exact(&mut ident))
+        .map_err(io_err)?;
+    match ident {
+        [0x7f, b'E', b'L', b'F', ELFCLASS32] => Ok(true),
+        [0x7f, b'E', b'L', b'F', ELFCLASS64] => Ok(false),
+        _ => Err(Errno::ENOEXEC),
+    }
+}
+
+/// Whether `pc` is in the code that returns from a signal handler, which
+/// calls `rt_sigreturn` or, for old-style i386 handlers, `sigreturn`.
+///
+/// When the tracee is stopped in a signal handler, the return address of the
+/// handler's outermost frame points in this trampoline, and the frame below
+/// it is a `ucontext_t` rather than a normal call frame, which unwinders must
+/// treat specially.  `pc` may point at any instruction of the trampoline.
+///
+/// The trampoline is found by its code, as debuggers do, since it lives in
+/// the C library on x86_64 and in the vDSO on i386: it is `mov $15, %rax;
+/// syscall` for 64-bit tracees and `mov $173, %eax; int $0x80`, or `pop %eax;
+/// mov $119, %eax; int $0x80`, for 32-bit ones.  Returns `EFAULT` if `pc`
+/// isn't mapped.
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "x86")
+))]
+pub fn is_signal_trampoline(pid: Pid, pc: u64) -> Result<bool> {
+    // Each trampoline, with the offsets of its instructions
+    const TRAMPOLINES: [(&[u8], &[u64]); 3] = [
+        (
+            &[0x48, 0xc7, 0xc0, 0x0f, 0x00, 0x00, 0x00, 0x0f, 0x05],
+            &[0, 7],
+        ),
+        (&[0xb8, 0xad, 0x00, 0x00, 0x00, 0xcd, 0x80], &[0, 5]),
+        (
+            &[0x58, 0xb8, 0x77, 0x00, 0x00, 0x00, 0xcd, 0x80],
+            &[0, 1, 6],
+        ),
+    ];
+    let mut buf = [0u8; 9];
+
+    // Fails if pc itself isn't mapped
+    match read_bytes(pid, pc as usize as AddressType, &mut buf[..1]) {
+        Err(Errno::EIO) => return Err(Errno::EFAULT),
+        r => r?,
+    }
+    for (code, offsets) in TRAMPOLINES {
+        for &off in offsets {
+            let start = match pc.checked_sub(off) {
+                Some(start) => start as usize as AddressType,
+                None => continue,
+            };
+            let buf = &mut buf[..code.len()];
+            match read_bytes(pid, start, buf) {
+                Ok(()) if buf == code => return Ok(true),
+                // A neighbouring page may be unmapped
+                Ok(()) | Err(Errno::EIO | Errno::EFAULT) => (),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+    Ok(false)
+}
+
 /// Writes a word to a user area at `offset`.
 /// The user struct definition can be found in `/usr/include/sys/user.h`.
 ///
@@ -651,3 +1675,400 @@ pub unsafe fn write_user(
 ) -> Result<()> {
     ptrace_other(Request::PTRACE_POKEUSER, pid, offset, data).map(drop)
 }
+
+/// Make the tracee execute a single system call and return its result.
+///
+/// The tracee's registers are saved, the instruction at its current program
+/// counter is temporarily overwritten with a system call instruction, and the
+/// tracee is single-stepped through it.  Afterwards both the original
+/// instruction and the registers are restored, so the tracee resumes as if
+/// nothing had happened.
+///
+/// `nr` is the system call number (one of the `libc::SYS_*` constants) and
+/// `args` are its arguments, in order.  A return value in the range reserved
+/// for errors is converted to the corresponding [`Errno`].
+///
+/// The tracee must be in a ptrace-stop, for example after a `SIGSTOP` or
+/// `SIGTRAP` has been reported by `waitpid`.  Note that the injected system
+/// call really runs in the tracee: a call like `munmap` or `close` can break
+/// it as easily as if it had made the call itself.
+///
+/// If the single step ends in any other stop, for example because a signal
+/// arrived, the tracee is restored all the same, left in that stop, and
+/// `EINTR` is returned; the stop has been waited for already, so the caller
+/// should deal with it, for example with [`getsiginfo`], before resuming.
+/// If the tracee died meanwhile, `ESRCH` is returned.
+///
+/// Only x86 and x86_64 are supported, as the system call instruction and
+/// calling convention are specific to each architecture.
+#[cfg(all(
+    target_os = "linux",
+    any(
+        all(
+            target_arch = "x86_64",
+            any(target_env = "gnu", target_env = "musl")
+        ),
+        all(target_arch = "x86", target_env = "gnu")
+    )
+))]
+pub fn inject_syscall(pid: Pid, nr: c_long, args: [u64; 6]) -> Result<i64> {
+    // `syscall` on x86_64, `int $0x80` on x86
+    #[cfg(target_arch = "x86_64")]
+    const SYSCALL_INSN: [u8; 2] = [0x0f, 0x05];
+    #[cfg(target_arch = "x86")]
+    const SYSCALL_INSN: [u8; 2] = [0xcd, 0x80];
+
+    let saved_regs = getregs(pid)?;
+    #[cfg(target_arch = "x86_64")]
+    let pc = saved_regs.rip as AddressType;
+    #[cfg(target_arch = "x86")]
+    let pc = saved_regs.eip as AddressType;
+
+    let saved_text = read(pid, pc)?;
+    let mut text = saved_text.to_ne_bytes();
+    text[..SYSCALL_INSN.len()].copy_from_slice(&SYSCALL_INSN);
+
+    let mut regs = saved_regs;
+    #[cfg(target_arch = "x86_64")]
+    {
+        // Prevent the kernel from restarting an interrupted system call when
+        // the tracee is resumed.
+        regs.orig_rax = u64::MAX;
+        regs.rax = nr as u64;
+        regs.rdi = args[0];
+        regs.rsi = args[1];
+        regs.rdx = args[2];
+        regs.r10 = args[3];
+        regs.r8 = args[4];
+        regs.r9 = args[5];
+    }
+    #[cfg(target_arch = "x86")]
+    {
+        regs.orig_eax = -1;
+        regs.eax = nr;
+        regs.ebx = args[0] as c_long;
+        regs.ecx = args[1] as c_long;
+        regs.edx = args[2] as c_long;
+        regs.esi = args[3] as c_long;
+        regs.edi = args[4] as c_long;
+        regs.ebp = args[5] as c_long;
+    }
+
+    unsafe {
+        write(pid, pc, c_long::from_ne_bytes(text) as *mut c_void)?;
+    }
+    let res = setregs(pid, regs)
+        .and_then(|_| step(pid, None))
+        .and_then(|_| loop {
+            match waitpid(pid, Some(WaitPidFlag::__WALL)) {
+                Err(Errno::EINTR) => continue,
+                res => break res,
+            }
+        })
+        .and_then(|status| match status {
+            WaitStatus::Stopped(_, Signal::SIGTRAP) => getregs(pid),
+            WaitStatus::Exited(..) | WaitStatus::Signaled(..) => {
+                Err(Errno::ESRCH)
+            }
+            _ => Err(Errno::EINTR),
+        });
+
+    // Restore the tracee even if the call failed midway, and the registers
+    // even if the text couldn't be restored.  The first error is reported.
+    let text_res = unsafe { write(pid, pc, saved_text as *mut c_void) };
+    let regs_res = setregs(pid, saved_regs);
+    let regs = res.and_then(|regs| text_res.and(regs_res).map(|_| regs))?;
+
+    #[cfg(target_arch = "x86_64")]
+    let ret = regs.rax as i64;
+    #[cfg(target_arch = "x86")]
+    let ret = i64::from(regs.eax);
+    if (-4095..0).contains(&ret) {
+        Err(Errno::from_i32(-ret as i32))
+    } else {
+        Ok(ret)
+    }
+}
+
+/// A software breakpoint: an `int3` instruction written over the first byte
+/// of an instruction in the tracee.
+///
+/// When the tracee executes it, it stops with `SIGTRAP` and its instruction
+/// pointer just past the `int3`.  Use [`step_over_breakpoint`] to run the
+/// original instruction and carry on.
+///
+/// The whole word at the breakpoint's address is saved and restored, so two
+/// breakpoints must not share a word.
+#[cfg(all(
+    target_os = "linux",
+    any(
+        all(
+            target_arch = "x86_64",
+            any(target_env = "gnu", target_env = "musl")
+        ),
+        all(target_arch = "x86", target_env = "gnu")
+    )
+))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Breakpoint {
+    addr: AddressType,
+    saved: c_long,
+}
+
+#[cfg(all(
+    target_os = "linux",
+    any(
+        all(
+            target_arch = "x86_64",
+            any(target_env = "gnu", target_env = "musl")
+        ),
+        all(target_arch = "x86", target_env = "gnu")
+    )
+))]
+impl Breakpoint {
+    /// The `int3` instruction.
+    const INSN: u8 = 0xcc;
+
+    /// Write a breakpoint at `addr` in the stopped tracee `pid`.
+    pub fn insert(pid: Pid, addr: AddressType) -> Result<Breakpoint> {
+        let saved = read(pid, addr)?;
+        let bp = Breakpoint { addr, saved };
+        bp.arm(pid)?;
+        Ok(bp)
+    }
+
+    /// The address of the breakpoint.
+    pub fn addr(&self) -> AddressType {
+        self.addr
+    }
+
+    /// Restore the original instruction, leaving the tracee as if the
+    /// breakpoint had never been inserted.
+    ///
+    /// If the tracee is stopped at the breakpoint, its instruction pointer
+    /// must still be rewound with [`UserRegs::set_ip`].
+    pub fn remove(self, pid: Pid) -> Result<()> {
+        unsafe { write(pid, self.addr, self.saved as *mut c_void) }
+    }
+
+    fn arm(&self, pid: Pid) -> Result<()> {
+        let mut text = self.saved.to_ne_bytes();
+        text[0] = Self::INSN;
+        unsafe {
+            write(pid, self.addr, c_long::from_ne_bytes(text) as *mut c_void)
+        }
+    }
+}
+
+/// Resume a tracee that stopped at `bp`, keeping the breakpoint in place.
+///
+/// This restores the original instruction, rewinds the instruction pointer
+/// onto it, single-steps it, writes the breakpoint back and continues the
+/// tracee.  The tracee must be stopped with `SIGTRAP` at `bp`, with its
+/// instruction pointer either just past the breakpoint, as the kernel leaves
+/// it, or already rewound; otherwise `EINVAL` is returned.
+///
+/// If the single step ends in any other stop, for example because a signal
+/// arrived, the breakpoint is re-armed, the tracee is left stopped and
+/// `EINTR` is returned; the stop has been waited for already, and the caller
+/// should deal with it before resuming.  If the tracee died meanwhile,
+/// `ESRCH` is returned.
+#[cfg(all(
+    target_os = "linux",
+    any(
+        all(
+            target_arch = "x86_64",
+            any(target_env = "gnu", target_env = "musl")
+        ),
+        all(target_arch = "x86", target_env = "gnu")
+    )
+))]
+pub fn step_over_breakpoint(pid: Pid, bp: &Breakpoint) -> Result<()> {
+    let mut regs = getregs_portable(pid)?;
+    let addr = bp.addr as u64;
+    if regs.ip() != addr + 1 && regs.ip() != addr {
+        return Err(Errno::EINVAL);
+    }
+    regs.set_ip(addr);
+
+    unsafe { write(pid, bp.addr, bp.saved as *mut c_void)? };
+    let res = setregs(pid, regs.into())
+        .and_then(|_| step(pid, None))
+        .and_then(|_| waitpid(pid, Some(WaitPidFlag::__WALL)));
+    match res? {
+        WaitStatus::Stopped(_, Signal::SIGTRAP) => {
+            bp.arm(pid)?;
+            cont(pid, None)
+        }
+        WaitStatus::Exited(..) | WaitStatus::Signaled(..) => Err(Errno::ESRCH),
+        _ => {
+            bp.arm(pid)?;
+            Err(Errno::EINTR)
+        }
+    }
+}
+
+/// Resume the stopped tracee `pid` and run it until it reaches `addr`.
+///
+/// This inserts a temporary [`Breakpoint`] at `addr`, continues the tracee
+/// and waits for it to stop.  When the breakpoint is hit, it is removed and
+/// the instruction pointer rewound onto `addr`, so that the tracee is left
+/// stopped right before the instruction at `addr`, and
+/// `WaitStatus::Stopped(pid, SIGTRAP)` is returned.
+///
+/// If the tracee stops for another reason first, for example because a
+/// signal arrived, the breakpoint is removed all the same and the status of
+/// that stop is returned; the caller should deal with it, and may call
+/// `run_until` again.  If the tracee exited or was killed, its status is
+/// returned too.
+#[cfg(all(
+    target_os = "linux",
+    any(
+        all(
+            target_arch = "x86_64",
+            any(target_env = "gnu", target_env = "musl")
+        ),
+        all(target_arch = "x86", target_env = "gnu")
+    )
+))]
+pub fn run_until(pid: Pid, addr: AddressType) -> Result<WaitStatus> {
+    let bp = Breakpoint::insert(pid, addr)?;
+    let status = cont(pid, None).and_then(|_| loop {
+        match waitpid(pid, Some(WaitPidFlag::__WALL)) {
+            Err(Errno::EINTR) => continue,
+            res => break res,
+        }
+    });
+    let status = match status {
+        Ok(status @ (WaitStatus::Exited(..) | WaitStatus::Signaled(..))) => {
+            return Ok(status)
+        }
+        Ok(status) => status,
+        Err(e) => {
+            // The tracee may still be stopped, so try to clean up
+            let _ = bp.remove(pid);
+            return Err(e);
+        }
+    };
+
+    let regs = getregs_portable(pid);
+    bp.remove(pid)?;
+    let mut regs = regs?;
+    if status == WaitStatus::Stopped(pid, Signal::SIGTRAP)
+        && regs.ip() == addr as u64 + 1
+    {
+        regs.set_ip(addr as u64);
+        setregs(pid, regs.into())?;
+    }
+    Ok(status)
+}
+
+/// Attach to a running process, as with [`attach`], reporting failure as a
+/// [`TracerError`].
+pub fn attach_ctx(pid: Pid) -> std::result::Result<(), TracerError> {
+    attach(pid).map_err(|e| TracerError::new(Request::PTRACE_ATTACH, e))
+}
+
+/// Attach to a running process, as with [`seize`], reporting failure as a
+/// [`TracerError`].
+#[cfg(target_os = "linux")]
+#[cfg_attr(docsrs, doc(cfg(all())))]
+pub fn seize_ctx(
+    pid: Pid,
+    options: Options,
+) -> std::result::Result<(), TracerError> {
+    seize(pid, options).map_err(|e| TracerError::new(Request::PTRACE_SEIZE, e))
+}
+
+/// Detach from the tracee, as with [`detach`], reporting failure as a
+/// [`TracerError`].
+pub fn detach_ctx<T: Into<Option<Signal>>>(
+    pid: Pid,
+    sig: T,
+) -> std::result::Result<(), TracerError> {
+    detach(pid, sig).map_err(|e| TracerError::new(Request::PTRACE_DETACH, e))
+}
+
+/// Restart the stopped tracee, as with [`cont`], reporting failure as a
+/// [`TracerError`].
+pub fn cont_ctx<T: Into<Option<Signal>>>(
+    pid: Pid,
+    sig: T,
+) -> std::result::Result<(), TracerError> {
+    cont(pid, sig).map_err(|e| TracerError::new(Request::PTRACE_CONT, e))
+}
+
+/// Continue to the next syscall, as with [`syscall`], reporting failure as a
+/// [`TracerError`].
+pub fn syscall_ctx<T: Into<Option<Signal>>>(
+    pid: Pid,
+    sig: T,
+) -> std::result::Result<(), TracerError> {
+    syscall(pid, sig).map_err(|e| TracerError::new(Request::PTRACE_SYSCALL, e))
+}
+
+/// Single-step the tracee, as with [`step`], reporting failure as a
+/// [`TracerError`].
+pub fn step_ctx<T: Into<Option<Signal>>>(
+    pid: Pid,
+    sig: T,
+) -> std::result::Result<(), TracerError> {
+    step(pid, sig).map_err(|e| TracerError::new(Request::PTRACE_SINGLESTEP, e))
+}
+
+/// Stop a tracee, as with [`interrupt`], reporting failure as a
+/// [`TracerError`].
+#[cfg(target_os = "linux")]
+#[cfg_attr(docsrs, doc(cfg(all())))]
+pub fn interrupt_ctx(pid: Pid) -> std::result::Result<(), TracerError> {
+    interrupt(pid).map_err(|e| TracerError::new(Request::PTRACE_INTERRUPT, e))
+}
+
+/// Set options, as with [`setoptions`], reporting failure as a
+/// [`TracerError`].
+pub fn setoptions_ctx(
+    pid: Pid,
+    options: Options,
+) -> std::result::Result<(), TracerError> {
+    setoptions(pid, options)
+        .map_err(|e| TracerError::new(Request::PTRACE_SETOPTIONS, e))
+}
+
+/// Get a ptrace event message, as with [`getevent`], reporting failure as a
+/// [`TracerError`].
+pub fn getevent_ctx(pid: Pid) -> std::result::Result<c_long, TracerError> {
+    getevent(pid).map_err(|e| TracerError::new(Request::PTRACE_GETEVENTMSG, e))
+}
+
+/// Get siginfo, as with [`getsiginfo`], reporting failure as a
+/// [`TracerError`].
+pub fn getsiginfo_ctx(pid: Pid) -> std::result::Result<siginfo_t, TracerError> {
+    getsiginfo(pid).map_err(|e| TracerError::new(Request::PTRACE_GETSIGINFO, e))
+}
+
+/// Get user registers, as with [`getregs`], reporting failure as a
+/// [`TracerError`].
+#[cfg(all(
+    target_os = "linux",
+    any(
+        all(
+            target_arch = "x86_64",
+            any(target_env = "gnu", target_env = "musl")
+        ),
+        all(target_arch = "x86", target_env = "gnu")
+    )
+))]
+pub fn getregs_ctx(
+    pid: Pid,
+) -> std::result::Result<user_regs_struct, TracerError> {
+    getregs(pid).map_err(|e| TracerError::new(Request::PTRACE_GETREGS, e))
+}
+
+/// Read a word from the tracee's memory, as with [`read`], reporting failure
+/// as a [`TracerError`].
+pub fn read_ctx(
+    pid: Pid,
+    addr: AddressType,
+) -> std::result::Result<c_long, TracerError> {
+    read(pid, addr).map_err(|e| TracerError::new(Request::PTRACE_PEEKDATA, e))
+}
@@ -6,6 +6,12 @@ mod linux;
 #[cfg(any(target_os = "android", target_os = "linux"))]
 pub use self::linux::*;
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod tracee;
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use self::tracee::*;
+
 #[cfg(any(
     target_os = "dragonfly",
     target_os = "freebsd",
@@ -0,0 +1,355 @@
+//! Typestate wrappers that only allow ptrace requests in a valid state
+//!
+//! Most ptrace requests fail with `ESRCH` unless the tracee is in a
+//! ptrace-stop.  [`StoppedTracee`] and [`RunningTracee`] encode that rule in
+//! the type system: the requests that need a stopped tracee are methods of
+//! `StoppedTracee`, resuming it consumes the `StoppedTracee` and yields a
+//! `RunningTracee` (or hands the `StoppedTracee` back if the request
+//! failed), and waiting on a `RunningTracee` turns it back into a
+//! `StoppedTracee`.
+//!
+//! The types only track what this process did to the tracee.  Something else
+//! (a `SIGKILL`, another thread calling the free functions of
+//! [`ptrace`](crate::sys::ptrace)) can still change its state behind their
+//! back, in which case requests fail with `ESRCH` as usual.  The
+//! [`StoppedTracee::assume_stopped`], [`RunningTracee::assume_running`] and
+//! `into_pid` methods convert from and to plain `Pid`s for code that needs to
+//! step outside the typestate.
+//!
+//! The kernel has no request to read back a tracee's ptrace options, so the
+//! types also remember the options this process set through them, as
+//! `current_options`.
+
+use super::{
+    attach, cont, detach, getevent, getsiginfo, read, setoptions, setsiginfo,
+    step, syscall, write, AddressType, Options,
+};
+#[cfg(all(
+    target_os = "linux",
+    any(
+        all(
+            target_arch = "x86_64",
+            any(target_env = "gnu", target_env = "musl")
+        ),
+        all(target_arch = "x86", target_env = "gnu")
+    )
+))]
+use super::{getregs_portable, setregs, UserRegs};
+use crate::errno::Errno;
+use crate::sys::signal::Signal;
+use crate::sys::wait::{waitpid, WaitPidFlag, WaitStatus};
+use crate::unistd::Pid;
+use crate::Result;
+use libc::{c_long, c_void, siginfo_t};
+
+/// A tracee that is known to be in a ptrace-stop.
+#[derive(Debug, Eq, PartialEq)]
+pub struct StoppedTracee {
+    pid: Pid,
+    status: WaitStatus,
+    options: Options,
+}
+
+/// A tracee that has been resumed and must be waited for before it can be
+/// inspected again.
+#[derive(Debug, Eq, PartialEq)]
+pub struct RunningTracee {
+    pid: Pid,
+    options: Options,
+}
+
+/// The result of [`RunningTracee::wait`].
+#[derive(Debug, Eq, PartialEq)]
+pub enum TraceeStatus {
+    /// The tracee entered a ptrace-stop.
+    Stopped(StoppedTracee),
+    /// The tracee exited or was killed, with the given
+    /// [`WaitStatus::Exited`] or [`WaitStatus::Signaled`] status.
+    Gone(WaitStatus),
+}
+
+impl StoppedTracee {
+    /// Wrap a tracee the caller knows to be in a ptrace-stop, for example
+    /// after `waitpid` reported `status` for it.
+    ///
+    /// Nothing is checked: if the tracee isn't actually stopped, requests
+    /// simply fail with `ESRCH`.  The tracee is assumed to have no ptrace
+    /// options set; use [`StoppedTracee::assume_options`] otherwise.
+    pub const fn assume_stopped(pid: Pid, status: WaitStatus) -> Self {
+        StoppedTracee {
+            pid,
+            status,
+            options: Options::empty(),
+        }
+    }
+
+    /// Attach to the running process `pid`, wait for it to stop and set
+    /// `options`, returning the stopped tracee.
+    ///
+    /// This performs the whole startup sequence of [`attach`]: the kernel
+    /// sends the tracee a `SIGSTOP`, which has to be waited for before any
+    /// other request, including [`setoptions`], can succeed.  Signals that
+    /// arrive before the `SIGSTOP` are passed on to the tracee.  The returned
+    /// tracee is in the `SIGSTOP` signal-delivery-stop; resume it with
+    /// `None` as the signal to discard the `SIGSTOP`.
+    ///
+    /// Fails with `ESRCH` if the process exits before it stops.
+    pub fn attach_with_options(pid: Pid, options: Options) -> Result<Self> {
+        attach(pid)?;
+        let mut tracee = loop {
+            match RunningTracee::assume_running(pid).wait()? {
+                TraceeStatus::Stopped(tracee) => match tracee.status {
+                    WaitStatus::Stopped(_, Signal::SIGSTOP) => break tracee,
+                    WaitStatus::Stopped(_, sig) => {
+                        cont(pid, sig)?;
+                    }
+                    _ => {
+                        cont(pid, None)?;
+                    }
+                },
+                TraceeStatus::Gone(_) => return Err(Errno::ESRCH),
+            }
+        };
+        tracee.set_options(options)?;
+        Ok(tracee)
+    }
+
+    /// Record that the tracee has `options` set, for example because they
+    /// were set with [`setoptions`] directly, without changing anything in
+    /// the kernel.
+    pub const fn assume_options(self, options: Options) -> Self {
+        StoppedTracee { options, ..self }
+    }
+
+    /// The tracee's process (or thread) ID.
+    pub const fn pid(&self) -> Pid {
+        self.pid
+    }
+
+    /// The status `waitpid` reported for the stop.
+    pub const fn status(&self) -> WaitStatus {
+        self.status
+    }
+
+    /// The ptrace options last set through the typestate, with
+    /// [`StoppedTracee::set_options`] or [`RunningTracee::seize`].
+    ///
+    /// This reflects what this process asked for, not the kernel's state,
+    /// which can't be queried: options set by other means, such as a call to
+    /// [`setoptions`], aren't accounted for.
+    pub const fn current_options(&self) -> Options {
+        self.options
+    }
+
+    /// Give up the typestate, returning the tracee's ID.  The tracee stays
+    /// stopped.
+    pub const fn into_pid(self) -> Pid {
+        self.pid
+    }
+
+    /// Get the tracee's registers, as with [`getregs_portable`].
+    #[cfg(all(
+        target_os = "linux",
+        any(
+            all(
+                target_arch = "x86_64",
+                any(target_env = "gnu", target_env = "musl")
+            ),
+            all(target_arch = "x86", target_env = "gnu")
+        )
+    ))]
+    pub fn regs(&self) -> Result<UserRegs> {
+        getregs_portable(self.pid)
+    }
+
+    /// Set the tracee's registers, as with [`setregs`].
+    #[cfg(all(
+        target_os = "linux",
+        any(
+            all(
+                target_arch = "x86_64",
+                any(target_env = "gnu", target_env = "musl")
+            ),
+            all(target_arch = "x86", target_env = "gnu")
+        )
+    ))]
+    pub fn set_regs(&self, regs: UserRegs) -> Result<()> {
+        setregs(self.pid, regs.into())
+    }
+
+    /// Read a word of the tracee's memory, as with [`read`].
+    pub fn read(&self, addr: AddressType) -> Result<c_long> {
+        read(self.pid, addr)
+    }
+
+    /// Write a word of the tracee's memory, as with [`write`].
+    ///
+    /// # Safety
+    ///
+    /// The `data` argument is passed directly to `ptrace(2)`.  Read that man
+    /// page for guidance.
+    pub unsafe fn write(
+        &self,
+        addr: AddressType,
+        data: *mut c_void,
+    ) -> Result<()> {
+        write(self.pid, addr, data)
+    }
+
+    /// Get the siginfo of the signal that caused the stop, as with
+    /// [`getsiginfo`].
+    pub fn siginfo(&self) -> Result<siginfo_t> {
+        getsiginfo(self.pid)
+    }
+
+    /// Change the siginfo of the signal that caused the stop, as with
+    /// [`setsiginfo`].
+    pub fn set_siginfo(&self, sig: &siginfo_t) -> Result<()> {
+        setsiginfo(self.pid, sig)
+    }
+
+    /// Get the message of the ptrace event that caused the stop, as with
+    /// [`getevent`].
+    pub fn event(&self) -> Result<c_long> {
+        getevent(self.pid)
+    }
+
+    /// Set the ptrace options, as with [`setoptions`], and remember them as
+    /// the [`current_options`](StoppedTracee::current_options).
+    pub fn set_options(&mut self, options: Options) -> Result<()> {
+        setoptions(self.pid, options)?;
+        self.options = options;
+        Ok(())
+    }
+
+    /// Resume the tracee, as with [`cont`].
+    ///
+    /// On failure, the tracee is handed back along with the error, so that
+    /// it can still be inspected or resumed differently.
+    pub fn cont<T: Into<Option<Signal>>>(
+        self,
+        sig: T,
+    ) -> std::result::Result<RunningTracee, (StoppedTracee, Errno)> {
+        let res = cont(self.pid, sig);
+        self.resumed(res)
+    }
+
+    /// Resume the tracee until the next system call entry or exit, as with
+    /// [`syscall`].
+    ///
+    /// On failure, the tracee is handed back along with the error.
+    pub fn syscall<T: Into<Option<Signal>>>(
+        self,
+        sig: T,
+    ) -> std::result::Result<RunningTracee, (StoppedTracee, Errno)> {
+        let res = syscall(self.pid, sig);
+        self.resumed(res)
+    }
+
+    /// Resume the tracee for a single instruction, as with [`step`].
+    ///
+    /// On failure, the tracee is handed back along with the error.
+    pub fn step<T: Into<Option<Signal>>>(
+        self,
+        sig: T,
+    ) -> std::result::Result<RunningTracee, (StoppedTracee, Errno)> {
+        let res = step(self.pid, sig);
+        self.resumed(res)
+    }
+
+    /// Stop tracing and let the tracee run freely, as with [`detach`].
+    ///
+    /// On failure, the tracee is handed back along with the error.
+    pub fn detach<T: Into<Option<Signal>>>(
+        self,
+        sig: T,
+    ) -> std::result::Result<(), (StoppedTracee, Errno)> {
+        detach(self.pid, sig).map_err(|e| (self, e))
+    }
+
+    fn resumed(
+        self,
+        res: Result<()>,
+    ) -> std::result::Result<RunningTracee, (StoppedTracee, Errno)> {
+        match res {
+            Ok(()) => Ok(RunningTracee {
+                pid: self.pid,
+                options: self.options,
+            }),
+            Err(e) => Err((self, e)),
+        }
+    }
+}
+
+impl RunningTracee {
+    /// Wrap a tracee the caller knows to have been resumed.  The tracee is
+    /// assumed to have no ptrace options set.
+    pub const fn assume_running(pid: Pid) -> Self {
+        RunningTracee {
+            pid,
+            options: Options::empty(),
+        }
+    }
+
+    /// Attach to the running process `pid` with `options`, as with
+    /// [`seize`](super::seize).
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(all())))]
+    pub fn seize(pid: Pid, options: Options) -> Result<Self> {
+        super::seize(pid, options)?;
+        Ok(RunningTracee { pid, options })
+    }
+
+    /// The tracee's process (or thread) ID.
+    pub const fn pid(&self) -> Pid {
+        self.pid
+    }
+
+    /// The ptrace options last set through the typestate; see
+    /// [`StoppedTracee::current_options`].
+    pub const fn current_options(&self) -> Options {
+        self.options
+    }
+
+    /// Give up the typestate, returning the tracee's ID.
+    pub const fn into_pid(self) -> Pid {
+        self.pid
+    }
+
+    /// Ask the kernel to stop the tracee, as with
+    /// [`interrupt`](super::interrupt).  Use [`RunningTracee::wait`] to
+    /// collect the resulting stop.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(all())))]
+    pub fn interrupt(&self) -> Result<()> {
+        super::interrupt(self.pid)
+    }
+
+    /// Block until the tracee stops or goes away.
+    ///
+    /// `EINTR` is retried, so an error means the tracee can no longer be
+    /// waited for at all, for example because it was reaped elsewhere.
+    pub fn wait(self) -> Result<TraceeStatus> {
+        loop {
+            match waitpid(self.pid, Some(WaitPidFlag::__WALL)) {
+                Ok(
+                    status
+                    @ (WaitStatus::Exited(..) | WaitStatus::Signaled(..)),
+                ) => return Ok(TraceeStatus::Gone(status)),
+                Ok(
+                    status @ (WaitStatus::Stopped(..)
+                    | WaitStatus::PtraceEvent(..)
+                    | WaitStatus::PtraceSyscall(..)),
+                ) => {
+                    return Ok(TraceeStatus::Stopped(StoppedTracee {
+                        pid: self.pid,
+                        status,
+                        options: self.options,
+                    }))
+                }
+                Ok(_) | Err(Errno::EINTR) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
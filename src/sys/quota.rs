@@ -234,6 +234,94 @@ impl Dqblk {
     pub fn set_inode_time_limit(&mut self, limit: u64) {
         self.0.dqb_itime = limit;
     }
+
+    /// The fields the kernel reported as valid.
+    pub fn valid_fields(&self) -> QuotaValidFlags {
+        QuotaValidFlags::from_bits_truncate(self.0.dqb_valid)
+    }
+}
+
+/// The decoded contents of a [`Dqblk`].
+///
+/// Each field is `None` if the corresponding [`QuotaValidFlags`] bit wasn't
+/// set.  Block limits are in units of 1024-byte quota blocks, while
+/// `occupied_space` is in bytes.  Time limits are absolute times, in seconds
+/// since the epoch, at which a soft limit turns into a hard one.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct DiskQuota {
+    /// The absolute limit on disk quota blocks allocated.
+    pub blocks_hard_limit: Option<u64>,
+    /// The preferred limit on disk quota blocks allocated.
+    pub blocks_soft_limit: Option<u64>,
+    /// The current occupied space, in bytes.
+    pub occupied_space: Option<u64>,
+    /// The maximum number of allocated inodes.
+    pub inodes_hard_limit: Option<u64>,
+    /// The preferred limit on allocated inodes.
+    pub inodes_soft_limit: Option<u64>,
+    /// The current number of allocated inodes.
+    pub allocated_inodes: Option<u64>,
+    /// The time limit for excessive disk use.
+    pub block_time_limit: Option<u64>,
+    /// The time limit for excessive files.
+    pub inode_time_limit: Option<u64>,
+}
+
+impl DiskQuota {
+    /// Encode the quota for [`quotactl_set`], returning the `Dqblk` and the
+    /// flags of the fields to set.
+    ///
+    /// The hard and soft limits are set together: if only one of a pair is
+    /// `Some`, the other is set to 0, meaning no limit.
+    pub fn to_dqblk(&self) -> (Dqblk, QuotaValidFlags) {
+        let mut dqblk = Dqblk::default();
+        let mut fields = QuotaValidFlags::empty();
+        if self.blocks_hard_limit.is_some() || self.blocks_soft_limit.is_some()
+        {
+            dqblk.0.dqb_bhardlimit = self.blocks_hard_limit.unwrap_or(0);
+            dqblk.0.dqb_bsoftlimit = self.blocks_soft_limit.unwrap_or(0);
+            fields |= QuotaValidFlags::QIF_BLIMITS;
+        }
+        if self.inodes_hard_limit.is_some() || self.inodes_soft_limit.is_some()
+        {
+            dqblk.0.dqb_ihardlimit = self.inodes_hard_limit.unwrap_or(0);
+            dqblk.0.dqb_isoftlimit = self.inodes_soft_limit.unwrap_or(0);
+            fields |= QuotaValidFlags::QIF_ILIMITS;
+        }
+        if let Some(space) = self.occupied_space {
+            dqblk.0.dqb_curspace = space;
+            fields |= QuotaValidFlags::QIF_SPACE;
+        }
+        if let Some(inodes) = self.allocated_inodes {
+            dqblk.0.dqb_curinodes = inodes;
+            fields |= QuotaValidFlags::QIF_INODES;
+        }
+        if let Some(time) = self.block_time_limit {
+            dqblk.0.dqb_btime = time;
+            fields |= QuotaValidFlags::QIF_BTIME;
+        }
+        if let Some(time) = self.inode_time_limit {
+            dqblk.0.dqb_itime = time;
+            fields |= QuotaValidFlags::QIF_ITIME;
+        }
+        dqblk.0.dqb_valid = fields.bits();
+        (dqblk, fields)
+    }
+}
+
+impl From<&Dqblk> for DiskQuota {
+    fn from(dqblk: &Dqblk) -> Self {
+        DiskQuota {
+            blocks_hard_limit: dqblk.blocks_hard_limit(),
+            blocks_soft_limit: dqblk.blocks_soft_limit(),
+            occupied_space: dqblk.occupied_space(),
+            inodes_hard_limit: dqblk.inodes_hard_limit(),
+            inodes_soft_limit: dqblk.inodes_soft_limit(),
+            allocated_inodes: dqblk.allocated_inodes(),
+            block_time_limit: dqblk.block_time_limit(),
+            inode_time_limit: dqblk.inode_time_limit(),
+        }
+    }
 }
 
 fn quotactl<P: ?Sized + NixPath>(
@@ -26,9 +26,14 @@ libc_enum! {
         /// Suspend the system using software suspend.
         RB_SW_SUSPEND,
     }
+    impl TryFrom<i32>
 }
 
 /// Reboots or shuts down the system.
+///
+/// Unlike `reboot(8)`, this doesn't flush the file system buffers first;
+/// call `nix::unistd::sync` beforehand to avoid losing data.  Requires
+/// `CAP_SYS_BOOT`.
 pub fn reboot(how: RebootMode) -> Result<Infallible> {
     unsafe { libc::reboot(how as libc::c_int) };
     Err(Errno::last())
@@ -265,6 +265,185 @@ pub fn setrlimit(
     Errno::result(res).map(drop)
 }
 
+/// Get and optionally set the resource limits of another process
+///
+/// Like [`getrlimit`] and [`setrlimit`] combined, but for the process `pid`,
+/// or the calling process if `pid` is 0.  If `new_limit` is `Some((soft,
+/// hard))`, the limits are replaced.  Either way the limits in effect before
+/// the call are returned.
+///
+/// Changing another process's limits requires `CAP_SYS_RESOURCE`, or the same
+/// real, effective and saved user and group IDs as the target.
+///
+/// # References
+///
+/// [prlimit(2)](https://man7.org/linux/man-pages/man2/prlimit.2.html)
+#[cfg(all(
+    any(target_os = "android", target_os = "linux"),
+    feature = "process"
+))]
+#[cfg_attr(docsrs, doc(cfg(feature = "process")))]
+pub fn prlimit(
+    pid: crate::unistd::Pid,
+    resource: Resource,
+    new_limit: Option<(rlim_t, rlim_t)>,
+) -> Result<(rlim_t, rlim_t)> {
+    // The kernel's struct rlimit64, which uses all ones for "infinity"
+    #[repr(C)]
+    struct Rlimit64 {
+        rlim_cur: u64,
+        rlim_max: u64,
+    }
+
+    #[allow(clippy::unnecessary_cast)]
+    // The cast is not unnecessary on all platforms.
+    let to_raw = |v: rlim_t| {
+        if v == RLIM_INFINITY {
+            u64::MAX
+        } else {
+            v as u64
+        }
+    };
+    #[allow(clippy::unnecessary_cast)]
+    let from_raw = |v: u64| {
+        if v >= RLIM_INFINITY as u64 {
+            RLIM_INFINITY
+        } else {
+            v as rlim_t
+        }
+    };
+
+    let new_rlim = new_limit.map(|(soft, hard)| Rlimit64 {
+        rlim_cur: to_raw(soft),
+        rlim_max: to_raw(hard),
+    });
+    let new_ptr = new_rlim
+        .as_ref()
+        .map_or(std::ptr::null(), |r| r as *const Rlimit64);
+    let mut old_rlim = mem::MaybeUninit::<Rlimit64>::uninit();
+    let res = unsafe {
+        libc::syscall(
+            libc::SYS_prlimit64,
+            pid.as_raw(),
+            resource as c_int,
+            new_ptr,
+            old_rlim.as_mut_ptr(),
+        )
+    };
+
+    Errno::result(res).map(|_| {
+        let old_rlim = unsafe { old_rlim.assume_init() };
+        (from_raw(old_rlim.rlim_cur), from_raw(old_rlim.rlim_max))
+    })
+}
+
+/// Set the soft stack size limit of the process `pid` to `bytes`, keeping the
+/// hard limit
+///
+/// The stack limit determines the size the main thread's stack may grow to,
+/// and also how the kernel lays out the address space, but the layout is
+/// only chosen at `execve`.  For a reproducible memory layout, set the limit
+/// before the tracee executes the program, for example while it is stopped
+/// at the start of the exec.  Fails with `EINVAL` if `bytes` is above the
+/// hard limit.  See [`prlimit`] for the required privileges.
+#[cfg(all(
+    any(target_os = "android", target_os = "linux"),
+    feature = "process"
+))]
+#[cfg_attr(docsrs, doc(cfg(feature = "process")))]
+pub fn set_tracee_stack_limit(
+    pid: crate::unistd::Pid,
+    bytes: rlim_t,
+) -> Result<()> {
+    let (_, hard) = prlimit(pid, Resource::RLIMIT_STACK, None)?;
+    if hard != RLIM_INFINITY && bytes > hard {
+        return Err(Errno::EINVAL);
+    }
+    prlimit(pid, Resource::RLIMIT_STACK, Some((bytes, hard))).map(drop)
+}
+
+/// Whose nice value [`getpriority`] and [`setpriority`] refer to.
+#[cfg(feature = "process")]
+#[cfg_attr(docsrs, doc(cfg(feature = "process")))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PriorityWho {
+    /// A process (`PRIO_PROCESS`), or the calling process for pid `0`.
+    Process(crate::unistd::Pid),
+    /// A process group (`PRIO_PGRP`), or the calling process's group for
+    /// pid `0`.
+    ProcessGroup(crate::unistd::Pid),
+    /// All processes of a real user ID (`PRIO_USER`).
+    #[cfg(feature = "user")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "user")))]
+    User(crate::unistd::Uid),
+}
+
+cfg_if! {
+    if #[cfg(all(feature = "process", target_os = "linux", target_env = "gnu"))] {
+        type PriorityWhich = libc::__priority_which_t;
+    } else if #[cfg(feature = "process")] {
+        type PriorityWhich = c_int;
+    }
+}
+
+#[cfg(feature = "process")]
+impl PriorityWho {
+    #[allow(clippy::unnecessary_cast)]
+    fn to_raw(self) -> (PriorityWhich, libc::id_t) {
+        match self {
+            PriorityWho::Process(pid) => {
+                (libc::PRIO_PROCESS, pid.as_raw() as libc::id_t)
+            }
+            PriorityWho::ProcessGroup(pgid) => {
+                (libc::PRIO_PGRP, pgid.as_raw() as libc::id_t)
+            }
+            #[cfg(feature = "user")]
+            PriorityWho::User(uid) => {
+                (libc::PRIO_USER, uid.as_raw() as libc::id_t)
+            }
+        }
+    }
+}
+
+/// Get the nice value of a process, process group or user
+///
+/// For a process group or user, this is the lowest (most favorable) nice
+/// value among its processes.
+///
+/// # References
+///
+/// [getpriority(2)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/getpriority.html)
+#[cfg(feature = "process")]
+#[cfg_attr(docsrs, doc(cfg(feature = "process")))]
+pub fn getpriority(who: PriorityWho) -> Result<c_int> {
+    let (which, who) = who.to_raw();
+    // -1 is a valid nice value, so errno must be checked
+    Errno::clear();
+    let res = unsafe { libc::getpriority(which, who) };
+
+    match Errno::result(res) {
+        Ok(..) | Err(Errno::UnknownErrno) => Ok(res),
+        Err(e) => Err(e),
+    }
+}
+
+/// Set the nice value of a process, process group or user
+///
+/// Lowering a nice value, making a process more favorable, needs the
+/// `CAP_SYS_NICE` capability on Linux.
+///
+/// # References
+///
+/// [setpriority(2)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/setpriority.html)
+#[cfg(feature = "process")]
+#[cfg_attr(docsrs, doc(cfg(feature = "process")))]
+pub fn setpriority(who: PriorityWho, prio: c_int) -> Result<()> {
+    let (which, who) = who.to_raw();
+    let res = unsafe { libc::setpriority(which, who, prio) };
+
+    Errno::result(res).map(drop)
+}
+
 libc_enum! {
     /// Whose resource usage should be returned by [`getrusage`].
     #[repr(i32)]
@@ -440,4 +619,32 @@ mod test {
         assert_eq!(user.tv_sec(), rusage.ru_utime.tv_sec);
         assert_eq!(user.tv_usec(), rusage.ru_utime.tv_usec);
     }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "freebsd",
+        target_os = "openbsd"
+    ))]
+    #[test]
+    pub fn test_thread_cpu_time() {
+        use crate::sys::time::TimeValLike;
+
+        // Spin in a fresh thread so that its usage starts from zero.
+        let (thread, process) = std::thread::spawn(|| {
+            let mut numbers: Vec<i32> = (1..1_000_000).collect();
+            numbers.iter_mut().for_each(|item| *item *= 2);
+            assert_eq!(numbers[100..200].iter().sum::<i32>(), 30_100);
+
+            let thread = getrusage(UsageWho::RUSAGE_THREAD).unwrap();
+            let process = getrusage(UsageWho::RUSAGE_SELF).unwrap();
+            (thread, process)
+        })
+        .join()
+        .unwrap();
+
+        let thread_cpu = thread.user_time() + thread.system_time();
+        let process_cpu = process.user_time() + process.system_time();
+        assert!(thread_cpu.num_microseconds() > 0);
+        assert!(thread_cpu <= process_cpu);
+    }
 }
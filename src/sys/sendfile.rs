@@ -77,6 +77,43 @@ pub fn sendfile64<F1: AsFd, F2: AsFd>(
     Errno::result(ret).map(|r| r as usize)
 }
 
+/// Copy `count` bytes to `out_fd` from `in_fd`, calling [`sendfile64`] as
+/// many times as needed.
+///
+/// A single `sendfile` may copy fewer bytes than asked for, for example when
+/// a signal interrupts it or when sending to a socket.  This function
+/// instead carries on until `count` bytes were copied or the end of `in_fd`
+/// is reached, retrying on `EINTR`, and returns the total number of bytes
+/// copied.  Offsets are 64 bits wide on all platforms, and `offset` is
+/// treated as with `sendfile64`: if it is `Some`, it points after the last
+/// byte copied on return.
+///
+/// If `out_fd` is non-blocking, copying stops when it would block.  The
+/// number of bytes copied so far is returned, or `EAGAIN` if there were
+/// none.  Any other error is returned as is, and the bytes already copied
+/// are lost track of unless `offset` is `Some`.
+#[cfg(target_os = "linux")]
+#[cfg_attr(docsrs, doc(cfg(all())))]
+pub fn sendfile64_all<F1: AsFd, F2: AsFd>(
+    out_fd: F1,
+    in_fd: F2,
+    mut offset: Option<&mut libc::off64_t>,
+    count: usize,
+) -> Result<usize> {
+    let mut total = 0;
+    while total < count {
+        let off = offset.as_deref_mut();
+        match sendfile64(&out_fd, &in_fd, off, count - total) {
+            Ok(0) => break,
+            Ok(n) => total += n,
+            Err(Errno::EINTR) => (),
+            Err(Errno::EAGAIN) if total > 0 => break,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(total)
+}
+
 cfg_if! {
     if #[cfg(any(target_os = "dragonfly",
                  target_os = "freebsd",
@@ -4,6 +4,13 @@
 //! Operating system signals.
 
 use crate::errno::Errno;
+#[cfg(any(target_os = "android",
+          target_os = "dragonfly",
+          target_os = "freebsd",
+          target_os = "linux",
+          target_os = "netbsd"))]
+#[cfg(feature = "signal")]
+use crate::sys::time::TimeSpec;
 use crate::{Error, Result};
 use cfg_if::cfg_if;
 use std::fmt;
@@ -475,6 +482,10 @@ pub struct SigSet {
 
 impl SigSet {
     /// Initialize to include all signals.
+    ///
+    /// The set really does contain every signal, `SIGKILL` and `SIGSTOP`
+    /// included, but those two can never be blocked: installing this set as
+    /// a signal mask silently leaves them out.
     #[doc(alias("sigfillset"))]
     pub fn all() -> SigSet {
         let mut sigset = mem::MaybeUninit::uninit();
@@ -767,6 +778,51 @@ pub unsafe fn sigaction(signal: Signal, sigaction: &SigAction) -> Result<SigActi
     Errno::result(res).map(|_| SigAction { sigaction: oldact.assume_init() })
 }
 
+/// Restores the previous action for a signal when dropped.
+///
+/// Returned by [`SigActionGuard::install`].  This is handy to catch a signal,
+/// such as `SIGCHLD`, for the duration of a scope without leaking the handler
+/// to the rest of the program.
+#[derive(Debug)]
+#[must_use = "the previous action is restored as soon as the guard is dropped"]
+pub struct SigActionGuard {
+    signal: Signal,
+    old: SigAction,
+}
+
+impl SigActionGuard {
+    /// Installs `sigaction` for `signal`, as with [`sigaction`], and returns
+    /// a guard that reinstalls the previous action when dropped.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as for [`sigaction`] apply, both to the new
+    /// action and to the previous one, which the guard reinstalls.
+    pub unsafe fn install(signal: Signal, sigaction: &SigAction) -> Result<SigActionGuard> {
+        let old = self::sigaction(signal, sigaction)?;
+        Ok(SigActionGuard { signal, old })
+    }
+
+    /// The signal whose action the guard restores.
+    pub fn signal(&self) -> Signal {
+        self.signal
+    }
+
+    /// The previous action, which the guard restores.
+    pub fn old_action(&self) -> &SigAction {
+        &self.old
+    }
+}
+
+impl Drop for SigActionGuard {
+    fn drop(&mut self) {
+        // The previous action was valid when it was replaced, so restoring it
+        // can only fail if something is very wrong; there's nothing useful to
+        // do about it in a destructor.
+        let _ = unsafe { sigaction(self.signal, &self.old) };
+    }
+}
+
 /// Signal management (see [signal(3p)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/signal.html))
 ///
 /// Installs `handler` for the given `signal`, returning the previous signal
@@ -905,6 +961,32 @@ pub fn sigprocmask(how: SigmaskHow, set: Option<&SigSet>, oldset: Option<&mut Si
     Errno::result(res).map(drop)
 }
 
+/// Synchronously wait for one of the signals in `set` to become pending, and
+/// accept it.
+///
+/// Unlike [`SigSet::wait`], this returns the full `siginfo_t` of the accepted
+/// signal, and gives up with `EAGAIN` once `timeout` has elapsed.  A `timeout`
+/// of `None` waits forever.  The signals in `set` should be blocked in every
+/// thread beforehand, otherwise they may be delivered to a handler instead.
+///
+/// See also [`sigtimedwait(2)`](https://man7.org/linux/man-pages/man2/sigtimedwait.2.html)
+#[cfg(any(target_os = "android",
+          target_os = "dragonfly",
+          target_os = "freebsd",
+          target_os = "linux",
+          target_os = "netbsd"))]
+#[cfg_attr(docsrs, doc(cfg(all())))]
+pub fn sigtimedwait(set: &SigSet, timeout: Option<TimeSpec>) -> Result<libc::siginfo_t> {
+    let mut siginfo = mem::MaybeUninit::uninit();
+    let res = unsafe {
+        libc::sigtimedwait(&set.sigset as *const libc::sigset_t,
+                           siginfo.as_mut_ptr(),
+                           timeout.as_ref().map_or_else(ptr::null, |t| t.as_ref()))
+    };
+
+    Errno::result(res).map(|_| unsafe { siginfo.assume_init() })
+}
+
 /// Send a signal to a process
 ///
 /// # Arguments
@@ -955,6 +1037,33 @@ pub fn killpg<T: Into<Option<Signal>>>(pgrp: Pid, signal: T) -> Result<()> {
     Errno::result(res).map(drop)
 }
 
+/// Send a signal to a single thread
+///
+/// # Arguments
+///
+/// * `tgid` -   Thread group (process) the thread belongs to.  The call fails
+///              with `ESRCH` if `tid` is not part of it, which guards against
+///              the thread having exited and its ID being reused.
+/// * `tid` -    Thread to signal, as returned by
+///              [`gettid`](crate::unistd::gettid).
+/// * `signal` - Signal to send. If `None`, `tgkill` will only perform error
+///              checking and won't send any signal.
+///
+/// See Also [tgkill(2)](https://man7.org/linux/man-pages/man2/tgkill.2.html).
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[cfg_attr(docsrs, doc(cfg(all())))]
+pub fn tgkill<T: Into<Option<Signal>>>(tgid: Pid, tid: Pid, signal: T) -> Result<()> {
+    let res = unsafe { libc::syscall(libc::SYS_tgkill,
+                                     libc::pid_t::from(tgid),
+                                     libc::pid_t::from(tid),
+                                     match signal.into() {
+                                         Some(s) => s as libc::c_int,
+                                         None => 0,
+                                     }) };
+
+    Errno::result(res).map(drop)
+}
+
 /// Send a signal to the current thread
 ///
 /// See Also [raise(3)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/raise.html)
@@ -1179,6 +1288,34 @@ mod tests {
         assert!(two_signals.contains(SIGUSR2));
     }
 
+    #[test]
+    #[cfg(not(target_os = "redox"))]
+    fn test_all_cannot_block_sigkill() {
+        let all = SigSet::all();
+        for signal in Signal::iterator() {
+            assert!(all.contains(signal));
+        }
+
+        thread::spawn(|| {
+            let prev_mask = SigSet::thread_get_mask()
+                .expect("Failed to get existing signal mask!");
+
+            SigSet::all().thread_set_mask().expect("assertion failed");
+            let new_mask =
+                SigSet::thread_get_mask().expect("Failed to get new mask!");
+
+            assert!(new_mask.contains(SIGTERM));
+            assert!(!new_mask.contains(SIGKILL));
+            assert!(!new_mask.contains(SIGSTOP));
+
+            prev_mask
+                .thread_set_mask()
+                .expect("Failed to revert signal mask!");
+        })
+        .join()
+        .unwrap();
+    }
+
     #[test]
     #[cfg(not(target_os = "redox"))]
     fn test_thread_signal_set_mask() {
@@ -1328,6 +1465,31 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    #[cfg(any(target_os = "android",
+              target_os = "dragonfly",
+              target_os = "freebsd",
+              target_os = "linux",
+              target_os = "netbsd"))]
+    fn test_sigtimedwait() {
+        use crate::sys::time::{TimeSpec, TimeValLike};
+
+        thread::spawn(|| {
+            let mut mask = SigSet::empty();
+            mask.add(SIGUSR1);
+            mask.thread_block().unwrap();
+
+            let timeout = TimeSpec::milliseconds(10);
+            assert_eq!(sigtimedwait(&mask, Some(timeout)).unwrap_err(), Errno::EAGAIN);
+
+            raise(SIGUSR1).unwrap();
+            let siginfo = sigtimedwait(&mask, Some(timeout)).unwrap();
+            assert_eq!(siginfo.si_signo, SIGUSR1 as libc::c_int);
+        })
+        .join()
+        .unwrap();
+    }
+
     #[test]
     fn test_from_sigset_t_unchecked() {
         let src_set = SigSet::empty();
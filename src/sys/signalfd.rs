@@ -18,6 +18,10 @@
 use crate::errno::Errno;
 pub use crate::sys::signal::{self, SigSet};
 use crate::Result;
+#[cfg(feature = "process")]
+use crate::unistd::Pid;
+#[cfg(feature = "user")]
+use crate::unistd::Uid;
 pub use libc::signalfd_siginfo as siginfo;
 
 use std::mem;
@@ -119,6 +123,82 @@ impl SignalFd {
             Err(error) => Err(error),
         }
     }
+
+    /// Like [`read_signal`](SignalFd::read_signal), but decode the result
+    /// into a [`SiginfoExt`].
+    pub fn read_signal_typed(&mut self) -> Result<Option<SiginfoExt>> {
+        self.read_signal().map(|info| info.map(SiginfoExt))
+    }
+}
+
+/// A signal read from a [`SignalFd`], with accessors for the commonly used
+/// fields of the raw [`siginfo`].
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SiginfoExt(siginfo);
+
+impl SiginfoExt {
+    /// The signal that was received, or `None` if it isn't a
+    /// [`Signal`](signal::Signal), which can happen for real-time signals put
+    /// in the mask through a raw `sigset_t`.  See [`signo`](Self::signo) for
+    /// those.
+    pub fn signal(&self) -> Option<signal::Signal> {
+        signal::Signal::try_from(self.signo()).ok()
+    }
+
+    /// The number of the signal that was received, the `ssi_signo` field.
+    pub fn signo(&self) -> libc::c_int {
+        self.0.ssi_signo as libc::c_int
+    }
+
+    /// The signal code, the `si_code` field of the `siginfo_t`.
+    pub fn code(&self) -> libc::c_int {
+        self.0.ssi_code
+    }
+
+    /// The process that sent the signal, or for `SIGCHLD`, the child whose
+    /// state changed.
+    #[cfg(feature = "process")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "process")))]
+    pub fn sender_pid(&self) -> Pid {
+        Pid::from_raw(self.0.ssi_pid as libc::pid_t)
+    }
+
+    /// The real user ID of the process that sent the signal.
+    #[cfg(feature = "user")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "user")))]
+    pub fn sender_uid(&self) -> Uid {
+        Uid::from_raw(self.0.ssi_uid)
+    }
+
+    /// For `SIGCHLD`, how the child changed state: one of the `CLD_*`
+    /// constants, such as `libc::CLD_EXITED`.  `None` for other signals.
+    pub fn child_code(&self) -> Option<libc::c_int> {
+        self.is_sigchld().then(|| self.0.ssi_code)
+    }
+
+    /// For `SIGCHLD`, the child's exit status if it exited, or else the
+    /// signal that killed, stopped or continued it.  `None` for other
+    /// signals.
+    pub fn child_status(&self) -> Option<libc::c_int> {
+        self.is_sigchld().then(|| self.0.ssi_status)
+    }
+
+    fn is_sigchld(&self) -> bool {
+        self.0.ssi_signo == libc::SIGCHLD as u32
+    }
+}
+
+impl AsRef<siginfo> for SiginfoExt {
+    fn as_ref(&self) -> &siginfo {
+        &self.0
+    }
+}
+
+impl From<SiginfoExt> for siginfo {
+    fn from(info: SiginfoExt) -> Self {
+        info.0
+    }
 }
 
 impl AsFd for SignalFd {
@@ -143,6 +223,168 @@ impl Iterator for SignalFd {
     }
 }
 
+/// A reactor that reaps children as they change state, using a `signalfd`
+/// for `SIGCHLD` inside an [`Epoll`](crate::sys::epoll::Epoll) instance.
+///
+/// Creating a `SignalEpoll` blocks `SIGCHLD` in the calling thread, so that
+/// it is queued on the signalfd instead of being delivered; dropping it
+/// restores the previous signal mask.  Like for any signalfd, `SIGCHLD` must
+/// be blocked in every thread of the process, so create the `SignalEpoll`
+/// before spawning threads, and drop it on the thread that created it.
+///
+/// Only the children registered in the reactor's
+/// [`ManagedChildren`](crate::sys::wait::ManagedChildren), through
+/// [`children_mut`](SignalEpoll::children_mut), are reaped, so other code
+/// can keep waiting for its own children.  [`poll`](SignalEpoll::poll)
+/// collects their statuses with `waitpid`, which includes ptrace stops of
+/// traced children.
+///
+/// # Examples
+///
+/// ```
+/// # use nix::sys::signalfd::SignalEpoll;
+/// # use nix::sys::wait::WaitStatus;
+/// # use nix::unistd::ForkResult;
+/// let mut reactor = SignalEpoll::new().unwrap();
+/// match unsafe { reactor.children_mut().fork() }.unwrap() {
+///     ForkResult::Child => unsafe { libc::_exit(7) },
+///     ForkResult::Parent { child } => {
+///         let reaped = reactor.poll(-1).unwrap();
+///         assert_eq!(reaped, vec![(child, WaitStatus::Exited(child, 7))]);
+///     }
+/// }
+/// ```
+#[cfg(all(feature = "event", feature = "process", not(target_env = "uclibc")))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "event", feature = "process"))))]
+#[derive(Debug)]
+pub struct SignalEpoll {
+    epoll: crate::sys::epoll::Epoll,
+    sfd: SignalFd,
+    old_mask: SigSet,
+    children: crate::sys::wait::ManagedChildren,
+}
+
+#[cfg(all(feature = "event", feature = "process", not(target_env = "uclibc")))]
+impl SignalEpoll {
+    /// Block `SIGCHLD` in the calling thread and start watching for it.
+    pub fn new() -> Result<Self> {
+        use crate::sys::epoll::{Epoll, EpollCreateFlags, EpollEvent, EpollFlags};
+
+        let mut mask = SigSet::empty();
+        mask.add(signal::SIGCHLD);
+        let old_mask = mask.thread_swap_mask(signal::SigmaskHow::SIG_BLOCK)?;
+
+        let res = SignalFd::with_flags(&mask, SfdFlags::SFD_NONBLOCK | SfdFlags::SFD_CLOEXEC)
+            .and_then(|sfd| {
+                let epoll = Epoll::new(EpollCreateFlags::EPOLL_CLOEXEC)?;
+                epoll.add(&sfd, EpollEvent::new(EpollFlags::EPOLLIN, 0))?;
+                Ok((epoll, sfd))
+            });
+        match res {
+            Ok((epoll, sfd)) => Ok(SignalEpoll {
+                epoll,
+                sfd,
+                old_mask,
+                children: crate::sys::wait::ManagedChildren::new(),
+            }),
+            Err(e) => {
+                let _ = old_mask.thread_set_mask();
+                Err(e)
+            }
+        }
+    }
+
+    /// The children this reactor reaps.
+    pub fn children(&self) -> &crate::sys::wait::ManagedChildren {
+        &self.children
+    }
+
+    /// The children this reactor reaps, to fork or register new ones.
+    pub fn children_mut(&mut self) -> &mut crate::sys::wait::ManagedChildren {
+        &mut self.children
+    }
+
+    /// Wait up to `timeout` milliseconds, or forever if it is `-1`, for
+    /// registered children to change state, and return the pid and status of
+    /// every one that did.
+    ///
+    /// Returns immediately if some of them have changed state already.
+    /// Returns an empty `Vec` if the timeout expired, and fails with
+    /// `ECHILD` if no children are registered, rather than waiting for a
+    /// `SIGCHLD` that can never come.  Children that exited or were killed
+    /// are unregistered once reaped.
+    pub fn poll(&mut self, timeout: isize) -> Result<Vec<(Pid, crate::sys::wait::WaitStatus)>> {
+        use crate::sys::epoll::EpollEvent;
+        use std::time::{Duration, Instant};
+
+        let deadline = u64::try_from(timeout)
+            .ok()
+            .map(|ms| Instant::now() + Duration::from_millis(ms));
+        loop {
+            let reaped = self.reap()?;
+            if !reaped.is_empty() {
+                return Ok(reaped);
+            }
+            let timeout = match deadline {
+                Some(deadline) => {
+                    let left = deadline.saturating_duration_since(Instant::now());
+                    left.as_millis().min(isize::MAX as u128) as isize
+                }
+                None => -1,
+            };
+            let mut events = [EpollEvent::empty()];
+            if self.epoll.wait(&mut events, timeout)? == 0 {
+                return Ok(reaped);
+            }
+            // Several SIGCHLDs may have been merged into one, and some may
+            // come from children other code owns, so the signals only tell
+            // that a registered child may have something to report.
+            while self.sfd.read_signal()?.is_some() {}
+        }
+    }
+
+    /// Collect the statuses of all registered children that changed state,
+    /// without blocking.  Fails with `ECHILD` only if none are registered.
+    fn reap(&mut self) -> Result<Vec<(Pid, crate::sys::wait::WaitStatus)>> {
+        use crate::sys::wait::{ManagedStatus, WaitPidFlag, WaitStatus};
+
+        let flags = WaitPidFlag::WNOHANG | WaitPidFlag::__WALL;
+        let mut reaped = Vec::new();
+        loop {
+            match self.children.reap_managed(flags) {
+                Ok(ManagedStatus::Managed(WaitStatus::StillAlive)) => return Ok(reaped),
+                Ok(ManagedStatus::Managed(status)) => {
+                    if let Some(pid) = status.pid() {
+                        reaped.push((pid, status));
+                    }
+                }
+                // Not reported with WNOHANG
+                Ok(ManagedStatus::Unmanaged(_)) => return Ok(reaped),
+                Err(Errno::ECHILD) if reaped.is_empty() => return Err(Errno::ECHILD),
+                Err(Errno::ECHILD) => return Ok(reaped),
+                Err(Errno::EINTR) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "event", feature = "process", not(target_env = "uclibc")))]
+impl AsFd for SignalEpoll {
+    /// The epoll instance, which becomes readable when children may have
+    /// changed state.
+    fn as_fd(&self) -> BorrowedFd {
+        self.epoll.as_fd()
+    }
+}
+
+#[cfg(all(feature = "event", feature = "process", not(target_env = "uclibc")))]
+impl Drop for SignalEpoll {
+    fn drop(&mut self) {
+        let _ = self.old_mask.thread_set_mask();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -19,6 +19,8 @@ use std::io::{IoSlice, IoSliceMut};
 #[cfg(feature = "net")]
 use std::net;
 use std::os::unix::io::RawFd;
+#[cfg(feature = "uio")]
+use std::os::unix::io::{AsRawFd, BorrowedFd, FromRawFd, OwnedFd};
 use std::{mem, ptr};
 
 #[deny(missing_docs)]
@@ -2063,6 +2065,73 @@ pub fn recvmsg<'a, 'outer, 'inner, S>(fd: RawFd, iov: &'outer mut [IoSliceMut<'i
 
     Ok(unsafe { read_mhdr(mhdr, r, msg_controllen, address.assume_init()) })
 }
+
+/// Send file descriptors over a Unix domain socket.
+///
+/// The descriptors travel in a single `SCM_RIGHTS` control message, along
+/// with one byte of ordinary data, because some socket types can't carry
+/// ancillary data on its own.  Receive them with [`recv_fds`].
+///
+/// # Examples
+///
+/// ```
+/// # use nix::sys::socket::*;
+/// # use nix::unistd::pipe;
+/// # use std::os::unix::io::{AsFd, FromRawFd, OwnedFd};
+/// let (fd1, fd2) = socketpair(AddressFamily::Unix, SockType::Stream, None,
+///     SockFlag::empty())
+///     .unwrap();
+/// let (r, _w) = pipe().unwrap();
+/// let r = unsafe { OwnedFd::from_raw_fd(r) };
+///
+/// send_fds(fd1, &[r.as_fd()]).unwrap();
+/// let fds = recv_fds(fd2, 1).unwrap();
+/// assert_eq!(fds.len(), 1);
+/// ```
+pub fn send_fds(sock: RawFd, fds: &[BorrowedFd<'_>]) -> Result<()> {
+    let raw_fds: Vec<RawFd> = fds.iter().map(|fd| fd.as_raw_fd()).collect();
+    let iov = [IoSlice::new(&[0u8])];
+    let cmsgs = [ControlMessage::ScmRights(&raw_fds)];
+
+    sendmsg::<()>(sock, &iov, &cmsgs, MsgFlags::empty(), None).map(drop)
+}
+
+/// Receive up to `max` file descriptors sent by [`send_fds`].
+///
+/// The received descriptors have the close-on-exec flag set where the
+/// platform supports `MSG_CMSG_CLOEXEC`.  If the sender passed more than
+/// `max` descriptors, the excess ones are closed by the kernel.  An empty
+/// `Vec` is returned if the peer closed the connection.
+pub fn recv_fds(sock: RawFd, max: usize) -> Result<Vec<OwnedFd>> {
+    let mut byte = [0u8];
+    let mut iov = [IoSliceMut::new(&mut byte)];
+    let space = unsafe {
+        libc::CMSG_SPACE((max * mem::size_of::<RawFd>()) as libc::c_uint)
+    };
+    let mut cmsg_buffer = Vec::with_capacity(space as usize);
+    cfg_if! {
+        if #[cfg(any(target_os = "android",
+                     target_os = "dragonfly",
+                     target_os = "freebsd",
+                     target_os = "linux",
+                     target_os = "netbsd",
+                     target_os = "openbsd"))] {
+            let flags = MsgFlags::MSG_CMSG_CLOEXEC;
+        } else {
+            let flags = MsgFlags::empty();
+        }
+    }
+
+    let msg = recvmsg::<()>(sock, &mut iov, Some(&mut cmsg_buffer), flags)?;
+    let mut fds = Vec::new();
+    for cmsg in msg.cmsgs() {
+        if let ControlMessageOwned::ScmRights(received) = cmsg {
+            fds.extend(received.into_iter()
+                       .map(|fd| unsafe { OwnedFd::from_raw_fd(fd) }));
+        }
+    }
+    Ok(fds)
+}
 }
 
 /// Create an endpoint for communication
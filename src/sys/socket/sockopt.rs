@@ -10,12 +10,19 @@ use std::mem::{self, MaybeUninit};
 #[cfg(target_family = "unix")]
 use std::os::unix::ffi::OsStrExt;
 use std::os::unix::io::RawFd;
+#[cfg(target_os = "linux")]
+use std::os::unix::io::{FromRawFd, OwnedFd};
 
 // Constants
 // TCP_CA_NAME_MAX isn't defined in user space include files
 #[cfg(any(target_os = "freebsd", target_os = "linux"))]
 #[cfg(feature = "net")]
 const TCP_CA_NAME_MAX: usize = 16;
+// SO_PEERPIDFD was added in Linux 6.5 and isn't in libc yet
+#[cfg(all(target_os = "linux", not(target_arch = "sparc64")))]
+const SO_PEERPIDFD: c_int = 77;
+#[cfg(all(target_os = "linux", target_arch = "sparc64"))]
+const SO_PEERPIDFD: c_int = 0x0056;
 
 /// Helper for implementing `SetSockOpt` for a given socket option. See
 /// [`::sys::socket::SetSockOpt`](sys/socket/trait.SetSockOpt.html).
@@ -510,6 +517,22 @@ sockopt_impl!(
     libc::SO_PEERCRED,
     super::UnixCredentials
 );
+#[cfg(target_os = "linux")]
+sockopt_impl!(
+    /// Return a pidfd referring to the foreign process connected to this
+    /// socket, as it was when the connection was made.
+    ///
+    /// Unlike the pid from [`PeerCredentials`], the pidfd can't end up
+    /// referring to an unrelated process if the peer exits and its pid is
+    /// reused.  Requires Linux 6.5 or later; older kernels fail with
+    /// `ENOPROTOOPT`.
+    PeerPidfd,
+    GetOnly,
+    libc::SOL_SOCKET,
+    SO_PEERPIDFD,
+    OwnedFd,
+    GetOwnedFd
+);
 #[cfg(any(target_os = "ios", target_os = "macos"))]
 #[cfg(feature = "net")]
 sockopt_impl!(
@@ -1299,6 +1322,40 @@ impl<'a> Set<'a, usize> for SetUsize {
     }
 }
 
+/// Getter for a file descriptor returned by the kernel.
+#[cfg(target_os = "linux")]
+struct GetOwnedFd {
+    len: socklen_t,
+    val: MaybeUninit<c_int>,
+}
+
+#[cfg(target_os = "linux")]
+impl Get<OwnedFd> for GetOwnedFd {
+    fn uninit() -> Self {
+        GetOwnedFd {
+            len: mem::size_of::<c_int>() as socklen_t,
+            val: MaybeUninit::uninit(),
+        }
+    }
+
+    fn ffi_ptr(&mut self) -> *mut c_void {
+        self.val.as_mut_ptr() as *mut c_void
+    }
+
+    fn ffi_len(&mut self) -> *mut socklen_t {
+        &mut self.len
+    }
+
+    unsafe fn assume_init(self) -> OwnedFd {
+        assert_eq!(
+            self.len as usize,
+            mem::size_of::<c_int>(),
+            "invalid getsockopt implementation"
+        );
+        OwnedFd::from_raw_fd(self.val.assume_init())
+    }
+}
+
 /// Getter for a `OsString` value.
 struct GetOsString<T: AsMut<[u8]>> {
     len: socklen_t,
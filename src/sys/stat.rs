@@ -267,6 +267,16 @@ pub fn fstat(fd: RawFd) -> Result<FileStat> {
     Ok(unsafe { dst.assume_init() })
 }
 
+/// Get the status of the file at `pathname`, relative to the directory
+/// referred to by `dirfd`.
+///
+/// If `f` contains `AT_SYMLINK_NOFOLLOW` and `pathname` names a symbolic
+/// link, the link itself is described.  On Linux and Android, `f` may also
+/// contain `AT_EMPTY_PATH`, to describe the file referred to by `dirfd` if
+/// `pathname` is empty, and `AT_NO_AUTOMOUNT`.  Other flags are rejected
+/// with `EINVAL`.
+///
+/// See also [fstatat(2)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/fstatat.html).
 #[cfg(not(target_os = "redox"))]
 #[cfg_attr(docsrs, doc(cfg(all())))]
 pub fn fstatat<P: ?Sized + NixPath>(
@@ -416,21 +426,86 @@ pub fn futimens(fd: RawFd, atime: &TimeSpec, mtime: &TimeSpec) -> Result<()> {
 }
 
 /// Flags for `utimensat` function.
-// TODO: replace with fcntl::AtFlags
 #[derive(Clone, Copy, Debug)]
 pub enum UtimensatFlags {
     FollowSymlink,
     NoFollowSymlink,
 }
 
+impl From<UtimensatFlags> for AtFlags {
+    fn from(flag: UtimensatFlags) -> Self {
+        match flag {
+            UtimensatFlags::FollowSymlink => AtFlags::empty(),
+            UtimensatFlags::NoFollowSymlink => AtFlags::AT_SYMLINK_NOFOLLOW,
+        }
+    }
+}
+
+/// A timestamp to set with [`utimensat`].
+#[cfg(not(target_os = "redox"))]
+#[cfg_attr(docsrs, doc(cfg(all())))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum UtimeSpec {
+    /// Set the timestamp to the current time (`UTIME_NOW`).
+    Now,
+    /// Leave the timestamp unchanged (`UTIME_OMIT`).
+    Omit,
+    /// Set the timestamp to the given time.
+    Time(TimeSpec),
+}
+
+#[cfg(not(target_os = "redox"))]
+impl UtimeSpec {
+    fn to_timespec(self) -> libc::timespec {
+        let nsec = match self {
+            UtimeSpec::Time(ts) => return *ts.as_ref(),
+            UtimeSpec::Now => libc::UTIME_NOW,
+            UtimeSpec::Omit => libc::UTIME_OMIT,
+        };
+        #[allow(clippy::unnecessary_cast)]
+        // The cast is not unnecessary on all platforms.
+        let ts = TimeSpec::new(0, nsec as _);
+        *ts.as_ref()
+    }
+}
+
+#[cfg(not(target_os = "redox"))]
+impl From<TimeSpec> for UtimeSpec {
+    fn from(ts: TimeSpec) -> Self {
+        UtimeSpec::Time(ts)
+    }
+}
+
+#[cfg(not(target_os = "redox"))]
+impl From<&TimeSpec> for UtimeSpec {
+    fn from(ts: &TimeSpec) -> Self {
+        UtimeSpec::Time(*ts)
+    }
+}
+
+/// `None` leaves the timestamp unchanged.
+#[cfg(not(target_os = "redox"))]
+impl From<Option<TimeSpec>> for UtimeSpec {
+    fn from(ts: Option<TimeSpec>) -> Self {
+        ts.map_or(UtimeSpec::Omit, UtimeSpec::Time)
+    }
+}
+
 /// Change the access and modification times of a file.
 ///
 /// The file to be changed is determined relative to the directory associated
 /// with the file descriptor `dirfd` or the current working directory
 /// if `dirfd` is `None`.
 ///
-/// If `flag` is `UtimensatFlags::NoFollowSymlink` and `path` names a symbolic link,
-/// then the mode of the symbolic link is changed.
+/// `atime` and `mtime` are each either a [`TimeSpec`], or a [`UtimeSpec`]
+/// to set the timestamp to the current time or leave it unchanged.  `None`
+/// also leaves it unchanged.
+///
+/// `flag` is either a [`UtimensatFlags`] or an [`AtFlags`].  If it contains
+/// `AT_SYMLINK_NOFOLLOW` and `path` names a symbolic link,
+/// then the times of the symbolic link are changed.  On Linux 5.8 and later,
+/// if `flag` contains `AT_EMPTY_PATH` and `path` is empty, the file referred
+/// to by `dirfd` is changed.  Other flags are rejected with `EINVAL`.
 ///
 /// `utimensat(None, path, times, UtimensatFlags::FollowSymlink)` is identical to
 /// `utimes(path, times)`. The latter is a deprecated API so prefer using the
@@ -441,24 +516,28 @@ pub enum UtimensatFlags {
 /// [utimensat(2)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/utimens.html).
 #[cfg(not(target_os = "redox"))]
 #[cfg_attr(docsrs, doc(cfg(all())))]
-pub fn utimensat<P: ?Sized + NixPath>(
+pub fn utimensat<P, A, M, F>(
     dirfd: Option<RawFd>,
     path: &P,
-    atime: &TimeSpec,
-    mtime: &TimeSpec,
-    flag: UtimensatFlags,
-) -> Result<()> {
-    let atflag = match flag {
-        UtimensatFlags::FollowSymlink => AtFlags::empty(),
-        UtimensatFlags::NoFollowSymlink => AtFlags::AT_SYMLINK_NOFOLLOW,
-    };
-    let times: [libc::timespec; 2] = [*atime.as_ref(), *mtime.as_ref()];
+    atime: A,
+    mtime: M,
+    flag: F,
+) -> Result<()>
+where
+    P: ?Sized + NixPath,
+    A: Into<UtimeSpec>,
+    M: Into<UtimeSpec>,
+    F: Into<AtFlags>,
+{
+    let flag = flag.into();
+    let times: [libc::timespec; 2] =
+        [atime.into().to_timespec(), mtime.into().to_timespec()];
     let res = path.with_nix_path(|cstr| unsafe {
         libc::utimensat(
             at_rawfd(dirfd),
             cstr.as_ptr(),
             &times[0],
-            atflag.bits() as libc::c_int,
+            flag.bits() as libc::c_int,
         )
     })?;
 
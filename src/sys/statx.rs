@@ -0,0 +1,265 @@
+//! Get extended file status, including birth time and mount ID
+//!
+//! For more documentation, please read
+//! [statx(2)](https://man7.org/linux/man-pages/man2/statx.2.html).
+
+use crate::errno::Errno;
+use crate::sys::time::TimeSpec;
+use crate::{NixPath, Result};
+use libc::c_int;
+use std::mem;
+use std::os::unix::io::RawFd;
+
+bitflags::bitflags! {
+    /// Flags for [`statx`], controlling how the path is resolved and how
+    /// the attributes are synchronized with a remote file system.
+    pub struct StatxFlags: c_int {
+        /// If `path` is empty, operate on `dirfd` itself.
+        const AT_EMPTY_PATH = libc::AT_EMPTY_PATH;
+        /// Don't automount the terminal component of `path`.
+        const AT_NO_AUTOMOUNT = 0x800;
+        /// If `path` is a symbolic link, report on the link itself.
+        const AT_SYMLINK_NOFOLLOW = libc::AT_SYMLINK_NOFOLLOW;
+        /// Synchronize the attributes with the server before reporting
+        /// them, even if that wouldn't happen for `stat`.
+        const AT_STATX_FORCE_SYNC = 0x2000;
+        /// Report whatever attributes are cached locally, without
+        /// synchronizing with the server.
+        const AT_STATX_DONT_SYNC = 0x4000;
+    }
+}
+
+bitflags::bitflags! {
+    /// The attributes to request from, and reported by, [`statx`].
+    ///
+    /// The file system may report attributes that weren't requested, and
+    /// may fail to report ones that were; [`Statx::mask`] tells which are
+    /// valid.
+    pub struct StatxMask: u32 {
+        /// The file type part of [`Statx::mode`].
+        const STATX_TYPE = 0x0001;
+        /// The permission part of [`Statx::mode`].
+        const STATX_MODE = 0x0002;
+        /// [`Statx::nlink`].
+        const STATX_NLINK = 0x0004;
+        /// [`Statx::uid`].
+        const STATX_UID = 0x0008;
+        /// [`Statx::gid`].
+        const STATX_GID = 0x0010;
+        /// [`Statx::atime`].
+        const STATX_ATIME = 0x0020;
+        /// [`Statx::mtime`].
+        const STATX_MTIME = 0x0040;
+        /// [`Statx::ctime`].
+        const STATX_CTIME = 0x0080;
+        /// [`Statx::ino`].
+        const STATX_INO = 0x0100;
+        /// [`Statx::size`].
+        const STATX_SIZE = 0x0200;
+        /// [`Statx::blocks`].
+        const STATX_BLOCKS = 0x0400;
+        /// Everything `stat` reports.
+        const STATX_BASIC_STATS = 0x07ff;
+        /// [`Statx::btime`].
+        const STATX_BTIME = 0x0800;
+        /// [`Statx::mnt_id`].  Requires Linux 5.8 or later.
+        const STATX_MNT_ID = 0x1000;
+    }
+}
+
+/// `struct statx_timestamp` from `linux/stat.h`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(dead_code)] // The reserved fields
+struct RawStatxTimestamp {
+    tv_sec: i64,
+    tv_nsec: u32,
+    __reserved: i32,
+}
+
+/// `struct statx` from `linux/stat.h`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(dead_code)] // The reserved fields
+struct RawStatx {
+    stx_mask: u32,
+    stx_blksize: u32,
+    stx_attributes: u64,
+    stx_nlink: u32,
+    stx_uid: u32,
+    stx_gid: u32,
+    stx_mode: u16,
+    __spare0: [u16; 1],
+    stx_ino: u64,
+    stx_size: u64,
+    stx_blocks: u64,
+    stx_attributes_mask: u64,
+    stx_atime: RawStatxTimestamp,
+    stx_btime: RawStatxTimestamp,
+    stx_ctime: RawStatxTimestamp,
+    stx_mtime: RawStatxTimestamp,
+    stx_rdev_major: u32,
+    stx_rdev_minor: u32,
+    stx_dev_major: u32,
+    stx_dev_minor: u32,
+    stx_mnt_id: u64,
+    __spare2: u64,
+    __spare3: [u64; 12],
+}
+
+static_assertions::assert_eq_size!(RawStatxTimestamp, [u8; 16]);
+static_assertions::assert_eq_size!(RawStatx, [u8; 256]);
+
+/// The file status returned by [`statx`].
+///
+/// Attributes governed by [`StatxMask`] are `None` unless the kernel
+/// reported them.
+#[derive(Clone, Copy)]
+pub struct Statx(RawStatx);
+
+impl Statx {
+    fn get<T>(&self, bit: StatxMask, val: T) -> Option<T> {
+        self.mask().contains(bit).then_some(val)
+    }
+
+    fn time(&self, bit: StatxMask, ts: RawStatxTimestamp) -> Option<TimeSpec> {
+        #[allow(clippy::unnecessary_cast)]
+        // The cast is not unnecessary on all platforms.
+        let ts = TimeSpec::new(ts.tv_sec as libc::time_t, ts.tv_nsec as _);
+        self.get(bit, ts)
+    }
+
+    /// The attributes the kernel reported.
+    pub fn mask(&self) -> StatxMask {
+        StatxMask::from_bits_truncate(self.0.stx_mask)
+    }
+
+    /// The preferred block size for I/O.
+    pub fn blksize(&self) -> u32 {
+        self.0.stx_blksize
+    }
+
+    /// The `STATX_ATTR_*` file attributes, such as `STATX_ATTR_IMMUTABLE`.
+    pub fn attributes(&self) -> u64 {
+        self.0.stx_attributes
+    }
+
+    /// Which of the bits of [`Statx::attributes`] the file system supports.
+    pub fn attributes_mask(&self) -> u64 {
+        self.0.stx_attributes_mask
+    }
+
+    /// The number of hard links.
+    pub fn nlink(&self) -> Option<u32> {
+        self.get(StatxMask::STATX_NLINK, self.0.stx_nlink)
+    }
+
+    /// The owner's user ID.
+    pub fn uid(&self) -> Option<libc::uid_t> {
+        self.get(StatxMask::STATX_UID, self.0.stx_uid)
+    }
+
+    /// The owner's group ID.
+    pub fn gid(&self) -> Option<libc::gid_t> {
+        self.get(StatxMask::STATX_GID, self.0.stx_gid)
+    }
+
+    /// The file type and permissions, as in `st_mode`.  Only the parts
+    /// selected by `STATX_TYPE` and `STATX_MODE` are valid.
+    pub fn mode(&self) -> Option<u16> {
+        let bits = StatxMask::STATX_TYPE | StatxMask::STATX_MODE;
+        self.mask().intersects(bits).then_some(self.0.stx_mode)
+    }
+
+    /// The inode number.
+    pub fn ino(&self) -> Option<u64> {
+        self.get(StatxMask::STATX_INO, self.0.stx_ino)
+    }
+
+    /// The size of the file in bytes.
+    pub fn size(&self) -> Option<u64> {
+        self.get(StatxMask::STATX_SIZE, self.0.stx_size)
+    }
+
+    /// The number of 512-byte blocks allocated to the file.
+    pub fn blocks(&self) -> Option<u64> {
+        self.get(StatxMask::STATX_BLOCKS, self.0.stx_blocks)
+    }
+
+    /// The time of last access.
+    pub fn atime(&self) -> Option<TimeSpec> {
+        self.time(StatxMask::STATX_ATIME, self.0.stx_atime)
+    }
+
+    /// The time the file was created.  Not all file systems record it.
+    pub fn btime(&self) -> Option<TimeSpec> {
+        self.time(StatxMask::STATX_BTIME, self.0.stx_btime)
+    }
+
+    /// The time of last status change.
+    pub fn ctime(&self) -> Option<TimeSpec> {
+        self.time(StatxMask::STATX_CTIME, self.0.stx_ctime)
+    }
+
+    /// The time of last modification.
+    pub fn mtime(&self) -> Option<TimeSpec> {
+        self.time(StatxMask::STATX_MTIME, self.0.stx_mtime)
+    }
+
+    /// The major and minor numbers of the device this file represents, if
+    /// it is a device file.
+    pub fn rdev(&self) -> (u32, u32) {
+        (self.0.stx_rdev_major, self.0.stx_rdev_minor)
+    }
+
+    /// The major and minor numbers of the device containing the file.
+    pub fn dev(&self) -> (u32, u32) {
+        (self.0.stx_dev_major, self.0.stx_dev_minor)
+    }
+
+    /// The ID of the mount containing the file, as in
+    /// `/proc/self/mountinfo`.
+    pub fn mnt_id(&self) -> Option<u64> {
+        self.get(StatxMask::STATX_MNT_ID, self.0.stx_mnt_id)
+    }
+}
+
+impl std::fmt::Debug for Statx {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Statx")
+            .field("mask", &self.mask())
+            .field("mode", &self.mode())
+            .field("ino", &self.ino())
+            .field("size", &self.size())
+            .field("mnt_id", &self.mnt_id())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Get the status of the file at `path`, relative to `dirfd` or to the
+/// current directory if `dirfd` is `None`.
+///
+/// `mask` selects the attributes of interest; the kernel may report more or
+/// fewer.  Requires Linux 4.11 or later.
+pub fn statx<P: ?Sized + NixPath>(
+    dirfd: Option<RawFd>,
+    path: &P,
+    flags: StatxFlags,
+    mask: StatxMask,
+) -> Result<Statx> {
+    let mut dst = mem::MaybeUninit::<RawStatx>::uninit();
+    let res = path.with_nix_path(|cstr| unsafe {
+        libc::syscall(
+            libc::SYS_statx,
+            crate::fcntl::at_rawfd(dirfd),
+            cstr.as_ptr(),
+            flags.bits(),
+            mask.bits(),
+            dst.as_mut_ptr(),
+        )
+    })?;
+
+    Errno::result(res)?;
+
+    Ok(Statx(unsafe { dst.assume_init() }))
+}
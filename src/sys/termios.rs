@@ -1123,6 +1123,13 @@ pub fn cfmakeraw(termios: &mut Termios) {
     termios.update_wrapper();
 }
 
+/// Return a copy of `termios` configured for raw mode, as with [`cfmakeraw`].
+pub fn cfmakeraw_owned(termios: &Termios) -> Termios {
+    let mut raw = termios.clone();
+    cfmakeraw(&mut raw);
+    raw
+}
+
 /// Configures the port to "sane" mode (like the configuration of a newly created terminal) (see
 /// [tcsetattr(3)](https://www.freebsd.org/cgi/man.cgi?query=tcsetattr)).
 ///
@@ -1177,6 +1184,18 @@ pub fn tcsetattr<Fd: AsFd>(
     .map(drop)
 }
 
+/// Put a terminal into raw mode, returning its previous configuration.
+///
+/// The change takes effect immediately, and discards any pending input, as
+/// with [`SetArg::TCSAFLUSH`].  Pass the returned `Termios` to
+/// [`tcsetattr`] to restore the terminal afterwards.
+pub fn set_raw_mode<Fd: AsFd>(fd: Fd) -> Result<Termios> {
+    let fd = fd.as_fd();
+    let orig = tcgetattr(fd)?;
+    tcsetattr(fd, SetArg::TCSAFLUSH, &cfmakeraw_owned(&orig))?;
+    Ok(orig)
+}
+
 /// Block until all output data is written (see
 /// [tcdrain(3p)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/tcdrain.html)).
 pub fn tcdrain<Fd: AsFd>(fd: Fd) -> Result<()> {
@@ -369,6 +369,27 @@ impl TimeSpec {
     pub const fn from_timespec(timespec: timespec) -> Self {
         Self(timespec)
     }
+
+    /// Returns the amount of time elapsed from `earlier` to `self`, or
+    /// `None` if `earlier` is later than `self`.
+    pub fn checked_duration_since(
+        &self,
+        earlier: TimeSpec,
+    ) -> Option<Duration> {
+        let nanos = self
+            .num_nanoseconds()
+            .checked_sub(earlier.num_nanoseconds())?;
+        u64::try_from(nanos).ok().map(Duration::from_nanos)
+    }
+
+    /// Returns the amount of time elapsed from `earlier` to `self`, or zero
+    /// if `earlier` is later than `self`.
+    ///
+    /// This mirrors [`std::time::Instant::duration_since`], and is mostly
+    /// useful with timestamps read from a monotonic clock.
+    pub fn duration_since(&self, earlier: TimeSpec) -> Duration {
+        self.checked_duration_since(earlier).unwrap_or_default()
+    }
 }
 
 impl ops::Neg for TimeSpec {
@@ -737,6 +758,25 @@ mod test {
         assert_eq!(Duration::from(timespec), duration);
     }
 
+    #[test]
+    pub fn test_timespec_duration_since() {
+        let earlier = TimeSpec::new(10, 900_000_000);
+        let later = TimeSpec::new(12, 100_000_000);
+
+        assert_eq!(later.duration_since(earlier), Duration::from_millis(1200));
+        assert_eq!(
+            later.checked_duration_since(earlier),
+            Some(Duration::from_millis(1200))
+        );
+        assert_eq!(earlier.duration_since(earlier), Duration::ZERO);
+        assert_eq!(earlier.checked_duration_since(later), None);
+        assert_eq!(earlier.duration_since(later), Duration::ZERO);
+        assert_eq!(
+            Duration::from(later - earlier),
+            Duration::from_millis(1200)
+        );
+    }
+
     #[test]
     pub fn test_timespec_neg() {
         let a = TimeSpec::seconds(1) + TimeSpec::nanoseconds(123);
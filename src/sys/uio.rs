@@ -216,4 +216,309 @@ pub fn process_vm_readv(
 
     Errno::result(res).map(|r| r as usize)
 }
+
+/// The most iovecs the kernel accepts in a single call (`UIO_MAXIOV`).
+#[cfg(all(any(target_os = "linux", target_os = "android"), not(target_env = "uclibc")))]
+const UIO_MAXIOV: usize = 1024;
+
+/// A position within a list of iovecs.
+#[cfg(all(any(target_os = "linux", target_os = "android"), not(target_env = "uclibc")))]
+#[derive(Clone, Copy, Debug, Default)]
+struct IovCursor {
+    idx: usize,
+    off: usize,
+}
+
+#[cfg(all(any(target_os = "linux", target_os = "android"), not(target_env = "uclibc")))]
+impl IovCursor {
+    /// The next `UIO_MAXIOV` iovecs of `iovs`, starting at the cursor.
+    fn batch(&self, iovs: &[libc::iovec]) -> Vec<libc::iovec> {
+        let mut batch: Vec<libc::iovec> =
+            iovs[self.idx..].iter().take(UIO_MAXIOV).copied().collect();
+        if let Some(first) = batch.first_mut() {
+            first.iov_base =
+                (first.iov_base as usize + self.off) as *mut c_void;
+            first.iov_len -= self.off;
+        }
+        batch
+    }
+
+    /// Move the cursor `n` bytes forward.
+    fn advance(&mut self, iovs: &[libc::iovec], mut n: usize) {
+        while n > 0 {
+            let left = iovs[self.idx].iov_len - self.off;
+            if n < left {
+                self.off += n;
+                return;
+            }
+            n -= left;
+            self.idx += 1;
+            self.off = 0;
+        }
+    }
+}
+
+/// Read data directly from another process's virtual memory, like
+/// [`process_vm_readv`], but without a limit on the number of iovecs.
+///
+/// `process_vm_readv` fails with `EINVAL` when given more than `IOV_MAX`
+/// iovecs on either side.  This function instead issues as many calls as
+/// needed, each with at most `IOV_MAX` iovecs per side, and returns the
+/// total number of bytes read.  It stops at the first partial read, which
+/// happens when part of `remote_iov` isn't mapped in the target process.
+/// An error is only returned if nothing could be read at all.
+///
+/// Unlike with a single `process_vm_readv` call, the result isn't atomic:
+/// the target process may run in between the calls.
+#[cfg(all(any(target_os = "linux", target_os = "android"), not(target_env = "uclibc")))]
+pub fn read_process_vm(
+    pid: crate::unistd::Pid,
+    local_iov: &mut [IoSliceMut<'_>],
+    remote_iov: &[RemoteIoVec]) -> Result<usize>
+{
+    // Empty iovecs could make a batch transfer nothing, so drop them
+    let local: Vec<libc::iovec> = local_iov
+        .iter_mut()
+        .filter(|buf| !buf.is_empty())
+        .map(|buf| libc::iovec {
+            iov_base: buf.as_mut_ptr() as *mut c_void,
+            iov_len: buf.len(),
+        })
+        .collect();
+    let remote: Vec<libc::iovec> = remote_iov
+        .iter()
+        .filter(|iov| iov.len != 0)
+        .map(|iov| libc::iovec {
+            iov_base: iov.base as *mut c_void,
+            iov_len: iov.len,
+        })
+        .collect();
+
+    let mut local_pos = IovCursor::default();
+    let mut remote_pos = IovCursor::default();
+    let mut total = 0;
+    while local_pos.idx < local.len() && remote_pos.idx < remote.len() {
+        let local_batch = local_pos.batch(&local);
+        let remote_batch = remote_pos.batch(&remote);
+        let expected = std::cmp::min(
+            local_batch.iter().map(|iov| iov.iov_len).sum::<usize>(),
+            remote_batch.iter().map(|iov| iov.iov_len).sum::<usize>(),
+        );
+
+        let res = unsafe {
+            libc::process_vm_readv(pid.into(),
+                                   local_batch.as_ptr(), local_batch.len() as libc::c_ulong,
+                                   remote_batch.as_ptr(), remote_batch.len() as libc::c_ulong, 0)
+        };
+        let n = match Errno::result(res) {
+            Ok(n) => n as usize,
+            Err(e) if total == 0 => return Err(e),
+            Err(_) => break,
+        };
+        total += n;
+        if n < expected {
+            break;
+        }
+        local_pos.advance(&local, n);
+        remote_pos.advance(&remote, n);
+    }
+    Ok(total)
+}
+
+/// The error returned by [`read_process_vm_exact`].
+#[cfg(all(any(target_os = "linux", target_os = "android"), not(target_env = "uclibc")))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ProcessVmError {
+    /// The read stopped early, because the remote memory at position
+    /// `iov_offset` of `remote_iov[iov_index]` isn't mapped or readable.
+    /// The first `bytes` bytes were transferred.
+    PartialTransfer {
+        /// The number of bytes read before the gap.
+        bytes: usize,
+        /// The index, in `remote_iov`, of the iovec containing the gap.
+        iov_index: usize,
+        /// The offset of the gap within `remote_iov[iov_index]`.
+        iov_offset: usize,
+        /// The remote address of the gap.
+        address: usize,
+    },
+    /// The read failed for another reason, such as the process not
+    /// existing or the caller lacking permission to read its memory.
+    Failed(Errno),
+}
+
+#[cfg(all(any(target_os = "linux", target_os = "android"), not(target_env = "uclibc")))]
+impl ProcessVmError {
+    /// Locate the gap after `bytes` bytes of `remote_iov` were read.
+    fn partial(remote_iov: &[RemoteIoVec], bytes: usize) -> Self {
+        let mut left = bytes;
+        for (iov_index, iov) in remote_iov.iter().enumerate() {
+            if left < iov.len {
+                return ProcessVmError::PartialTransfer {
+                    bytes,
+                    iov_index,
+                    iov_offset: left,
+                    address: iov.base.wrapping_add(left),
+                };
+            }
+            left -= iov.len;
+        }
+        // Only reachable if the kernel read more than was asked for
+        ProcessVmError::Failed(Errno::EIO)
+    }
+
+    /// Returns the underlying [`Errno`]: `EFAULT` for a
+    /// [`PartialTransfer`](ProcessVmError::PartialTransfer).
+    pub const fn errno(&self) -> Errno {
+        match *self {
+            ProcessVmError::PartialTransfer { .. } => Errno::EFAULT,
+            ProcessVmError::Failed(errno) => errno,
+        }
+    }
+}
+
+#[cfg(all(any(target_os = "linux", target_os = "android"), not(target_env = "uclibc")))]
+impl std::fmt::Display for ProcessVmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            ProcessVmError::PartialTransfer { bytes, address, .. } => write!(
+                f,
+                "partial transfer of {} bytes, stopped at {:#x}",
+                bytes, address
+            ),
+            ProcessVmError::Failed(errno) => write!(f, "{}", errno),
+        }
+    }
+}
+
+#[cfg(all(any(target_os = "linux", target_os = "android"), not(target_env = "uclibc")))]
+impl std::error::Error for ProcessVmError {}
+
+#[cfg(all(any(target_os = "linux", target_os = "android"), not(target_env = "uclibc")))]
+impl From<ProcessVmError> for Errno {
+    fn from(err: ProcessVmError) -> Self {
+        err.errno()
+    }
+}
+
+/// Read data directly from another process's virtual memory, like
+/// [`read_process_vm`], but fail unless the buffers are filled completely.
+///
+/// On success, returns the number of bytes read, which is the smaller of the
+/// sizes of `local_iov` and `remote_iov`.  If the read runs into memory that
+/// isn't mapped in the target process, the error is a
+/// [`ProcessVmError::PartialTransfer`] telling how much was read and where,
+/// relative to `remote_iov`, the gap starts, so that a caller dumping a
+/// sparse address space can skip past the gap and carry on.
+#[cfg(all(any(target_os = "linux", target_os = "android"), not(target_env = "uclibc")))]
+pub fn read_process_vm_exact(
+    pid: crate::unistd::Pid,
+    local_iov: &mut [IoSliceMut<'_>],
+    remote_iov: &[RemoteIoVec]) -> std::result::Result<usize, ProcessVmError>
+{
+    let expected = std::cmp::min(
+        local_iov.iter().map(|buf| buf.len()).sum::<usize>(),
+        remote_iov.iter().map(|iov| iov.len).sum::<usize>(),
+    );
+    match read_process_vm(pid, local_iov, remote_iov) {
+        Ok(n) if n >= expected => Ok(n),
+        Ok(n) => Err(ProcessVmError::partial(remote_iov, n)),
+        // The local buffers are valid, so the first remote byte is missing
+        Err(Errno::EFAULT) => Err(ProcessVmError::partial(remote_iov, 0)),
+        Err(e) => Err(ProcessVmError::Failed(e)),
+    }
+}
+
+/// A cursor over another process's virtual memory, starting at address
+/// `base`.
+///
+/// `RemoteMem` implements [`Read`] and [`Seek`], so that the standard I/O
+/// machinery can be used to parse data structures out of a tracee.  The
+/// position is relative to `base`.  Reads are serviced by
+/// [`process_vm_readv`], falling back to `/proc/<pid>/mem` on kernels that
+/// don't implement it.  The same permissions are required in both cases.
+///
+/// # Example
+///
+/// ```no_run
+/// # use nix::sys::uio::RemoteMem;
+/// # use nix::unistd::Pid;
+/// use std::io::Read;
+///
+/// # let (pid, addr) = (Pid::from_raw(1), 0x1000);
+/// let mut mem = RemoteMem::new(pid, addr);
+/// let mut word = [0u8; 8];
+/// mem.read_exact(&mut word).unwrap();
+/// ```
+///
+/// [`Read`]: std::io::Read
+/// [`Seek`]: std::io::Seek
+#[cfg(all(any(target_os = "linux", target_os = "android"), not(target_env = "uclibc")))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct RemoteMem {
+    /// The process whose memory is read.
+    pub pid: crate::unistd::Pid,
+    /// The address that position 0 refers to.
+    pub base: usize,
+    pos: u64,
+}
+
+#[cfg(all(any(target_os = "linux", target_os = "android"), not(target_env = "uclibc")))]
+impl RemoteMem {
+    /// Create a cursor over the memory of `pid`, positioned at `base`.
+    pub const fn new(pid: crate::unistd::Pid, base: usize) -> Self {
+        RemoteMem { pid, base, pos: 0 }
+    }
+
+    /// The remote address that the next read will start at.
+    pub fn address(&self) -> usize {
+        self.base.wrapping_add(self.pos as usize)
+    }
+}
+
+#[cfg(all(any(target_os = "linux", target_os = "android"), not(target_env = "uclibc")))]
+impl std::io::Read for RemoteMem {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        use std::os::unix::fs::FileExt;
+
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let addr = self.address();
+        let remote_iov = [RemoteIoVec { base: addr, len: buf.len() }];
+        let n = match process_vm_readv(self.pid, &mut [IoSliceMut::new(buf)], &remote_iov) {
+            Ok(n) => n,
+            Err(Errno::ENOSYS) => {
+                let mem = std::fs::File::open(format!("/proc/{}/mem", self.pid))?;
+                mem.read_at(buf, addr as u64)?
+            }
+            Err(e) => return Err(e.into()),
+        };
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+#[cfg(all(any(target_os = "linux", target_os = "android"), not(target_env = "uclibc")))]
+impl std::io::Seek for RemoteMem {
+    /// Seek relative to `base`.  Since a process's address space has no
+    /// meaningful end, `SeekFrom::End` is rejected with `InvalidInput`.
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        use std::io::{Error, ErrorKind, SeekFrom};
+
+        let new_pos = match pos {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::Current(d) if d >= 0 => self.pos.checked_add(d as u64),
+            SeekFrom::Current(d) => self.pos.checked_sub(d.unsigned_abs()),
+            SeekFrom::End(_) => None,
+        };
+        match new_pos {
+            Some(n) => {
+                self.pos = n;
+                Ok(n)
+            }
+            None => Err(Error::from(ErrorKind::InvalidInput)),
+        }
+    }
+}
 }
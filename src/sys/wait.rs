@@ -143,6 +143,28 @@ impl WaitStatus {
             PtraceEvent(p, _, _) | PtraceSyscall(p) => Some(p),
         }
     }
+
+    /// Decode a [`WaitStatus::PtraceEvent`] into its PID, signal and
+    /// [`Event`](crate::sys::ptrace::Event).
+    ///
+    /// Returns `None` for other statuses, and for events unknown to nix.
+    #[cfg(all(
+        feature = "ptrace",
+        any(target_os = "android", target_os = "linux")
+    ))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ptrace")))]
+    pub fn ptrace_event(
+        &self,
+    ) -> Option<(Pid, Signal, crate::sys::ptrace::Event)> {
+        match *self {
+            WaitStatus::PtraceEvent(pid, sig, event) => {
+                crate::sys::ptrace::Event::try_from(event)
+                    .ok()
+                    .map(|event| (pid, sig, event))
+            }
+            _ => None,
+        }
+    }
 }
 
 fn exited(status: i32) -> bool {
@@ -329,6 +351,89 @@ pub fn waitpid<P: Into<Option<Pid>>>(
     }
 }
 
+/// Wait for a process to change status, and return the resources it used
+///
+/// This is like [`waitpid`], but also returns the `rusage` accounting data of
+/// the child.  When the child has exited, this covers its whole lifetime,
+/// which `getrusage(RUSAGE_CHILDREN)` can't attribute to an individual child.
+/// If no status is available (`WaitStatus::StillAlive`), the returned
+/// `rusage` is zeroed.
+///
+/// See also [wait4(2)](https://man7.org/linux/man-pages/man2/wait4.2.html)
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+pub fn wait4<P: Into<Option<Pid>>>(
+    pid: P,
+    options: Option<WaitPidFlag>,
+) -> Result<(WaitStatus, libc::rusage)> {
+    let mut status: i32 = 0;
+    // Zeroed, as the kernel doesn't fill it in the StillAlive case
+    let mut rusage: libc::rusage = unsafe { std::mem::zeroed() };
+
+    let option_bits = match options {
+        Some(bits) => bits.bits(),
+        None => 0,
+    };
+
+    let res = unsafe {
+        libc::wait4(
+            pid.into().unwrap_or_else(|| Pid::from_raw(-1)).into(),
+            &mut status as *mut c_int,
+            option_bits,
+            &mut rusage as *mut libc::rusage,
+        )
+    };
+
+    let status = match Errno::result(res)? {
+        0 => WaitStatus::StillAlive,
+        res => WaitStatus::from_raw(Pid::from_raw(res), status)?,
+    };
+    Ok((status, rusage))
+}
+
+/// Which children [`waitpid_target`] waits for.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum WaitTarget {
+    /// The child with the given process ID.
+    Pid(Pid),
+    /// Any child in the process group with the given ID.
+    ProcessGroup(Pid),
+    /// Any child.
+    Any,
+    /// Any child in the caller's process group.
+    CurrentGroup,
+}
+
+/// Wait for a process to change status, with an explicit [`WaitTarget`]
+/// instead of the sign conventions of `waitpid`'s `pid` argument.
+///
+/// Returns `EINVAL` for `WaitTarget::Pid` with a non-positive ID, and for
+/// `WaitTarget::ProcessGroup` with an ID below 2: `waitpid` can't tell
+/// process group 1 apart from any child.
+///
+/// See also [waitpid(2)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/waitpid.html)
+pub fn waitpid_target(
+    target: WaitTarget,
+    options: Option<WaitPidFlag>,
+) -> Result<WaitStatus> {
+    let pid = match target {
+        WaitTarget::Pid(pid) if pid.as_raw() > 0 => pid.as_raw(),
+        WaitTarget::ProcessGroup(pgid) if pgid.as_raw() > 1 => -pgid.as_raw(),
+        WaitTarget::Any => -1,
+        WaitTarget::CurrentGroup => 0,
+        _ => return Err(Errno::EINVAL),
+    };
+    waitpid(Pid::from_raw(pid), options)
+}
+
 /// Wait for any child process to change status or a signal is received.
 ///
 /// See also [wait(2)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/wait.html)
@@ -391,3 +496,170 @@ pub fn waitid(id: Id, flags: WaitPidFlag) -> Result<WaitStatus> {
 
     unsafe { WaitStatus::from_siginfo(&siginfo) }
 }
+
+/// A registry of the children a component is responsible for reaping.
+///
+/// Waiting for any child, as with `waitpid(None, ..)`, reaps whatever child
+/// changes state first, including children other parts of the program
+/// forked and wait for themselves.  A `ManagedChildren` remembers the
+/// processes forked through [`ManagedChildren::fork`], or registered with
+/// [`ManagedChildren::insert`], and [`ManagedChildren::reap_managed`] only
+/// ever reaps those.
+#[cfg(any(
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "haiku",
+    all(target_os = "linux", not(target_env = "uclibc")),
+))]
+#[derive(Clone, Debug, Default)]
+pub struct ManagedChildren {
+    pids: std::collections::BTreeSet<Pid>,
+}
+
+/// The result of [`ManagedChildren::reap_managed`].
+#[cfg(any(
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "haiku",
+    all(target_os = "linux", not(target_env = "uclibc")),
+))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ManagedStatus {
+    /// The status of a managed child, which was collected.  With `WNOHANG`
+    /// this may be [`WaitStatus::StillAlive`].
+    Managed(WaitStatus),
+    /// The status of a child that isn't managed.  It was only peeked at and
+    /// is left for its owner to collect.
+    Unmanaged(WaitStatus),
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "haiku",
+    all(target_os = "linux", not(target_env = "uclibc")),
+))]
+impl ManagedChildren {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fork, as with [`fork`](crate::unistd::fork), and register the child
+    /// in the parent.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as for [`fork`](crate::unistd::fork) apply.
+    pub unsafe fn fork(&mut self) -> Result<crate::unistd::ForkResult> {
+        let res = crate::unistd::fork()?;
+        if let crate::unistd::ForkResult::Parent { child } = res {
+            self.pids.insert(child);
+        }
+        Ok(res)
+    }
+
+    /// Register a child created by other means, such as `clone`.  Returns
+    /// whether it wasn't registered yet.
+    pub fn insert(&mut self, pid: Pid) -> bool {
+        self.pids.insert(pid)
+    }
+
+    /// Unregister a child, returning whether it was registered.
+    pub fn remove(&mut self, pid: Pid) -> bool {
+        self.pids.remove(&pid)
+    }
+
+    /// Whether a child is registered.
+    pub fn contains(&self, pid: Pid) -> bool {
+        self.pids.contains(&pid)
+    }
+
+    /// The number of registered children.
+    pub fn len(&self) -> usize {
+        self.pids.len()
+    }
+
+    /// Whether no children are registered.
+    pub fn is_empty(&self) -> bool {
+        self.pids.is_empty()
+    }
+
+    /// Wait for a managed child to change status, without reaping other
+    /// children.
+    ///
+    /// Children that exited or were killed are unregistered once reaped.
+    /// Managed children that already have a status to report are collected
+    /// first.  Otherwise, unless `flags` contains `WNOHANG`, this blocks
+    /// until any child has a status to report, as `waitid` does with
+    /// `WNOWAIT`; if that child isn't managed, its status is returned as
+    /// [`ManagedStatus::Unmanaged`] without being collected.  Such a status
+    /// stays pending until its owner collects it, so calling this again
+    /// returns it again right away: wait for the owner, or for a signal,
+    /// before retrying.
+    ///
+    /// Termination is always reported; `flags` may add `WUNTRACED`,
+    /// `WCONTINUED` and, on Linux, `__WALL` or `__WCLONE`.  Returns `ECHILD`
+    /// if no children are registered.
+    pub fn reap_managed(
+        &mut self,
+        flags: WaitPidFlag,
+    ) -> Result<ManagedStatus> {
+        loop {
+            if self.pids.is_empty() {
+                return Err(Errno::ECHILD);
+            }
+            if let Some(status) = self.collect(flags)? {
+                return Ok(ManagedStatus::Managed(status));
+            }
+            if flags.contains(WaitPidFlag::WNOHANG) {
+                return Ok(ManagedStatus::Managed(WaitStatus::StillAlive));
+            }
+
+            let peek = flags | WaitPidFlag::WEXITED | WaitPidFlag::WNOWAIT;
+            match waitid(Id::All, peek) {
+                Ok(status) => match status.pid() {
+                    Some(pid) if !self.pids.contains(&pid) => {
+                        return Ok(ManagedStatus::Unmanaged(status))
+                    }
+                    // A managed child is ready, which the next round
+                    // collects
+                    _ => (),
+                },
+                Err(Errno::EINTR) => (),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Collect the status of the first managed child that has one to report.
+    fn collect(&mut self, flags: WaitPidFlag) -> Result<Option<WaitStatus>> {
+        // waitpid rejects the flags only waitid knows
+        let flags = (flags | WaitPidFlag::WNOHANG)
+            - (WaitPidFlag::WEXITED | WaitPidFlag::WNOWAIT);
+        let mut gone = Vec::new();
+        let mut found = None;
+        for &pid in &self.pids {
+            match waitpid(pid, Some(flags)) {
+                Ok(WaitStatus::StillAlive) => (),
+                Ok(status) => {
+                    found = Some(status);
+                    break;
+                }
+                // Reaped behind our back, by someone who didn't know better
+                Err(Errno::ECHILD) => gone.push(pid),
+                Err(e) => return Err(e),
+            }
+        }
+        for pid in gone {
+            self.pids.remove(&pid);
+        }
+        if let Some(
+            WaitStatus::Exited(pid, _) | WaitStatus::Signaled(pid, _, _),
+        ) = found
+        {
+            self.pids.remove(&pid);
+        }
+        Ok(found)
+    }
+}
@@ -53,6 +53,15 @@ impl ClockId {
         clock_gettime(self)
     }
 
+    /// Returns the current time on the monotonic clock
+    ///
+    /// This is shorthand for `ClockId::CLOCK_MONOTONIC.now()`.  Use
+    /// [`TimeSpec::duration_since`] to measure the time between two such
+    /// readings.
+    pub fn monotonic_now() -> Result<TimeSpec> {
+        clock_gettime(ClockId::CLOCK_MONOTONIC)
+    }
+
     /// Sets time to `timespec` on the clock id
     #[cfg(not(any(
         target_os = "macos",
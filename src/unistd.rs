@@ -318,6 +318,41 @@ pub fn getppid() -> Pid {
     Pid(unsafe { libc::getppid() }) // no error handling, according to man page: "These functions are always successful."
 }
 
+/// Whether the process `pid` exists.
+///
+/// Unlike probing with `kill(pid, 0)`, this tells a process that exists but
+/// can't be signaled from one that doesn't: on Linux 5.3 and later, the
+/// process is looked up with `pidfd_open`, which needs no permission, and
+/// otherwise `kill`'s `EPERM` is taken to mean that the process exists.  A
+/// child that has terminated but hasn't been reaped yet still exists.
+///
+/// Returns `EINVAL` if `pid` isn't positive, as `kill` would interpret it as
+/// a process group.
+pub fn is_alive(pid: Pid) -> Result<bool> {
+    if pid.0 <= 0 {
+        return Err(Errno::EINVAL);
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        use crate::sys::pidfd::{pidfd_open, PidfdFlags};
+
+        match pidfd_open(pid, PidfdFlags::empty()) {
+            Ok(_) => return Ok(true),
+            Err(Errno::ESRCH) => return Ok(false),
+            // The kernel may lack pidfds, or `pid` may be a thread ID, which
+            // only kill accepts
+            Err(_) => (),
+        }
+    }
+
+    match Errno::result(unsafe { libc::kill(pid.0, 0) }) {
+        Ok(_) | Err(Errno::EPERM) => Ok(true),
+        Err(Errno::ESRCH) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
 /// Set a process group ID (see
 /// [setpgid(2)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/setpgid.html)).
 ///
@@ -356,6 +391,20 @@ pub fn getsid(pid: Option<Pid>) -> Result<Pid> {
     let res = unsafe { libc::getsid(pid.unwrap_or(Pid(0)).into()) };
     Errno::result(res).map(Pid)
 }
+
+/// Make the calling process the leader of a new session and of a new
+/// process group in it, and return the IDs of both.
+///
+/// This is [`setsid`], meant to be called in a child right after `fork`, so
+/// that it can't be the leader of a process group already, which is the one
+/// case `setsid` fails with `EPERM`.  The new session has no controlling
+/// terminal.  Both returned IDs equal the caller's process ID.
+#[cfg(not(target_os = "redox"))]
+pub fn create_session_and_group() -> Result<(Pid, Pid)> {
+    let sid = setsid()?;
+    let pgid = getpgid(None)?;
+    Ok((sid, pgid))
+}
 }
 
 feature! {
@@ -365,21 +414,59 @@ feature! {
 ///
 /// Get the group process id (GPID) of the foreground process group on the
 /// terminal associated to file descriptor (FD).
+///
+/// Only processes in the foreground group may read from the terminal, so a
+/// debugger checks this to find out whether it or its tracee currently owns
+/// the terminal.  `fd` must refer to the caller's controlling terminal, or
+/// `ENOTTY` is returned.
 #[inline]
 pub fn tcgetpgrp(fd: c_int) -> Result<Pid> {
     let res = unsafe { libc::tcgetpgrp(fd) };
     Errno::result(res).map(Pid)
 }
 /// Set the terminal foreground process group (see
-/// [tcgetpgrp(3)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/tcsetpgrp.html)).
+/// [tcsetpgrp(3)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/tcsetpgrp.html)).
 ///
-/// Get the group process id (PGID) to the foreground process group on the
+/// Set the group process id (PGID) of the foreground process group on the
 /// terminal associated to file descriptor (FD).
+///
+/// `pgrp` must be a process group in the caller's session, and `fd` its
+/// controlling terminal.  A caller in a background group is sent `SIGTTOU`,
+/// which stops it by default, unless it blocks or ignores the signal; see
+/// [`set_foreground`] for a version that takes care of that.
 #[inline]
 pub fn tcsetpgrp(fd: c_int, pgrp: Pid) -> Result<()> {
     let res = unsafe { libc::tcsetpgrp(fd, pgrp.into()) };
     Errno::result(res).map(drop)
 }
+
+/// Make `pgid` the foreground process group of the terminal `tty_fd`, which
+/// must be the controlling terminal of the caller.
+///
+/// This is [`tcsetpgrp`], except that it also works when called from a
+/// background process group, such as by a debugger handing the terminal
+/// over to its tracee and taking it back: `SIGTTOU`, which would otherwise
+/// stop the caller, is blocked for the duration of the call.
+pub fn set_foreground(tty_fd: c_int, pgid: Pid) -> Result<()> {
+    unsafe {
+        let mut ttou = mem::MaybeUninit::<libc::sigset_t>::uninit();
+        let mut old = mem::MaybeUninit::<libc::sigset_t>::uninit();
+        libc::sigemptyset(ttou.as_mut_ptr());
+        libc::sigaddset(ttou.as_mut_ptr(), libc::SIGTTOU);
+        let res = libc::pthread_sigmask(
+            libc::SIG_BLOCK,
+            ttou.as_ptr(),
+            old.as_mut_ptr(),
+        );
+        // pthread_sigmask returns the error rather than setting errno
+        if res != 0 {
+            return Err(Errno::from_i32(res));
+        }
+        let res = tcsetpgrp(tty_fd, pgid);
+        libc::pthread_sigmask(libc::SIG_SETMASK, old.as_ptr(), ptr::null_mut());
+        res
+    }
+}
 }
 
 feature! {
@@ -756,6 +843,15 @@ pub enum FchownatFlags {
     NoFollowSymlink,
 }
 
+impl From<FchownatFlags> for AtFlags {
+    fn from(flag: FchownatFlags) -> Self {
+        match flag {
+            FchownatFlags::FollowSymlink => AtFlags::empty(),
+            FchownatFlags::NoFollowSymlink => AtFlags::AT_SYMLINK_NOFOLLOW,
+        }
+    }
+}
+
 /// Change the ownership of the file at `path` to be owned by the specified
 /// `owner` (user) and `group`.
 ///
@@ -767,10 +863,13 @@ pub enum FchownatFlags {
 /// with the file descriptor `dirfd` or the current working directory
 /// if `dirfd` is `None`.
 ///
-/// If `flag` is `FchownatFlags::NoFollowSymlink` and `path` names a symbolic link,
-/// then the mode of the symbolic link is changed.
+/// `flag` is either a [`FchownatFlags`] or an [`AtFlags`].  If it contains
+/// `AT_SYMLINK_NOFOLLOW` and `path` names a symbolic link,
+/// then the ownership of the symbolic link is changed.  On Linux and Android,
+/// if `flag` contains `AT_EMPTY_PATH` and `path` is empty, the file referred
+/// to by `dirfd` is changed.  Other flags are rejected with `EINVAL`.
 ///
-/// `fchownat(None, path, owner, group, FchownatFlags::NoFollowSymlink)` is identical to
+/// `fchownat(None, path, owner, group, AtFlags::AT_SYMLINK_NOFOLLOW)` is identical to
 /// a call `libc::lchown(path, owner, group)`.  That's why `lchown` is unimplemented in
 /// the `nix` crate.
 ///
@@ -778,17 +877,14 @@ pub enum FchownatFlags {
 ///
 /// [fchownat(2)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/fchownat.html).
 #[cfg(not(target_os = "redox"))]
-pub fn fchownat<P: ?Sized + NixPath>(
+pub fn fchownat<P: ?Sized + NixPath, F: Into<AtFlags>>(
     dirfd: Option<RawFd>,
     path: &P,
     owner: Option<Uid>,
     group: Option<Gid>,
-    flag: FchownatFlags,
+    flag: F,
 ) -> Result<()> {
-    let atflag = match flag {
-        FchownatFlags::FollowSymlink => AtFlags::empty(),
-        FchownatFlags::NoFollowSymlink => AtFlags::AT_SYMLINK_NOFOLLOW,
-    };
+    let flag = flag.into();
     let res = path.with_nix_path(|cstr| unsafe {
         let (uid, gid) = chown_raw_ids(owner, group);
         libc::fchownat(
@@ -796,7 +892,7 @@ pub fn fchownat<P: ?Sized + NixPath>(
             cstr.as_ptr(),
             uid,
             gid,
-            atflag.bits() as libc::c_int,
+            flag.bits() as libc::c_int,
         )
     })?;
 
@@ -1004,6 +1100,320 @@ pub fn daemon(nochdir: bool, noclose: bool) -> Result<()> {
     let res = unsafe { libc::daemon(nochdir as c_int, noclose as c_int) };
     Errno::result(res).map(drop)
 }
+
+// Not yet exported by libc
+#[cfg(target_os = "linux")]
+const POSIX_SPAWN_SETSID: libc::c_short = 0x80;
+
+#[cfg(target_os = "linux")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum SpawnFileAction {
+    Close(RawFd),
+    Dup2(RawFd, RawFd),
+    #[cfg(feature = "fs")]
+    Open(RawFd, CString, OFlag, Mode),
+}
+
+/// Owns a `posix_spawn_file_actions_t` for the duration of a spawn.
+#[cfg(target_os = "linux")]
+struct SpawnFileActions(libc::posix_spawn_file_actions_t);
+
+#[cfg(target_os = "linux")]
+impl SpawnFileActions {
+    fn new() -> Result<Self> {
+        let mut actions = mem::MaybeUninit::uninit();
+        spawn_result(unsafe {
+            libc::posix_spawn_file_actions_init(actions.as_mut_ptr())
+        })?;
+        Ok(SpawnFileActions(unsafe { actions.assume_init() }))
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for SpawnFileActions {
+    fn drop(&mut self) {
+        unsafe { libc::posix_spawn_file_actions_destroy(&mut self.0) };
+    }
+}
+
+/// Owns a `posix_spawnattr_t` for the duration of a spawn.
+#[cfg(target_os = "linux")]
+struct SpawnAttr(libc::posix_spawnattr_t);
+
+#[cfg(target_os = "linux")]
+impl SpawnAttr {
+    fn new() -> Result<Self> {
+        let mut attr = mem::MaybeUninit::uninit();
+        spawn_result(unsafe { libc::posix_spawnattr_init(attr.as_mut_ptr()) })?;
+        Ok(SpawnAttr(unsafe { attr.assume_init() }))
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for SpawnAttr {
+    fn drop(&mut self) {
+        unsafe { libc::posix_spawnattr_destroy(&mut self.0) };
+    }
+}
+
+/// The `posix_spawn` family returns an error number instead of setting
+/// `errno`.
+#[cfg(target_os = "linux")]
+fn spawn_result(res: c_int) -> Result<()> {
+    match res {
+        0 => Ok(()),
+        e => Err(Errno::from_i32(e)),
+    }
+}
+
+/// A builder for launching a program in a new process with
+/// [posix_spawn(3)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/posix_spawn.html).
+///
+/// Unlike [`fork`] followed by [`execve`], nothing runs in the child before
+/// the new program starts, so none of the async-signal-safety rules of
+/// `fork` apply.  The file actions, such as [`dup2`](ProcessSpawner::dup2),
+/// are performed in the child in the order they were added; the attributes
+/// apply to the child as a whole.
+///
+/// To trace the spawned program, [`seize`](crate::sys::ptrace::seize) it
+/// from the parent: the child can't call `traceme` itself.
+///
+/// # Examples
+///
+/// ```
+/// # use nix::sys::wait::{waitpid, WaitStatus};
+/// # use nix::unistd::ProcessSpawner;
+/// # use std::ffi::CString;
+/// let path = CString::new("/bin/true").unwrap();
+/// let child = ProcessSpawner::new(&path).setsid(true).spawn().unwrap();
+/// assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+/// ```
+#[cfg(target_os = "linux")]
+#[cfg_attr(docsrs, doc(cfg(all())))]
+#[derive(Clone, Debug)]
+pub struct ProcessSpawner {
+    path: CString,
+    args: Vec<CString>,
+    env: Option<Vec<CString>>,
+    file_actions: Vec<SpawnFileAction>,
+    search_path: bool,
+    setsid: bool,
+    pgroup: Option<Pid>,
+    #[cfg(feature = "signal")]
+    sigmask: Option<crate::sys::signal::SigSet>,
+    #[cfg(feature = "signal")]
+    sigdefault: Option<crate::sys::signal::SigSet>,
+}
+
+#[cfg(target_os = "linux")]
+impl ProcessSpawner {
+    /// Prepare to run the program at `path`.
+    ///
+    /// By default, `path` is also passed as the only argument, the child
+    /// inherits the environment of the calling process, and `PATH` isn't
+    /// searched.
+    pub fn new(path: &CStr) -> Self {
+        ProcessSpawner {
+            path: path.to_owned(),
+            args: Vec::new(),
+            env: None,
+            file_actions: Vec::new(),
+            search_path: false,
+            setsid: false,
+            pgroup: None,
+            #[cfg(feature = "signal")]
+            sigmask: None,
+            #[cfg(feature = "signal")]
+            sigdefault: None,
+        }
+    }
+
+    /// Set the argument vector, including the program name as `args[0]`.
+    pub fn args<S: AsRef<CStr>>(&mut self, args: &[S]) -> &mut Self {
+        self.args = args.iter().map(|s| s.as_ref().to_owned()).collect();
+        self
+    }
+
+    /// Replace the environment of the child.  Each element should be a
+    /// string in the form "key=value".
+    pub fn env<S: AsRef<CStr>>(&mut self, env: &[S]) -> &mut Self {
+        self.env = Some(env.iter().map(|s| s.as_ref().to_owned()).collect());
+        self
+    }
+
+    /// Look up the program in `PATH` if it contains no slash, as with
+    /// [`execvp`], by spawning it with `posix_spawnp`.
+    pub fn search_path(&mut self, search_path: bool) -> &mut Self {
+        self.search_path = search_path;
+        self
+    }
+
+    /// Close `fd` in the child.
+    pub fn close(&mut self, fd: RawFd) -> &mut Self {
+        self.file_actions.push(SpawnFileAction::Close(fd));
+        self
+    }
+
+    /// Duplicate `oldfd` onto `newfd` in the child, as with [`dup2`].
+    pub fn dup2(&mut self, oldfd: RawFd, newfd: RawFd) -> &mut Self {
+        self.file_actions.push(SpawnFileAction::Dup2(oldfd, newfd));
+        self
+    }
+
+    /// Open `path` as `fd` in the child, as with [`open`](crate::fcntl::open)
+    /// followed by [`dup2`].
+    #[cfg(feature = "fs")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "fs")))]
+    pub fn open(
+        &mut self,
+        fd: RawFd,
+        path: &CStr,
+        oflag: OFlag,
+        mode: Mode,
+    ) -> &mut Self {
+        self.file_actions.push(SpawnFileAction::Open(
+            fd,
+            path.to_owned(),
+            oflag,
+            mode,
+        ));
+        self
+    }
+
+    /// Make the child the leader of a new session, as with [`setsid`].
+    pub fn setsid(&mut self, setsid: bool) -> &mut Self {
+        self.setsid = setsid;
+        self
+    }
+
+    /// Move the child to process group `pgid`, as with [`setpgid`].  A
+    /// `pgid` of 0 makes the child the leader of a new group.
+    pub fn process_group(&mut self, pgid: Pid) -> &mut Self {
+        self.pgroup = Some(pgid);
+        self
+    }
+
+    /// Set the signal mask of the child.
+    #[cfg(feature = "signal")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "signal")))]
+    pub fn sigmask(&mut self, mask: &crate::sys::signal::SigSet) -> &mut Self {
+        self.sigmask = Some(*mask);
+        self
+    }
+
+    /// Reset the signals in `set` to their default disposition in the
+    /// child.
+    #[cfg(feature = "signal")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "signal")))]
+    pub fn sigdefault(
+        &mut self,
+        set: &crate::sys::signal::SigSet,
+    ) -> &mut Self {
+        self.sigdefault = Some(*set);
+        self
+    }
+
+    /// Launch the program, returning the ID of the new process.
+    ///
+    /// Errors from the file actions or from executing the program are
+    /// reported here too, as long as the C library can detect them before
+    /// the program starts, as glibc and musl do.
+    pub fn spawn(&self) -> Result<Pid> {
+        extern "C" {
+            static environ: *const *mut c_char;
+        }
+
+        let mut actions = SpawnFileActions::new()?;
+        for action in &self.file_actions {
+            let res = unsafe {
+                match action {
+                    SpawnFileAction::Close(fd) => {
+                        libc::posix_spawn_file_actions_addclose(
+                            &mut actions.0,
+                            *fd,
+                        )
+                    }
+                    SpawnFileAction::Dup2(oldfd, newfd) => {
+                        libc::posix_spawn_file_actions_adddup2(
+                            &mut actions.0,
+                            *oldfd,
+                            *newfd,
+                        )
+                    }
+                    #[cfg(feature = "fs")]
+                    SpawnFileAction::Open(fd, path, oflag, mode) => {
+                        libc::posix_spawn_file_actions_addopen(
+                            &mut actions.0,
+                            *fd,
+                            path.as_ptr(),
+                            oflag.bits(),
+                            mode.bits(),
+                        )
+                    }
+                }
+            };
+            spawn_result(res)?;
+        }
+
+        let mut attr = SpawnAttr::new()?;
+        let mut flags: libc::c_short = 0;
+        if self.setsid {
+            flags |= POSIX_SPAWN_SETSID;
+        }
+        if let Some(pgid) = self.pgroup {
+            flags |= libc::POSIX_SPAWN_SETPGROUP as libc::c_short;
+            spawn_result(unsafe {
+                libc::posix_spawnattr_setpgroup(&mut attr.0, pgid.into())
+            })?;
+        }
+        #[cfg(feature = "signal")]
+        if let Some(mask) = &self.sigmask {
+            flags |= libc::POSIX_SPAWN_SETSIGMASK as libc::c_short;
+            spawn_result(unsafe {
+                libc::posix_spawnattr_setsigmask(&mut attr.0, mask.as_ref())
+            })?;
+        }
+        #[cfg(feature = "signal")]
+        if let Some(set) = &self.sigdefault {
+            flags |= libc::POSIX_SPAWN_SETSIGDEF as libc::c_short;
+            spawn_result(unsafe {
+                libc::posix_spawnattr_setsigdefault(&mut attr.0, set.as_ref())
+            })?;
+        }
+        spawn_result(unsafe {
+            libc::posix_spawnattr_setflags(&mut attr.0, flags)
+        })?;
+
+        let args_p = if self.args.is_empty() {
+            to_exec_array(&[&self.path])
+        } else {
+            to_exec_array(&self.args)
+        };
+        let env_p = self.env.as_ref().map(|env| to_exec_array(env.as_slice()));
+        let envp = match &env_p {
+            Some(env_p) => env_p.as_ptr() as *const *mut c_char,
+            None => unsafe { environ },
+        };
+
+        let mut pid = 0;
+        let res = unsafe {
+            let spawn = if self.search_path {
+                libc::posix_spawnp
+            } else {
+                libc::posix_spawn
+            };
+            spawn(
+                &mut pid,
+                self.path.as_ptr(),
+                &actions.0,
+                &attr.0,
+                args_p.as_ptr() as *const *mut c_char,
+                envp,
+            )
+        };
+        spawn_result(res).map(|_| Pid::from_raw(pid))
+    }
+}
 }
 
 feature! {
@@ -1068,6 +1478,24 @@ pub fn gethostname() -> Result<OsString> {
         OsString::from_vec(buffer)
     })
 }
+
+/// Set the NIS domain name of the system (see
+/// [setdomainname(2)](https://man7.org/linux/man-pages/man2/setdomainname.2.html)).
+///
+/// Like the host name, the domain name belongs to the caller's UTS
+/// namespace, so a process that has unshared one with `CLONE_NEWUTS` can
+/// change it without affecting the rest of the system.  It is limited to 64
+/// bytes, and changing it requires `CAP_SYS_ADMIN`.  Read it back with
+/// [`uname`](crate::sys::utsname::uname).
+#[cfg(target_os = "linux")]
+#[cfg_attr(docsrs, doc(cfg(all())))]
+pub fn setdomainname<S: AsRef<OsStr>>(name: S) -> Result<()> {
+    let name = name.as_ref().as_bytes();
+    let res = unsafe {
+        libc::setdomainname(name.as_ptr() as *const c_char, name.len())
+    };
+    Errno::result(res).map(drop)
+}
 }
 
 /// Close a raw file descriptor
@@ -1100,6 +1528,90 @@ pub fn close(fd: RawFd) -> Result<()> {
     Errno::result(res).map(drop)
 }
 
+#[cfg(target_os = "linux")]
+bitflags::bitflags! {
+    /// Flags for [`close_range`].
+    #[cfg_attr(docsrs, doc(cfg(all())))]
+    pub struct CloseRangeFlags: c_uint {
+        /// Unshare the file descriptor table first, so that other threads
+        /// or processes sharing it with `CLONE_FILES` aren't affected.
+        const CLOSE_RANGE_UNSHARE = 1 << 1;
+        /// Set the close-on-exec flag on the descriptors instead of closing
+        /// them.  Requires Linux 5.11 or later.
+        const CLOSE_RANGE_CLOEXEC = 1 << 2;
+    }
+}
+
+/// Close all open file descriptors from `first` to `last`, inclusive.
+///
+/// Descriptors in the range that aren't open are ignored, so
+/// `close_range(3, c_uint::MAX, ...)` closes everything but stdio, without
+/// first having to find out which descriptors are open.  Requires Linux
+/// 5.9 or later.
+///
+/// As with [`close`], beware of closing descriptors still owned by a `File`
+/// or an `OwnedFd`.  This is mostly useful in a child process that is about
+/// to call `execve`.
+///
+/// See also [close_range(2)](https://man7.org/linux/man-pages/man2/close_range.2.html)
+#[cfg(target_os = "linux")]
+#[cfg_attr(docsrs, doc(cfg(all())))]
+pub fn close_range(
+    first: c_uint,
+    last: c_uint,
+    flags: CloseRangeFlags,
+) -> Result<()> {
+    let res = unsafe {
+        libc::syscall(libc::SYS_close_range, first, last, flags.bits())
+    };
+    Errno::result(res).map(drop)
+}
+
+/// Set the close-on-exec flag on every open file descriptor greater than
+/// `min_fd`, so that none of them leak into a program started with
+/// `execve`.
+///
+/// This uses [`close_range`] with `CLOSE_RANGE_CLOEXEC` if the kernel
+/// supports it, and otherwise sets the flag on each descriptor listed in
+/// `/proc/self/fd`.  In the latter case, descriptors opened concurrently by
+/// other threads may be missed.
+#[cfg(all(feature = "fs", target_os = "linux"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "fs")))]
+pub fn mark_fds_cloexec_above(min_fd: RawFd) -> Result<()> {
+    let first = match min_fd.checked_add(1) {
+        Some(fd) => fd.max(0) as c_uint,
+        None => return Ok(()),
+    };
+    match close_range(first, c_uint::MAX, CloseRangeFlags::CLOSE_RANGE_CLOEXEC)
+    {
+        Err(Errno::ENOSYS | Errno::EINVAL) => (),
+        res => return res,
+    }
+
+    let io_err = |e: std::io::Error| Errno::try_from(e).unwrap_or(Errno::EIO);
+    // Collect the descriptors first, as the directory has one of its own
+    let fds = std::fs::read_dir("/proc/self/fd")
+        .map_err(io_err)?
+        .map(|entry| {
+            let name = entry.map_err(io_err)?.file_name();
+            name.to_str()
+                .and_then(|name| name.parse::<RawFd>().ok())
+                .ok_or(Errno::EINVAL)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    for fd in fds.into_iter().filter(|&fd| fd > min_fd) {
+        let flags = match fcntl(fd, crate::fcntl::FcntlArg::F_GETFD) {
+            // The directory's own descriptor, since closed
+            Err(Errno::EBADF) => continue,
+            res => FdFlag::from_bits_truncate(res?),
+        };
+        if !flags.contains(FdFlag::FD_CLOEXEC) {
+            fcntl(fd, F_SETFD(flags | FdFlag::FD_CLOEXEC))?;
+        }
+    }
+    Ok(())
+}
+
 /// Read from a raw file descriptor.
 ///
 /// See also [read(2)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/read.html)
@@ -1122,6 +1634,53 @@ pub fn write(fd: RawFd, buf: &[u8]) -> Result<usize> {
     Errno::result(res).map(|r| r as usize)
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+bitflags::bitflags! {
+    /// Flags for [`getrandom`].
+    #[cfg_attr(docsrs, doc(cfg(all())))]
+    pub struct GetRandomFlags: c_uint {
+        /// Fail with `EAGAIN` instead of blocking if the entropy pool isn't
+        /// initialized yet, or, with `GRND_RANDOM`, has too little entropy.
+        const GRND_NONBLOCK = 0x0001;
+        /// Draw from the `/dev/random` pool instead of `/dev/urandom`.
+        const GRND_RANDOM = 0x0002;
+    }
+}
+
+/// Fill `buf` with random bytes from the kernel, without opening a device
+/// file.
+///
+/// The kernel may return fewer bytes than asked for, especially with
+/// `GRND_RANDOM`, or be interrupted by a signal; this retries until `buf` is
+/// full.  If `GRND_NONBLOCK` is given and the kernel runs out after part of
+/// `buf` was filled, the number of bytes filled so far is returned, so check
+/// the result in that case.  Requires Linux 3.17 or later.
+///
+/// See also [getrandom(2)](https://man7.org/linux/man-pages/man2/getrandom.2.html)
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[cfg_attr(docsrs, doc(cfg(all())))]
+pub fn getrandom(buf: &mut [u8], flags: GetRandomFlags) -> Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        let rest = &mut buf[filled..];
+        let res = unsafe {
+            libc::syscall(
+                libc::SYS_getrandom,
+                rest.as_mut_ptr(),
+                rest.len(),
+                flags.bits(),
+            )
+        };
+        match Errno::result(res) {
+            Ok(n) => filled += n as usize,
+            Err(Errno::EINTR) => (),
+            Err(Errno::EAGAIN) if filled > 0 => break,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
 feature! {
 #![feature = "fs"]
 
@@ -1282,32 +1841,43 @@ pub enum LinkatFlags {
     NoSymlinkFollow,
 }
 
+impl From<LinkatFlags> for AtFlags {
+    fn from(flag: LinkatFlags) -> Self {
+        match flag {
+            LinkatFlags::SymlinkFollow => AtFlags::AT_SYMLINK_FOLLOW,
+            LinkatFlags::NoSymlinkFollow => AtFlags::empty(),
+        }
+    }
+}
+
 /// Link one file to another file
 ///
 /// Creates a new link (directory entry) at `newpath` for the existing file at `oldpath`. In the
 /// case of a relative `oldpath`, the path is interpreted relative to the directory associated
 /// with file descriptor `olddirfd` instead of the current working directory and similiarly for
-/// `newpath` and file descriptor `newdirfd`. In case `flag` is LinkatFlags::SymlinkFollow and
+/// `newpath` and file descriptor `newdirfd`. `flag` is either a [`LinkatFlags`] or an
+/// [`AtFlags`]. In case it contains `AT_SYMLINK_FOLLOW` and
 /// `oldpath` names a symoblic link, a new link for the target of the symbolic link is created.
 /// If either `olddirfd` or `newdirfd` is `None`, `AT_FDCWD` is used respectively where `oldpath`
 /// and/or `newpath` is then interpreted relative to the current working directory of the calling
 /// process. If either `oldpath` or `newpath` is absolute, then `dirfd` is ignored.
 ///
+/// On Linux and Android, if `flag` contains `AT_EMPTY_PATH` and `oldpath` is empty, a link to
+/// the file referred to by `olddirfd` is created; this requires `CAP_DAC_READ_SEARCH`.  Other
+/// flags, including `AT_SYMLINK_NOFOLLOW`, which is the default anyway, are rejected with
+/// `EINVAL`.
+///
 /// # References
 /// See also [linkat(2)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/linkat.html)
 #[cfg(not(target_os = "redox"))] // RedoxFS does not support symlinks yet
-pub fn linkat<P: ?Sized + NixPath>(
+pub fn linkat<P: ?Sized + NixPath, F: Into<AtFlags>>(
     olddirfd: Option<RawFd>,
     oldpath: &P,
     newdirfd: Option<RawFd>,
     newpath: &P,
-    flag: LinkatFlags,
+    flag: F,
 ) -> Result<()> {
-    let atflag = match flag {
-        LinkatFlags::SymlinkFollow => AtFlags::AT_SYMLINK_FOLLOW,
-        LinkatFlags::NoSymlinkFollow => AtFlags::empty(),
-    };
-
+    let flag = flag.into();
     let res = oldpath.with_nix_path(|oldcstr| {
         newpath.with_nix_path(|newcstr| unsafe {
             libc::linkat(
@@ -1315,7 +1885,7 @@ pub fn linkat<P: ?Sized + NixPath>(
                 oldcstr.as_ptr(),
                 at_rawfd(newdirfd),
                 newcstr.as_ptr(),
-                atflag.bits() as libc::c_int,
+                flag.bits() as libc::c_int,
             )
         })
     })??;
@@ -1346,6 +1916,10 @@ pub enum UnlinkatFlags {
 /// `UnlinkatFlags::RemoveDir` then removal of the directory entry specified by `dirfd` and `path`
 /// is performed.
 ///
+/// Unlike the other at-family functions, `unlinkat` takes no `AT_SYMLINK_NOFOLLOW` or
+/// `AT_EMPTY_PATH` flags: it never follows a symbolic link at the end of `path`, and always
+/// needs a name to remove.
+///
 /// # References
 /// See also [unlinkat(2)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/unlinkat.html)
 #[cfg(not(target_os = "redox"))]
@@ -3245,11 +3819,25 @@ mod setres {
     /// * `suid`: saved user id
     /// * returns: Ok or libc error code.
     ///
+    /// Any id given as `None` is left unchanged.
+    ///
     /// Err is returned if the user doesn't have permission to set this UID.
     #[inline]
-    pub fn setresuid(ruid: Uid, euid: Uid, suid: Uid) -> Result<()> {
-        let res =
-            unsafe { libc::setresuid(ruid.into(), euid.into(), suid.into()) };
+    pub fn setresuid<R, E, S>(ruid: R, euid: E, suid: S) -> Result<()>
+    where
+        R: Into<Option<Uid>>,
+        E: Into<Option<Uid>>,
+        S: Into<Option<Uid>>,
+    {
+        // -1 leaves the id unchanged
+        let raw = |uid: Option<Uid>| uid.map_or(libc::uid_t::MAX, Into::into);
+        let res = unsafe {
+            libc::setresuid(
+                raw(ruid.into()),
+                raw(euid.into()),
+                raw(suid.into()),
+            )
+        };
 
         Errno::result(res).map(drop)
     }
@@ -3262,11 +3850,25 @@ mod setres {
     /// * `sgid`: saved group id
     /// * returns: Ok or libc error code.
     ///
+    /// Any id given as `None` is left unchanged.
+    ///
     /// Err is returned if the user doesn't have permission to set this GID.
     #[inline]
-    pub fn setresgid(rgid: Gid, egid: Gid, sgid: Gid) -> Result<()> {
-        let res =
-            unsafe { libc::setresgid(rgid.into(), egid.into(), sgid.into()) };
+    pub fn setresgid<R, E, S>(rgid: R, egid: E, sgid: S) -> Result<()>
+    where
+        R: Into<Option<Gid>>,
+        E: Into<Option<Gid>>,
+        S: Into<Option<Gid>>,
+    {
+        // -1 leaves the id unchanged
+        let raw = |gid: Option<Gid>| gid.map_or(libc::gid_t::MAX, Into::into);
+        let res = unsafe {
+            libc::setresgid(
+                raw(rgid.into()),
+                raw(egid.into()),
+                raw(sgid.into()),
+            )
+        };
 
         Errno::result(res).map(drop)
     }
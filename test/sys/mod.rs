@@ -13,17 +13,35 @@ mod test_signal;
     target_os = "netbsd"
 ))]
 mod test_aio;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod test_capability;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod test_futex;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod test_io_uring;
 #[cfg(not(any(
     target_os = "redox",
     target_os = "fuchsia",
     target_os = "haiku"
 )))]
 mod test_ioctl;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod test_ioprio;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod test_membarrier;
+#[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+mod test_memfd;
 #[cfg(not(target_os = "redox"))]
 mod test_mman;
 #[cfg(not(target_os = "redox"))]
 mod test_select;
 #[cfg(target_os = "linux")]
+mod test_pidfd;
+#[cfg(target_os = "linux")]
+mod test_quota;
+#[cfg(target_os = "linux")]
+mod test_reboot;
+#[cfg(target_os = "linux")]
 mod test_signalfd;
 #[cfg(not(any(target_os = "redox", target_os = "haiku")))]
 mod test_socket;
@@ -31,6 +49,8 @@ mod test_socket;
 mod test_sockopt;
 mod test_stat;
 #[cfg(any(target_os = "android", target_os = "linux"))]
+mod test_statx;
+#[cfg(any(target_os = "android", target_os = "linux"))]
 mod test_sysinfo;
 #[cfg(not(any(
     target_os = "redox",
@@ -43,8 +63,12 @@ mod test_wait;
 
 #[cfg(any(target_os = "android", target_os = "linux"))]
 mod test_epoll;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod test_eventfd;
 #[cfg(target_os = "linux")]
 mod test_inotify;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod test_prctl;
 mod test_pthread;
 #[cfg(any(
     target_os = "android",
@@ -0,0 +1,49 @@
+use nix::errno::Errno;
+use nix::sys::capability::{capget, capset, CapSet, CapState, Capability};
+use nix::sys::wait::{waitpid, WaitStatus};
+use nix::unistd::{fork, getpid, setuid, ForkResult, Uid};
+
+#[test]
+fn test_capget() {
+    let state = capget(None).unwrap();
+    assert_eq!(capget(Some(getpid())), Ok(state));
+    // The effective set is always a subset of the permitted set
+    assert_eq!(
+        state.effective.bits() & !state.permitted.bits(),
+        0,
+        "{:?}",
+        state
+    );
+}
+
+#[test]
+fn test_capset_drop_all() {
+    skip_if_not_root!("test_capset_drop_all");
+
+    let _m = crate::FORK_MTX.lock();
+
+    // Drop the capabilities in a child, so the test process keeps its own
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        ForkResult::Child => {
+            let dropped = capset(None, &CapState::default()).is_ok()
+                && capget(None) == Ok(CapState::default())
+                // Still root, but without CAP_SETUID
+                && setuid(Uid::from_raw(1)) == Err(Errno::EPERM)
+                // Capabilities can't be regained
+                && capset(
+                    None,
+                    &CapState {
+                        effective: CapSet::empty(),
+                        permitted: [Capability::CAP_SETUID]
+                            .into_iter()
+                            .collect(),
+                        inheritable: CapSet::empty(),
+                    },
+                ) == Err(Errno::EPERM);
+            unsafe { libc::_exit(if dropped { 0 } else { 1 }) };
+        }
+        ForkResult::Parent { child } => {
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+        }
+    }
+}
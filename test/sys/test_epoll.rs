@@ -2,7 +2,11 @@
 
 use nix::errno::Errno;
 use nix::sys::epoll::{epoll_create1, epoll_ctl};
-use nix::sys::epoll::{EpollCreateFlags, EpollEvent, EpollFlags, EpollOp};
+use nix::sys::epoll::{
+    Epoll, EpollCreateFlags, EpollEvent, EpollFlags, EpollOp,
+};
+use nix::unistd::{pipe, write};
+use std::os::unix::io::{AsFd, AsRawFd, FromRawFd, OwnedFd};
 
 #[test]
 pub fn test_epoll_errno() {
@@ -24,3 +28,36 @@ pub fn test_epoll_ctl() {
     epoll_ctl(efd, EpollOp::EpollCtlAdd, 1, &mut event).unwrap();
     epoll_ctl(efd, EpollOp::EpollCtlDel, 1, None).unwrap();
 }
+
+#[test]
+pub fn test_epoll_pipe_readiness() {
+    let epoll = Epoll::new(EpollCreateFlags::EPOLL_CLOEXEC).unwrap();
+    let (r, w) = pipe().unwrap();
+    let (r, w) = unsafe { (OwnedFd::from_raw_fd(r), OwnedFd::from_raw_fd(w)) };
+    epoll
+        .add(&r, EpollEvent::new(EpollFlags::EPOLLIN, 42))
+        .unwrap();
+
+    let mut events = [EpollEvent::empty(); 2];
+    assert_eq!(epoll.wait(&mut events, 0), Ok(0));
+
+    write(w.as_raw_fd(), b"x").unwrap();
+    assert_eq!(epoll.wait(&mut events, 1000), Ok(1));
+    assert_eq!(events[0].data(), 42);
+    assert!(events[0].events().contains(EpollFlags::EPOLLIN));
+
+    // The epoll instance is itself pollable
+    let outer = Epoll::new(EpollCreateFlags::empty()).unwrap();
+    outer
+        .add(epoll.as_fd(), EpollEvent::new(EpollFlags::EPOLLIN, 7))
+        .unwrap();
+    assert_eq!(outer.wait(&mut events, 0), Ok(1));
+    assert_eq!(events[0].data(), 7);
+
+    let mut event = EpollEvent::new(EpollFlags::EPOLLOUT, 43);
+    epoll.modify(&r, &mut event).unwrap();
+    assert_eq!(epoll.wait(&mut events, 0), Ok(0));
+
+    epoll.delete(&r).unwrap();
+    assert_eq!(epoll.delete(&r), Err(Errno::ENOENT));
+}
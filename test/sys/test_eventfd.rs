@@ -0,0 +1,31 @@
+use nix::errno::Errno;
+use nix::sys::eventfd::{EfdFlags, EventFd};
+use std::sync::Arc;
+
+#[test]
+fn test_eventfd_counter_across_threads() {
+    let efd = Arc::new(EventFd::new(0, EfdFlags::EFD_CLOEXEC).unwrap());
+
+    let writer = {
+        let efd = Arc::clone(&efd);
+        std::thread::spawn(move || {
+            efd.write(3).unwrap();
+            efd.write(4).unwrap();
+        })
+    };
+    writer.join().unwrap();
+
+    // Reading takes the whole counter
+    assert_eq!(efd.read(), Ok(7));
+    assert_eq!(efd.write(u64::MAX), Err(Errno::EINVAL));
+}
+
+#[test]
+fn test_eventfd_semaphore() {
+    let flags = EfdFlags::EFD_SEMAPHORE | EfdFlags::EFD_NONBLOCK;
+    let efd = EventFd::new(2, flags).unwrap();
+
+    assert_eq!(efd.read(), Ok(1));
+    assert_eq!(efd.read(), Ok(1));
+    assert_eq!(efd.read(), Err(Errno::EAGAIN));
+}
@@ -0,0 +1,125 @@
+use nix::errno::Errno;
+use nix::sys::futex::{futex, get_robust_list, FutexArg, FutexFlags, FutexOp};
+use nix::sys::time::TimeSpec;
+use nix::unistd::{gettid, Pid};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn test_get_robust_list() {
+    let (head, len) = get_robust_list(Pid::from_raw(0)).unwrap();
+    assert_eq!(get_robust_list(gettid()), Ok((head, len)));
+
+    // glibc registers a robust list for every thread on creation
+    #[cfg(target_env = "gnu")]
+    {
+        assert!(!head.is_null());
+        assert_eq!(len, 3 * std::mem::size_of::<usize>());
+    }
+}
+
+#[test]
+fn test_futex_wait_wake() {
+    let word = Arc::new(AtomicU32::new(0));
+    let flags = FutexFlags::FUTEX_PRIVATE_FLAG;
+
+    // The word doesn't hold the expected value
+    assert_eq!(
+        futex(&word, FutexOp::FUTEX_WAIT, flags, 1, None, None, 0),
+        Err(Errno::EAGAIN)
+    );
+    // Nobody wakes us up
+    let timeout = TimeSpec::from(Duration::from_millis(10));
+    assert_eq!(
+        futex(
+            &word,
+            FutexOp::FUTEX_WAIT,
+            flags,
+            0,
+            Some(FutexArg::Timeout(&timeout)),
+            None,
+            0
+        ),
+        Err(Errno::ETIMEDOUT)
+    );
+
+    let waiter = {
+        let word = word.clone();
+        thread::spawn(move || {
+            while word.load(Ordering::SeqCst) == 0 {
+                match futex(&word, FutexOp::FUTEX_WAIT, flags, 0, None, None, 0)
+                {
+                    Ok(_) | Err(Errno::EAGAIN) | Err(Errno::EINTR) => (),
+                    Err(e) => return Err(e),
+                }
+            }
+            Ok(word.load(Ordering::SeqCst))
+        })
+    };
+
+    thread::sleep(Duration::from_millis(10));
+    word.store(42, Ordering::SeqCst);
+    futex(&word, FutexOp::FUTEX_WAKE, flags, 1, None, None, 0).unwrap();
+
+    assert_eq!(waiter.join().unwrap(), Ok(42));
+}
+
+#[test]
+fn test_futex_requeue() {
+    let word1 = Arc::new(AtomicU32::new(0));
+    let word2 = Arc::new(AtomicU32::new(0));
+    let flags = FutexFlags::FUTEX_PRIVATE_FLAG;
+
+    let waiters: Vec<_> = (0..2)
+        .map(|_| {
+            let word1 = word1.clone();
+            thread::spawn(move || {
+                futex(&word1, FutexOp::FUTEX_WAIT, flags, 0, None, None, 0)
+            })
+        })
+        .collect();
+
+    // Move both waiters onto word2 once they are asleep, without waking any
+    let mut moved = 0;
+    while moved < 2 {
+        thread::sleep(Duration::from_millis(10));
+        moved += futex(
+            &word1,
+            FutexOp::FUTEX_REQUEUE,
+            flags,
+            0,
+            Some(FutexArg::Val2(2)),
+            Some(&word2),
+            0,
+        )
+        .unwrap();
+    }
+
+    // Wake one and requeue exactly one back onto word1
+    assert_eq!(
+        futex(
+            &word2,
+            FutexOp::FUTEX_CMP_REQUEUE,
+            flags,
+            1,
+            Some(FutexArg::Val2(1)),
+            Some(&word1),
+            0
+        ),
+        Ok(2)
+    );
+    assert_eq!(
+        futex(&word2, FutexOp::FUTEX_WAKE, flags, 1, None, None, 0),
+        Ok(0)
+    );
+    assert_eq!(
+        futex(&word1, FutexOp::FUTEX_WAKE, flags, 1, None, None, 0),
+        Ok(1)
+    );
+
+    for waiter in waiters {
+        assert_eq!(waiter.join().unwrap(), Ok(0));
+    }
+}
@@ -63,3 +63,22 @@ pub fn test_inotify_multi_events() {
 
     assert_eq!(events[3].cookie, events[4].cookie);
 }
+
+#[test]
+pub fn test_inotify_events_iterator() {
+    let instance = Inotify::init(InitFlags::IN_NONBLOCK).unwrap();
+    let tempdir = tempfile::tempdir().unwrap();
+
+    instance
+        .add_watch(tempdir.path(), AddWatchFlags::IN_CREATE)
+        .unwrap();
+
+    assert!(instance.events().next().is_none());
+
+    File::create(tempdir.path().join("created")).unwrap();
+
+    let events = instance.events().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].mask, AddWatchFlags::IN_CREATE);
+    assert_eq!(events[0].name, Some(OsString::from("created")));
+}
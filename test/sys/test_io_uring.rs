@@ -0,0 +1,48 @@
+use nix::errno::Errno;
+use nix::sys::io_uring::{IoUring, IoUringSqe};
+
+#[test]
+fn test_io_uring_nop() {
+    let mut ring = match IoUring::new(4) {
+        // Disabled by the kernel config, the io_uring_disabled sysctl or a
+        // seccomp filter
+        Err(Errno::ENOSYS) | Err(Errno::EPERM) => {
+            skip!("io_uring is not available. Skipping test.");
+        }
+        r => r.unwrap(),
+    };
+    assert!(ring.params().sq_entries >= 4);
+    assert_eq!(ring.pop(), None);
+
+    ring.push(IoUringSqe::nop(0xdead_beef)).unwrap();
+    assert_eq!(ring.submit_and_wait(1), Ok(1));
+
+    let cqe = ring.pop().unwrap();
+    assert_eq!(cqe.user_data, 0xdead_beef);
+    assert_eq!(cqe.res, 0);
+    assert_eq!(ring.pop(), None);
+}
+
+#[test]
+fn test_io_uring_push_full() {
+    let mut ring = match IoUring::new(1) {
+        Err(Errno::ENOSYS) | Err(Errno::EPERM) => {
+            skip!("io_uring is not available. Skipping test.");
+        }
+        r => r.unwrap(),
+    };
+    let entries = ring.params().sq_entries;
+    for i in 0..entries {
+        ring.push(IoUringSqe::nop(i.into())).unwrap();
+    }
+    assert_eq!(ring.push(IoUringSqe::nop(0)), Err(Errno::EBUSY));
+
+    // Anything but a NOP needs push_unchecked, even without an address:
+    // IORING_OP_CLOSE would close an arbitrary fd
+    let sqe = IoUringSqe {
+        opcode: 19,
+        fd: 0,
+        ..IoUringSqe::nop(0)
+    };
+    assert_eq!(ring.push(sqe), Err(Errno::EINVAL));
+}
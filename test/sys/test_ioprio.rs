@@ -0,0 +1,58 @@
+use nix::errno::Errno;
+use nix::sys::ioprio::{ioprio_get, ioprio_set, IoPrioClass, IoPrioWho};
+use nix::sys::signal::{kill, Signal};
+use nix::sys::wait::waitpid;
+use nix::unistd::{fork, pause, ForkResult::*};
+
+#[test]
+fn test_ioprio_idle_child() {
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => loop {
+            pause();
+        },
+        Parent { child } => {
+            let who = child.as_raw();
+            ioprio_set(
+                IoPrioWho::IOPRIO_WHO_PROCESS,
+                who,
+                IoPrioClass::IOPRIO_CLASS_IDLE,
+                0,
+            )
+            .unwrap();
+            assert_eq!(
+                ioprio_get(IoPrioWho::IOPRIO_WHO_PROCESS, who),
+                Ok((IoPrioClass::IOPRIO_CLASS_IDLE, 0))
+            );
+
+            ioprio_set(
+                IoPrioWho::IOPRIO_WHO_PROCESS,
+                who,
+                IoPrioClass::IOPRIO_CLASS_BE,
+                7,
+            )
+            .unwrap();
+            assert_eq!(
+                ioprio_get(IoPrioWho::IOPRIO_WHO_PROCESS, who),
+                Ok((IoPrioClass::IOPRIO_CLASS_BE, 7))
+            );
+
+            kill(child, Signal::SIGKILL).unwrap();
+            waitpid(child, None).unwrap();
+        }
+    }
+}
+
+#[test]
+fn test_ioprio_set_invalid_level() {
+    assert_eq!(
+        ioprio_set(
+            IoPrioWho::IOPRIO_WHO_PROCESS,
+            0,
+            IoPrioClass::IOPRIO_CLASS_BE,
+            1 << 13
+        ),
+        Err(Errno::EINVAL)
+    );
+}
@@ -0,0 +1,47 @@
+use nix::errno::Errno;
+use nix::sys::membarrier::{
+    membarrier, membarrier_query, MembarrierCmd, MembarrierFlags,
+};
+
+#[test]
+fn test_membarrier_global() {
+    let supported = match membarrier_query() {
+        // Kernel built without CONFIG_MEMBARRIER, or a seccomp filter
+        Err(Errno::ENOSYS) | Err(Errno::EPERM) => {
+            skip!("membarrier is not available. Skipping test.");
+        }
+        r => r.unwrap(),
+    };
+    let global = MembarrierCmd::MEMBARRIER_CMD_GLOBAL as libc::c_int;
+    if supported & global == 0 {
+        // MEMBARRIER_CMD_GLOBAL is unsupported on nohz_full kernels
+        skip!("MEMBARRIER_CMD_GLOBAL is not supported. Skipping test.");
+    }
+
+    membarrier(
+        MembarrierCmd::MEMBARRIER_CMD_GLOBAL,
+        MembarrierFlags::empty(),
+        0,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_membarrier_unregistered() {
+    let supported = match membarrier_query() {
+        Err(Errno::ENOSYS) | Err(Errno::EPERM) => {
+            skip!("membarrier is not available. Skipping test.");
+        }
+        r => r.unwrap(),
+    };
+    let cmd = MembarrierCmd::MEMBARRIER_CMD_PRIVATE_EXPEDITED_SYNC_CORE;
+    if supported & cmd as libc::c_int == 0 {
+        skip!("Core serializing barriers are not supported. Skipping test.");
+    }
+
+    // Expedited commands need a prior registration
+    assert_eq!(
+        membarrier(cmd, MembarrierFlags::empty(), 0),
+        Err(Errno::EPERM)
+    );
+}
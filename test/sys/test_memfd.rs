@@ -0,0 +1,54 @@
+use nix::errno::Errno;
+use nix::fcntl::SealFlag;
+use nix::sys::memfd::{
+    memfd_add_seals, memfd_create, memfd_get_seals, MemFdCreateFlag,
+};
+use nix::unistd::{ftruncate, write};
+use std::ffi::CStr;
+use std::os::unix::io::AsRawFd;
+
+#[test]
+fn test_memfd_seal_size() {
+    let name = CStr::from_bytes_with_nul(b"nix-seals\0").unwrap();
+    let fd = memfd_create(name, MemFdCreateFlag::MFD_ALLOW_SEALING).unwrap();
+    assert_eq!(memfd_get_seals(&fd), Ok(SealFlag::empty()));
+    ftruncate(&fd, 4).unwrap();
+
+    memfd_add_seals(&fd, SealFlag::F_SEAL_SHRINK | SealFlag::F_SEAL_GROW)
+        .unwrap();
+    assert_eq!(
+        memfd_get_seals(&fd),
+        Ok(SealFlag::F_SEAL_SHRINK | SealFlag::F_SEAL_GROW)
+    );
+
+    // Writing within the current size is still allowed
+    assert_eq!(write(fd.as_raw_fd(), b"ring"), Ok(4));
+    // But resizing isn't
+    assert_eq!(write(fd.as_raw_fd(), b"more"), Err(Errno::EPERM));
+    assert_eq!(ftruncate(&fd, 2), Err(Errno::EPERM));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_memfd_seal_future_write() {
+    let name = CStr::from_bytes_with_nul(b"nix-seals\0").unwrap();
+    let fd = memfd_create(name, MemFdCreateFlag::MFD_ALLOW_SEALING).unwrap();
+
+    match memfd_add_seals(&fd, SealFlag::F_SEAL_FUTURE_WRITE) {
+        Err(Errno::EINVAL) => skip!("F_SEAL_FUTURE_WRITE requires Linux 5.1"),
+        r => r.unwrap(),
+    }
+    assert_eq!(write(fd.as_raw_fd(), b"x"), Err(Errno::EPERM));
+}
+
+#[test]
+fn test_memfd_seal_not_allowed() {
+    let name = CStr::from_bytes_with_nul(b"nix-seals\0").unwrap();
+    let fd = memfd_create(name, MemFdCreateFlag::empty()).unwrap();
+
+    assert_eq!(memfd_get_seals(&fd), Ok(SealFlag::F_SEAL_SEAL));
+    assert_eq!(
+        memfd_add_seals(&fd, SealFlag::F_SEAL_GROW),
+        Err(Errno::EINVAL)
+    );
+}
@@ -1,4 +1,4 @@
-use nix::sys::mman::{mmap, MapFlags, ProtFlags};
+use nix::sys::mman::{mmap, msync, munmap, MapFlags, MsFlags, ProtFlags};
 use std::{num::NonZeroUsize, os::unix::io::BorrowedFd};
 
 #[test]
@@ -19,6 +19,37 @@ fn test_mmap_anonymous() {
     }
 }
 
+#[test]
+fn test_msync_file() {
+    use std::io::Read;
+
+    let mut file = tempfile::tempfile().unwrap();
+    file.set_len(4096).unwrap();
+    let len = NonZeroUsize::new(4096).unwrap();
+
+    unsafe {
+        let ptr = mmap(
+            None,
+            len,
+            ProtFlags::PROT_READ | ProtFlags::PROT_WRITE,
+            MapFlags::MAP_SHARED,
+            Some(&file),
+            0,
+        )
+        .unwrap();
+        std::slice::from_raw_parts_mut(ptr as *mut u8, 6)
+            .copy_from_slice(b"synced");
+        msync(ptr, len.get(), MsFlags::MS_SYNC).unwrap();
+        msync(ptr, len.get(), MsFlags::MS_ASYNC | MsFlags::MS_INVALIDATE)
+            .unwrap();
+        munmap(ptr, len.get()).unwrap();
+    }
+
+    let mut buf = [0u8; 6];
+    file.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"synced");
+}
+
 #[test]
 #[cfg(any(target_os = "linux", target_os = "netbsd"))]
 fn test_mremap_grow() {
@@ -75,6 +106,52 @@ fn test_mremap_grow() {
     assert_eq!(slice[10 * ONE_K - 1], 0xFF);
 }
 
+#[test]
+#[cfg(target_os = "linux")]
+fn test_mremap_fixed() {
+    use nix::libc::size_t;
+    use nix::sys::mman::{mremap, MRemapFlags};
+
+    const ONE_K: size_t = 1024;
+
+    unsafe {
+        let old = mmap::<BorrowedFd>(
+            None,
+            NonZeroUsize::new(ONE_K).unwrap(),
+            ProtFlags::PROT_READ | ProtFlags::PROT_WRITE,
+            MapFlags::MAP_ANONYMOUS | MapFlags::MAP_PRIVATE,
+            None,
+            0,
+        )
+        .unwrap();
+        // Reserve a larger region to move the mapping into
+        let target = mmap::<BorrowedFd>(
+            None,
+            NonZeroUsize::new(4 * ONE_K).unwrap(),
+            ProtFlags::PROT_NONE,
+            MapFlags::MAP_ANONYMOUS | MapFlags::MAP_PRIVATE,
+            None,
+            0,
+        )
+        .unwrap();
+        *(old as *mut u8) = 0xAB;
+
+        let new = mremap(
+            old,
+            ONE_K,
+            4 * ONE_K,
+            MRemapFlags::MREMAP_MAYMOVE | MRemapFlags::MREMAP_FIXED,
+            Some(target),
+        )
+        .unwrap();
+        assert_eq!(new, target);
+        assert_eq!(*(new as *const u8), 0xAB);
+        *((new as *mut u8).add(4 * ONE_K - 1)) = 0xCD;
+
+        munmap(new, 4 * ONE_K).unwrap();
+    }
+}
+
 #[test]
 #[cfg(any(target_os = "linux", target_os = "netbsd"))]
 // Segfaults for unknown reasons under QEMU for 32-bit targets
@@ -120,3 +197,35 @@ fn test_mremap_shrink() {
     // The first KB should still be accessible and have the old data in it.
     assert_eq!(slice[ONE_K - 1], 0xFF);
 }
+
+#[test]
+#[cfg(not(target_os = "haiku"))]
+fn test_mlock_munlock() {
+    use nix::errno::Errno;
+    use nix::sys::mman::{mlock, munlock};
+    use nix::unistd::{sysconf, SysconfVar};
+
+    let page = sysconf(SysconfVar::PAGE_SIZE).unwrap().unwrap() as usize;
+    let len = NonZeroUsize::new(page).unwrap();
+    unsafe {
+        let ptr = mmap::<BorrowedFd>(
+            None,
+            len,
+            ProtFlags::PROT_READ | ProtFlags::PROT_WRITE,
+            MapFlags::MAP_PRIVATE | MapFlags::MAP_ANONYMOUS,
+            None,
+            0,
+        )
+        .unwrap();
+        match mlock(ptr, page) {
+            // RLIMIT_MEMLOCK may be too low for even a single page.
+            Err(Errno::ENOMEM | Errno::EPERM) => {
+                munmap(ptr, page).unwrap();
+                skip!("mlock: RLIMIT_MEMLOCK too low. Skipping test.");
+            }
+            r => r.unwrap(),
+        }
+        munlock(ptr, page).unwrap();
+        munmap(ptr, page).unwrap();
+    }
+}
@@ -0,0 +1,47 @@
+use nix::errno::Errno;
+use nix::sys::pidfd::{pidfd_get_pid, pidfd_open, PidfdFlags};
+use nix::sys::wait::{waitpid, WaitStatus};
+use nix::unistd::{fork, getpid, ForkResult};
+
+#[test]
+fn test_pidfd_get_pid() {
+    let pidfd = match pidfd_open(getpid(), PidfdFlags::empty()) {
+        Err(Errno::ENOSYS) => skip!("pidfd_open requires Linux 5.3"),
+        r => r.unwrap(),
+    };
+    assert_eq!(pidfd_get_pid(&pidfd), Ok(getpid()));
+
+    let file = tempfile::tempfile().unwrap();
+    assert_eq!(pidfd_get_pid(&file), Err(Errno::EINVAL));
+
+    let _m = crate::FORK_MTX.lock();
+    match unsafe { fork() }.unwrap() {
+        ForkResult::Child => unsafe { libc::_exit(0) },
+        ForkResult::Parent { child } => {
+            let pidfd = pidfd_open(child, PidfdFlags::PIDFD_NONBLOCK).unwrap();
+            assert_eq!(pidfd_get_pid(&pidfd), Ok(child));
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+            // Older kernels keep reporting the reaped process' pid
+            let res = pidfd_get_pid(&pidfd);
+            assert!(res == Err(Errno::ESRCH) || res == Ok(child), "{:?}", res);
+        }
+    }
+}
+
+#[test]
+fn test_pidfd_open_esrch() {
+    // Find a process that is certainly gone by reaping a child
+    let _m = crate::FORK_MTX.lock();
+    let child = match unsafe { fork() }.unwrap() {
+        ForkResult::Child => unsafe { libc::_exit(0) },
+        ForkResult::Parent { child } => child,
+    };
+    waitpid(child, None).unwrap();
+
+    match pidfd_open(child, PidfdFlags::empty()) {
+        Err(Errno::ENOSYS) => skip!("pidfd_open requires Linux 5.3"),
+        Err(e) => assert_eq!(e, Errno::ESRCH),
+        // The pid was reused already
+        Ok(_) => (),
+    }
+}
@@ -0,0 +1,195 @@
+use nix::sys::prctl;
+
+#[test]
+fn test_get_set_dumpable() {
+    let original = prctl::get_dumpable().unwrap();
+
+    prctl::set_dumpable(false).unwrap();
+    assert!(!prctl::get_dumpable().unwrap());
+
+    prctl::set_dumpable(true).unwrap();
+    assert!(prctl::get_dumpable().unwrap());
+
+    prctl::set_dumpable(original).unwrap();
+}
+
+#[test]
+fn test_set_no_new_privs() {
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::{fork, ForkResult};
+
+    let _m = crate::FORK_MTX.lock();
+
+    // The attribute can't be unset, so set it in a child
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        ForkResult::Child => {
+            let ok = prctl::set_no_new_privs().is_ok()
+                && prctl::get_no_new_privs() == Ok(true);
+            unsafe { libc::_exit(if ok { 0 } else { 1 }) };
+        }
+        ForkResult::Parent { child } => {
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+        }
+    }
+}
+
+#[test]
+fn test_child_subreaper() {
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::{fork, getpid, getppid, pipe, read, write, ForkResult};
+    use std::time::Duration;
+
+    let _m = crate::FORK_MTX.lock();
+
+    assert_eq!(prctl::get_child_subreaper(), Ok(false));
+
+    // Become a subreaper in a child, so that the test process doesn't
+    // collect orphans of other tests
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        ForkResult::Child => {
+            let ok = (|| {
+                prctl::set_child_subreaper(true).ok()?;
+                if prctl::get_child_subreaper() != Ok(true) {
+                    return None;
+                }
+                let (rd, wr) = pipe().ok()?;
+                let middle = match unsafe { fork() }.ok()? {
+                    ForkResult::Child => {
+                        if let Ok(ForkResult::Child) = unsafe { fork() } {
+                            // The grandchild waits to be orphaned, and
+                            // reports its new parent
+                            let middle = getppid();
+                            for _ in 0..500 {
+                                if getppid() != middle {
+                                    break;
+                                }
+                                std::thread::sleep(Duration::from_millis(10));
+                            }
+                            let ppid = getppid().as_raw().to_ne_bytes();
+                            let _ = write(wr, &ppid);
+                        }
+                        unsafe { libc::_exit(0) };
+                    }
+                    ForkResult::Parent { child } => child,
+                };
+                waitpid(middle, None).ok()?;
+                let mut ppid = [0; 4];
+                if read(rd, &mut ppid).ok()? != 4 {
+                    return None;
+                }
+                let reparented = i32::from_ne_bytes(ppid) == getpid().as_raw();
+                // The orphan is now ours to reap
+                let reaped = matches!(
+                    nix::sys::wait::wait(),
+                    Ok(WaitStatus::Exited(_, 0))
+                );
+                Some(reparented && reaped)
+            })();
+            unsafe { libc::_exit(if ok == Some(true) { 0 } else { 1 }) };
+        }
+        ForkResult::Parent { child } => {
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+        }
+    }
+}
+
+#[test]
+fn test_pdeathsig() {
+    use nix::sys::signal::Signal;
+    use nix::sys::wait::{wait, waitpid, WaitStatus};
+    use nix::unistd::{fork, pipe, read, write, ForkResult};
+    use std::time::Duration;
+
+    let _m = crate::FORK_MTX.lock();
+
+    // A subreaper collects the grandchild once its parent is gone, so that
+    // it can tell how the grandchild died
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        ForkResult::Child => {
+            let ok = (|| {
+                prctl::set_child_subreaper(true).ok()?;
+                let (rd, wr) = pipe().ok()?;
+                let parent = match unsafe { fork() }.ok()? {
+                    ForkResult::Child => {
+                        if let Ok(ForkResult::Child) = unsafe { fork() } {
+                            let registered =
+                                prctl::set_pdeathsig(Signal::SIGTERM).is_ok()
+                                    && prctl::get_pdeathsig()
+                                        == Ok(Some(Signal::SIGTERM));
+                            if registered {
+                                let _ = write(wr, b"r");
+                                std::thread::sleep(Duration::from_secs(10));
+                            }
+                            unsafe { libc::_exit(1) };
+                        }
+                        // Die as soon as the grandchild is ready
+                        let mut buf = [0];
+                        let _ = read(rd, &mut buf);
+                        unsafe { libc::_exit(0) };
+                    }
+                    ForkResult::Parent { child } => child,
+                };
+                waitpid(parent, None).ok()?;
+                match wait().ok()? {
+                    WaitStatus::Signaled(_, Signal::SIGTERM, _) => Some(()),
+                    _ => None,
+                }
+            })();
+            unsafe { libc::_exit(if ok.is_some() { 0 } else { 1 }) };
+        }
+        ForkResult::Parent { child } => {
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+        }
+    }
+
+    prctl::set_pdeathsig(None).unwrap();
+    assert_eq!(prctl::get_pdeathsig(), Ok(None));
+}
+
+#[test]
+fn test_get_tid_address() {
+    use nix::errno::Errno;
+
+    require_capability!("test_get_tid_address", CAP_SYS_ADMIN);
+
+    let addr = match prctl::get_tid_address() {
+        Err(Errno::EINVAL) => {
+            skip!("Kernel lacks CONFIG_CHECKPOINT_RESTORE. Skipping test.");
+        }
+        r => r.unwrap(),
+    };
+    // The test runs in a thread created by the thread library, which always
+    // asks to have its thread ID cleared on exit
+    assert!(!addr.is_null());
+    // glibc points it at the thread ID itself
+    #[cfg(target_env = "gnu")]
+    assert_eq!(
+        unsafe { *(addr as *const libc::pid_t) },
+        nix::unistd::gettid().as_raw()
+    );
+}
+
+#[test]
+fn test_get_set_name() {
+    use nix::errno::Errno;
+
+    // Use a thread of our own, so that the test harness's names are left
+    // alone
+    std::thread::spawn(|| {
+        prctl::set_name("nix-tracer").unwrap();
+        assert_eq!(prctl::get_name().unwrap(), "nix-tracer");
+        let comm = format!("/proc/self/task/{}/comm", nix::unistd::gettid());
+        let comm = std::fs::read_to_string(comm).unwrap();
+        assert_eq!(comm, "nix-tracer\n");
+
+        // Names are truncated to 15 bytes, on a character boundary
+        prctl::set_name("a-very-long-thread-name").unwrap();
+        assert_eq!(prctl::get_name().unwrap(), "a-very-long-thr");
+        prctl::set_name("fourteen bytes\u{e9}").unwrap();
+        assert_eq!(prctl::get_name().unwrap(), "fourteen bytes");
+
+        assert_eq!(prctl::set_name("nul\0byte"), Err(Errno::EINVAL));
+    })
+    .join()
+    .unwrap();
+}
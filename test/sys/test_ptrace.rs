@@ -366,3 +366,1460 @@ fn test_ptrace_getsyscallinfo() {
         }
     }
 }
+
+#[cfg(all(target_os = "linux", target_arch = "x86_64", target_env = "gnu"))]
+#[test]
+fn test_ptrace_inject_syscall() {
+    use nix::sys::ptrace;
+    use nix::sys::signal::{kill, raise, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::fork;
+    use nix::unistd::ForkResult::*;
+
+    require_capability!("test_ptrace_inject_syscall", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            ptrace::traceme().unwrap();
+            loop {
+                raise(Signal::SIGSTOP).unwrap();
+            }
+        }
+        Parent { child } => {
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGSTOP))
+            );
+            let regs = ptrace::getregs(child).unwrap();
+
+            let ret = ptrace::inject_syscall(child, libc::SYS_getpid, [0; 6])
+                .unwrap();
+            assert_eq!(ret, i64::from(child.as_raw()));
+
+            // The tracee's state must be left untouched
+            let after = ptrace::getregs(child).unwrap();
+            assert_eq!(regs.rip, after.rip);
+            assert_eq!(regs.rax, after.rax);
+
+            kill(child, Signal::SIGKILL).unwrap();
+            assert!(matches!(
+                waitpid(child, None),
+                Ok(WaitStatus::Signaled(_, Signal::SIGKILL, _))
+            ));
+        }
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_ptrace_cont_wait_timeout() {
+    use nix::sys::ptrace;
+    use nix::sys::signal::{kill, raise, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::fork;
+    use nix::unistd::ForkResult::*;
+    use std::time::{Duration, Instant};
+
+    require_capability!("test_ptrace_cont_wait_timeout", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            ptrace::traceme().unwrap();
+            raise(Signal::SIGSTOP).unwrap();
+            #[allow(clippy::empty_loop)]
+            loop {}
+        }
+        Parent { child } => {
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGSTOP))
+            );
+
+            let timeout = Duration::from_millis(100);
+            let start = Instant::now();
+            let status =
+                ptrace::cont_wait_timeout(child, None, timeout).unwrap();
+            assert_eq!(status, None);
+            assert!(start.elapsed() >= timeout);
+
+            kill(child, Signal::SIGKILL).unwrap();
+            assert!(matches!(
+                waitpid(child, None),
+                Ok(WaitStatus::Signaled(_, Signal::SIGKILL, _))
+            ));
+        }
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_ptrace_cont_wait_timeout_exit() {
+    use nix::sys::ptrace;
+    use nix::sys::signal::{raise, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::fork;
+    use nix::unistd::ForkResult::*;
+    use std::time::{Duration, Instant};
+
+    require_capability!("test_ptrace_cont_wait_timeout_exit", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            ptrace::traceme().unwrap();
+            raise(Signal::SIGSTOP).unwrap();
+            unsafe { libc::_exit(3) }
+        }
+        Parent { child } => {
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGSTOP))
+            );
+
+            // The exit is reported long before the timeout
+            let timeout = Duration::from_secs(10);
+            let start = Instant::now();
+            assert_eq!(
+                ptrace::cont_wait_timeout(child, None, timeout),
+                Ok(Some(WaitStatus::Exited(child, 3)))
+            );
+            assert!(start.elapsed() < timeout);
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_ptrace_cont_all() {
+    use nix::sys::ptrace;
+    use nix::sys::signal::{kill, Signal};
+    use nix::sys::wait::{waitpid, WaitPidFlag, WaitStatus};
+    use nix::unistd::ForkResult::*;
+    use nix::unistd::{fork, Pid};
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    // Returns the state letter from /proc/<pid>/task/<tid>/stat
+    fn task_state(pid: Pid, tid: Pid) -> char {
+        let stat =
+            std::fs::read_to_string(format!("/proc/{pid}/task/{tid}/stat"))
+                .unwrap();
+        let after_comm = &stat[stat.rfind(')').unwrap() + 2..];
+        after_comm.chars().next().unwrap()
+    }
+
+    require_capability!("test_ptrace_cont_all", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            std::thread::spawn(|| loop {
+                sleep(Duration::from_millis(10));
+            });
+            loop {
+                sleep(Duration::from_millis(10));
+            }
+        }
+        Parent { child } => {
+            // Wait for the second thread to show up
+            let task_dir = format!("/proc/{child}/task");
+            let tids = loop {
+                let tids: Vec<Pid> = std::fs::read_dir(&task_dir)
+                    .unwrap()
+                    .map(|e| {
+                        let name = e.unwrap().file_name();
+                        Pid::from_raw(name.to_str().unwrap().parse().unwrap())
+                    })
+                    .collect();
+                if tids.len() == 2 {
+                    break tids;
+                }
+                sleep(Duration::from_millis(10));
+            };
+
+            for &tid in &tids {
+                ptrace::seize(tid, ptrace::Options::empty()).unwrap();
+                ptrace::interrupt(tid).unwrap();
+                assert!(matches!(
+                    waitpid(tid, Some(WaitPidFlag::__WALL)),
+                    Ok(WaitStatus::PtraceEvent(_, Signal::SIGTRAP, _))
+                ));
+                assert_eq!(task_state(child, tid), 't');
+            }
+
+            ptrace::cont_all(child, None).unwrap();
+            for &tid in &tids {
+                assert_ne!(task_state(child, tid), 't');
+            }
+
+            kill(child, Signal::SIGKILL).unwrap();
+            assert!(matches!(
+                waitpid(child, None),
+                Ok(WaitStatus::Signaled(_, Signal::SIGKILL, _))
+            ));
+        }
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_ptrace_ctx_errors() {
+    use nix::sys::ptrace::{Request, TracerError};
+
+    require_capability!("test_ptrace_ctx_errors", CAP_SYS_PTRACE);
+
+    let err = ptrace::attach_ctx(getpid()).unwrap_err();
+    if err.errno() == Errno::ENOSYS {
+        return;
+    }
+    assert_eq!(err.request(), Request::PTRACE_ATTACH);
+    assert!(matches!(
+        err,
+        TracerError::PermissionDenied { .. }
+            | TracerError::InvalidArgument { .. }
+    ));
+
+    // We aren't tracing ourselves, so there is no stopped tracee to resume
+    let err = ptrace::cont_ctx(getpid(), None).unwrap_err();
+    assert_eq!(
+        err,
+        TracerError::NotStopped {
+            request: Request::PTRACE_CONT
+        }
+    );
+    assert_eq!(Errno::from(err), Errno::ESRCH);
+    assert_eq!(
+        err.to_string(),
+        "PTRACE_CONT failed: ESRCH: No such process"
+    );
+}
+
+#[cfg(all(
+    target_os = "linux",
+    target_arch = "x86_64",
+    any(target_env = "gnu", target_env = "musl")
+))]
+#[test]
+fn test_user_regs_accessors_x86_64() {
+    let mut raw: libc::user_regs_struct = unsafe { mem::zeroed() };
+    raw.rip = 0x1000;
+    raw.rsp = 0x7fff_0000;
+    raw.orig_rax = libc::SYS_getpid as u64;
+    raw.rax = -libc::ENOENT as i64 as u64;
+
+    let mut regs = ptrace::UserRegs::from(raw);
+    assert_eq!(regs.ip(), 0x1000);
+    assert_eq!(regs.sp(), 0x7fff_0000);
+    assert_eq!(regs.syscall_number(), libc::SYS_getpid);
+    assert_eq!(regs.return_value(), -libc::ENOENT as i64);
+
+    regs.set_ip(0x2000);
+    regs.set_sp(0x3000);
+    let raw = libc::user_regs_struct::from(regs);
+    assert_eq!(raw.rip, 0x2000);
+    assert_eq!(raw.rsp, 0x3000);
+}
+
+#[cfg(all(target_os = "linux", target_arch = "x86", target_env = "gnu"))]
+#[test]
+fn test_user_regs_accessors_x86() {
+    let mut raw: libc::user_regs_struct = unsafe { mem::zeroed() };
+    raw.eip = 0x1000;
+    raw.esp = 0x7fff_0000;
+    raw.orig_eax = libc::SYS_getpid;
+    raw.eax = -libc::ENOENT as libc::c_long;
+
+    let mut regs = ptrace::UserRegs::from(raw);
+    assert_eq!(regs.ip(), 0x1000);
+    assert_eq!(regs.sp(), 0x7fff_0000);
+    assert_eq!(regs.syscall_number(), libc::SYS_getpid as i64);
+    assert_eq!(regs.return_value(), -libc::ENOENT as i64);
+
+    regs.set_ip(0x2000);
+    regs.set_sp(0x3000);
+    let raw = libc::user_regs_struct::from(regs);
+    assert_eq!(raw.eip, 0x2000);
+    assert_eq!(raw.esp, 0x3000);
+}
+
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "x86"),
+    target_env = "gnu"
+))]
+#[test]
+fn test_ptrace_getregs_portable() {
+    use nix::sys::signal::kill;
+    use nix::sys::signal::Signal;
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::fork;
+    use nix::unistd::ForkResult::*;
+
+    require_capability!("test_ptrace_getregs_portable", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            ptrace::traceme().unwrap();
+            let pid = getpid();
+            kill(pid, Signal::SIGSTOP).unwrap();
+            kill(pid, Signal::SIGCONT).unwrap();
+            unsafe {
+                ::libc::_exit(0);
+            }
+        }
+
+        Parent { child } => {
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGSTOP))
+            );
+            ptrace::setoptions(child, ptrace::Options::PTRACE_O_TRACESYSGOOD)
+                .unwrap();
+
+            // kill entry
+            ptrace::syscall(child, None).unwrap();
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::PtraceSyscall(child))
+            );
+            let regs = ptrace::getregs_portable(child).unwrap();
+            assert_eq!(regs.syscall_number(), libc::SYS_kill as i64);
+            assert_ne!(regs.ip(), 0);
+            assert_ne!(regs.sp(), 0);
+
+            // kill exit
+            ptrace::syscall(child, None).unwrap();
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::PtraceSyscall(child))
+            );
+            let regs = ptrace::getregs_portable(child).unwrap();
+            assert_eq!(regs.syscall_number(), libc::SYS_kill as i64);
+            assert_eq!(regs.return_value(), 0);
+
+            ptrace::detach(child, None).unwrap();
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+        }
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_ptrace_get_exit_state() {
+    use nix::sys::ptrace;
+    use nix::sys::signal::{raise, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::fork;
+    use nix::unistd::ForkResult::*;
+
+    require_capability!("test_ptrace_get_exit_state", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            ptrace::traceme().unwrap();
+            raise(Signal::SIGSTOP).unwrap();
+            unsafe { libc::_exit(17) };
+        }
+        Parent { child } => {
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGSTOP))
+            );
+            ptrace::setoptions(child, Options::PTRACE_O_TRACEEXIT).unwrap();
+            ptrace::cont(child, None).unwrap();
+
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::PtraceEvent(
+                    child,
+                    Signal::SIGTRAP,
+                    ptrace::Event::PTRACE_EVENT_EXIT as i32
+                ))
+            );
+            let state = ptrace::get_exit_state(child).unwrap();
+            assert_eq!(state.status(), WaitStatus::Exited(child, 17));
+            #[cfg(all(
+                target_os = "linux",
+                any(target_arch = "x86_64", target_arch = "x86"),
+                target_env = "gnu"
+            ))]
+            assert_eq!(
+                state.regs().syscall_number(),
+                libc::SYS_exit_group as i64
+            );
+
+            ptrace::cont(child, None).unwrap();
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 17)));
+        }
+    }
+}
+
+#[cfg(all(target_os = "linux", target_arch = "x86_64", target_env = "gnu"))]
+#[test]
+fn test_ptrace_read_user_area() {
+    use nix::sys::signal::{kill, raise, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::fork;
+    use nix::unistd::ForkResult::*;
+
+    require_capability!("test_ptrace_read_user_area", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            ptrace::traceme().unwrap();
+            raise(Signal::SIGSTOP).unwrap();
+            unsafe { libc::_exit(0) };
+        }
+        Parent { child } => {
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGSTOP))
+            );
+
+            let area = ptrace::read_user_area(child).unwrap();
+            assert_eq!(
+                area.len() * mem::size_of::<libc::c_long>(),
+                mem::size_of::<libc::user>()
+            );
+
+            // The area starts with the general purpose registers
+            let regs = ptrace::getregs(child).unwrap();
+            let rip = offset_of!(libc::user_regs_struct, rip)
+                / mem::size_of::<libc::c_long>();
+            assert_eq!(area[rip] as u64, regs.rip);
+
+            kill(child, Signal::SIGKILL).unwrap();
+            assert!(matches!(
+                waitpid(child, None),
+                Ok(WaitStatus::Signaled(_, Signal::SIGKILL, _))
+            ));
+        }
+    }
+}
+
+#[cfg(all(target_os = "linux", target_arch = "x86_64", target_env = "gnu"))]
+#[test]
+fn test_ptrace_get_fs_base() {
+    use nix::sys::signal::{kill, raise, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::fork;
+    use nix::unistd::ForkResult::*;
+
+    require_capability!("test_ptrace_get_fs_base", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            ptrace::traceme().unwrap();
+            raise(Signal::SIGSTOP).unwrap();
+            unsafe { libc::_exit(0) };
+        }
+        Parent { child } => {
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGSTOP))
+            );
+
+            // With glibc the FS base points to the thread's descriptor, and
+            // the child's only thread is a copy of the one that forked it.
+            let fs_base = ptrace::get_fs_base(child).unwrap();
+            assert_ne!(fs_base, 0);
+            assert_eq!(fs_base, unsafe { libc::pthread_self() } as u64);
+            ptrace::get_gs_base(child).unwrap();
+
+            kill(child, Signal::SIGKILL).unwrap();
+            assert!(matches!(
+                waitpid(child, None),
+                Ok(WaitStatus::Signaled(_, Signal::SIGKILL, _))
+            ));
+        }
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_ptrace_typestate_current_options() {
+    use nix::sys::ptrace::{StoppedTracee, TraceeStatus};
+    use nix::sys::signal::{kill, raise, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::fork;
+    use nix::unistd::ForkResult::*;
+
+    require_capability!(
+        "test_ptrace_typestate_current_options",
+        CAP_SYS_PTRACE
+    );
+
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            ptrace::traceme().unwrap();
+            raise(Signal::SIGSTOP).unwrap();
+            unsafe { libc::_exit(0) };
+        }
+        Parent { child } => {
+            let status = waitpid(child, None).unwrap();
+            let mut tracee = StoppedTracee::assume_stopped(child, status);
+            assert_eq!(tracee.current_options(), Options::empty());
+
+            let options = Options::PTRACE_O_TRACESYSGOOD;
+            tracee.set_options(options).unwrap();
+            assert_eq!(tracee.current_options(), options);
+
+            // The options are remembered across resuming and stopping, and
+            // tell how to interpret the stop
+            let running = tracee.syscall(None).unwrap();
+            assert_eq!(running.current_options(), options);
+            let tracee = match running.wait().unwrap() {
+                TraceeStatus::Stopped(tracee) => tracee,
+                other => panic!("unexpected {:?}", other),
+            };
+            assert_eq!(tracee.current_options(), options);
+            assert_eq!(tracee.status(), WaitStatus::PtraceSyscall(child));
+
+            kill(child, Signal::SIGKILL).unwrap();
+            let _ = waitpid(child, None);
+        }
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_ptrace_attach_with_options() {
+    use nix::sys::ptrace::{StoppedTracee, TraceeStatus};
+    use nix::sys::signal::{kill, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::fork;
+    use nix::unistd::ForkResult::*;
+
+    require_capability!("test_ptrace_attach_with_options", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => loop {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        },
+        Parent { child } => {
+            let options = Options::PTRACE_O_TRACESYSGOOD;
+            let tracee =
+                StoppedTracee::attach_with_options(child, options).unwrap();
+            assert_eq!(
+                tracee.status(),
+                WaitStatus::Stopped(child, Signal::SIGSTOP)
+            );
+            assert_eq!(tracee.current_options(), options);
+
+            // With TRACESYSGOOD active, syscall stops are reported as
+            // SIGTRAP | 0x80, which waitpid decodes as PtraceSyscall
+            let tracee = match tracee.syscall(None).unwrap().wait().unwrap() {
+                TraceeStatus::Stopped(tracee) => tracee,
+                other => panic!("unexpected {:?}", other),
+            };
+            assert_eq!(tracee.status(), WaitStatus::PtraceSyscall(child));
+            let siginfo = tracee.siginfo().unwrap();
+            assert_eq!(siginfo.si_code, libc::SIGTRAP | 0x80);
+
+            kill(child, Signal::SIGKILL).unwrap();
+            let _ = waitpid(child, None);
+        }
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_ptrace_typestate() {
+    use nix::errno::Errno;
+    use nix::sys::ptrace::{StoppedTracee, TraceeStatus};
+    use nix::sys::signal::{raise, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::fork;
+    use nix::unistd::ForkResult::*;
+
+    require_capability!("test_ptrace_typestate", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            ptrace::traceme().unwrap();
+            raise(Signal::SIGSTOP).unwrap();
+            raise(Signal::SIGUSR1).unwrap();
+            unsafe { libc::_exit(0) };
+        }
+        Parent { child } => {
+            let status = waitpid(child, None).unwrap();
+            assert_eq!(status, WaitStatus::Stopped(child, Signal::SIGSTOP));
+            let tracee = StoppedTracee::assume_stopped(child, status);
+            assert_eq!(tracee.pid(), child);
+            assert_eq!(
+                tracee.siginfo().unwrap().si_signo,
+                Signal::SIGSTOP as libc::c_int
+            );
+
+            // The tracee stops again at the SIGUSR1 delivery
+            let tracee = match tracee.cont(None).unwrap().wait().unwrap() {
+                TraceeStatus::Stopped(tracee) => tracee,
+                other => panic!("unexpected {:?}", other),
+            };
+            assert_eq!(
+                tracee.status(),
+                WaitStatus::Stopped(child, Signal::SIGUSR1)
+            );
+
+            // Suppress SIGUSR1 and let the tracee exit
+            match tracee.cont(None).unwrap().wait().unwrap() {
+                TraceeStatus::Gone(status) => {
+                    assert_eq!(status, WaitStatus::Exited(child, 0))
+                }
+                other => panic!("unexpected {:?}", other),
+            }
+
+            // A failed resume hands the tracee back
+            let stale = StoppedTracee::assume_stopped(
+                child,
+                WaitStatus::Stopped(child, Signal::SIGUSR1),
+            );
+            let (stale, err) = stale.cont(None).unwrap_err();
+            assert_eq!(err, Errno::ESRCH);
+            assert_eq!(stale.pid(), child);
+        }
+    }
+}
+
+#[cfg(all(
+    target_os = "linux",
+    target_arch = "x86_64",
+    any(target_env = "gnu", target_env = "musl")
+))]
+#[test]
+fn test_ptrace_step_over_breakpoint() {
+    use nix::sys::signal::{raise, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::fork;
+    use nix::unistd::ForkResult::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    #[inline(never)]
+    extern "C" fn target() {
+        COUNTER.fetch_add(1, Ordering::SeqCst);
+    }
+
+    require_capability!("test_ptrace_step_over_breakpoint", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            ptrace::traceme().unwrap();
+            raise(Signal::SIGSTOP).unwrap();
+            for _ in 0..2 {
+                target();
+            }
+            let code = COUNTER.load(Ordering::SeqCst) as i32;
+            unsafe { libc::_exit(code) };
+        }
+        Parent { child } => {
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGSTOP))
+            );
+
+            // The child has the same layout as us
+            let addr = target as extern "C" fn() as ptrace::AddressType;
+            let bp = ptrace::Breakpoint::insert(child, addr).unwrap();
+            ptrace::cont(child, None).unwrap();
+
+            for _ in 0..2 {
+                assert_eq!(
+                    waitpid(child, None),
+                    Ok(WaitStatus::Stopped(child, Signal::SIGTRAP))
+                );
+                let regs = ptrace::getregs_portable(child).unwrap();
+                assert_eq!(regs.ip(), addr as u64 + 1);
+                ptrace::step_over_breakpoint(child, &bp).unwrap();
+            }
+
+            // Both calls ran the original instructions
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 2)));
+        }
+    }
+}
+
+#[cfg(all(
+    target_os = "linux",
+    target_arch = "x86_64",
+    any(target_env = "gnu", target_env = "musl")
+))]
+#[test]
+fn test_ptrace_is_signal_trampoline() {
+    use nix::sys::signal::*;
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::fork;
+    use nix::unistd::ForkResult::*;
+
+    extern "C" fn handler(_: libc::c_int) {}
+
+    require_capability!("test_ptrace_is_signal_trampoline", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            let sa = SigAction::new(
+                SigHandler::Handler(handler),
+                SaFlags::empty(),
+                SigSet::empty(),
+            );
+            unsafe { sigaction(Signal::SIGUSR1, &sa) }.unwrap();
+            ptrace::traceme().unwrap();
+            raise(Signal::SIGUSR1).unwrap();
+            unsafe { libc::_exit(0) };
+        }
+        Parent { child } => {
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGUSR1))
+            );
+            // Stepping into the handler stops at its first instruction
+            ptrace::step(child, Signal::SIGUSR1).unwrap();
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGTRAP))
+            );
+            let regs = ptrace::getregs_portable(child).unwrap();
+            assert_eq!(regs.ip(), handler as extern "C" fn(libc::c_int) as u64);
+
+            // The handler returns to the trampoline
+            let ret = ptrace::read(child, regs.sp() as ptrace::AddressType)
+                .unwrap() as u64;
+            assert_eq!(ptrace::is_signal_trampoline(child, ret), Ok(true));
+            // Also with the pc on the syscall instruction
+            assert_eq!(ptrace::is_signal_trampoline(child, ret + 7), Ok(true));
+            assert_eq!(
+                ptrace::is_signal_trampoline(child, regs.ip()),
+                Ok(false)
+            );
+            assert_eq!(
+                ptrace::is_signal_trampoline(child, 0),
+                Err(nix::errno::Errno::EFAULT)
+            );
+
+            ptrace::cont(child, None).unwrap();
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+        }
+    }
+}
+
+#[cfg(all(
+    target_os = "linux",
+    target_arch = "x86_64",
+    any(target_env = "gnu", target_env = "musl")
+))]
+#[test]
+fn test_ptrace_run_until() {
+    use nix::sys::signal::{raise, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::fork;
+    use nix::unistd::ForkResult::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    #[inline(never)]
+    extern "C" fn target() {
+        COUNTER.fetch_add(1, Ordering::SeqCst);
+    }
+
+    require_capability!("test_ptrace_run_until", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            ptrace::traceme().unwrap();
+            raise(Signal::SIGSTOP).unwrap();
+            target();
+            let code = COUNTER.load(Ordering::SeqCst) as i32;
+            unsafe { libc::_exit(code) };
+        }
+        Parent { child } => {
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGSTOP))
+            );
+
+            // The child has the same layout as us
+            let addr = target as extern "C" fn() as ptrace::AddressType;
+            assert_eq!(
+                ptrace::run_until(child, addr),
+                Ok(WaitStatus::Stopped(child, Signal::SIGTRAP))
+            );
+            let regs = ptrace::getregs_portable(child).unwrap();
+            assert_eq!(regs.ip(), addr as u64);
+            // target hasn't run yet
+            let counter = &COUNTER as *const AtomicU32 as ptrace::AddressType;
+            let word = ptrace::read(child, counter).unwrap();
+            assert_eq!(word as u32, 0);
+
+            // The original instruction is back in place
+            ptrace::cont(child, None).unwrap();
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 1)));
+        }
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_ptrace_wait_for_event() {
+    use nix::sys::ptrace;
+    use nix::sys::signal::{raise, signal, SigHandler, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::ForkResult::*;
+    use nix::unistd::{execv, fork};
+    use std::ffi::CString;
+
+    require_capability!("test_ptrace_wait_for_event", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            ptrace::traceme().unwrap();
+            raise(Signal::SIGSTOP).unwrap();
+            // The tracer sees an unrelated signal-delivery stop first
+            unsafe { signal(Signal::SIGUSR1, SigHandler::SigIgn) }.unwrap();
+            raise(Signal::SIGUSR1).unwrap();
+            let path = CString::new("/bin/true").unwrap();
+            execv(&path, &[&path]).unwrap();
+            unsafe { libc::_exit(1) };
+        }
+        Parent { child } => {
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGSTOP))
+            );
+            ptrace::setoptions(child, Options::PTRACE_O_TRACEEXEC).unwrap();
+
+            let msg =
+                ptrace::wait_for_event(child, ptrace::Event::PTRACE_EVENT_EXEC)
+                    .unwrap();
+            // The message of an exec event is the former thread ID
+            assert_eq!(msg, child.as_raw() as libc::c_long);
+
+            ptrace::cont(child, None).unwrap();
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+        }
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_ptrace_run_to_exit() {
+    use nix::sys::ptrace;
+    use nix::sys::signal::{raise, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::fork;
+    use nix::unistd::ForkResult::*;
+
+    require_capability!("test_ptrace_run_to_exit", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            ptrace::traceme().unwrap();
+            raise(Signal::SIGSTOP).unwrap();
+            unsafe {
+                libc::syscall(libc::SYS_getppid);
+                libc::syscall(libc::SYS_getppid);
+                libc::_exit(7);
+            }
+        }
+        Parent { child } => {
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGSTOP))
+            );
+            ptrace::setoptions(child, Options::PTRACE_O_TRACESYSGOOD).unwrap();
+
+            let mut syscall_stops = 0;
+            let mut last = None;
+            let status = ptrace::run_to_exit(child, |status| {
+                if let WaitStatus::PtraceSyscall(_) = status {
+                    syscall_stops += 1;
+                }
+                last = Some(*status);
+                None
+            })
+            .unwrap();
+            assert_eq!(status, WaitStatus::Exited(child, 7));
+            assert_eq!(last, Some(WaitStatus::Exited(child, 7)));
+            // At least the entry and exit of both getppid calls and the
+            // entry of exit_group, plus whatever raise does after SIGSTOP
+            assert!(syscall_stops >= 5);
+        }
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_ptrace_run_to_exit_signaled() {
+    use nix::sys::ptrace;
+    use nix::sys::signal::{raise, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::fork;
+    use nix::unistd::ForkResult::*;
+
+    require_capability!("test_ptrace_run_to_exit_signaled", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            ptrace::traceme().unwrap();
+            raise(Signal::SIGSTOP).unwrap();
+            raise(Signal::SIGTERM).unwrap();
+            unsafe { libc::_exit(128 + libc::SIGTERM) }
+        }
+        Parent { child } => {
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGSTOP))
+            );
+            ptrace::setoptions(child, Options::PTRACE_O_TRACESYSGOOD).unwrap();
+
+            // Deliver the SIGTERM, which kills the tracee rather than
+            // letting it exit with the shell's code for it
+            let status = ptrace::run_to_exit(child, |status| match status {
+                WaitStatus::Stopped(_, signal) => Some(*signal),
+                _ => None,
+            });
+            assert_eq!(
+                status,
+                Ok(WaitStatus::Signaled(child, Signal::SIGTERM, false))
+            );
+        }
+    }
+}
+
+#[cfg(all(
+    target_os = "linux",
+    target_arch = "x86_64",
+    any(target_env = "gnu", target_env = "musl")
+))]
+#[test]
+fn test_ptrace_set_syscall_number() {
+    use nix::sys::signal::{raise, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::ForkResult::*;
+    use nix::unistd::{fork, getpid};
+
+    require_capability!("test_ptrace_set_syscall_number", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            ptrace::traceme().unwrap();
+            raise(Signal::SIGSTOP).unwrap();
+            // The tracer turns this into getpid
+            let ret = unsafe {
+                libc::syscall(libc::SYS_getppid, 11, 22, 33, 44, 55, 66)
+            };
+            let ok = ret == getpid().as_raw() as libc::c_long;
+            unsafe { libc::_exit(if ok { 0 } else { 1 }) };
+        }
+        Parent { child } => {
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGSTOP))
+            );
+            ptrace::setoptions(child, Options::PTRACE_O_TRACESYSGOOD).unwrap();
+
+            // The first stop in getppid is its entry
+            loop {
+                ptrace::syscall(child, None).unwrap();
+                assert_eq!(
+                    waitpid(child, None),
+                    Ok(WaitStatus::PtraceSyscall(child))
+                );
+                let regs = ptrace::getregs_portable(child).unwrap();
+                if regs.syscall_number() == libc::SYS_getppid {
+                    assert_eq!(regs.syscall_args(), [11, 22, 33, 44, 55, 66]);
+                    break;
+                }
+            }
+            ptrace::set_syscall_number(child, libc::SYS_getpid).unwrap();
+            ptrace::set_syscall_args(child, [0; 6]).unwrap();
+            let regs = ptrace::getregs_portable(child).unwrap();
+            assert_eq!(regs.syscall_number(), libc::SYS_getpid);
+            assert_eq!(regs.syscall_args(), [0; 6]);
+
+            ptrace::cont(child, None).unwrap();
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+        }
+    }
+}
+
+#[cfg(all(
+    target_os = "linux",
+    target_arch = "x86_64",
+    any(target_env = "gnu", target_env = "musl")
+))]
+#[test]
+fn test_ptrace_skip_syscall() {
+    use nix::sys::signal::{raise, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::fork;
+    use nix::unistd::ForkResult::*;
+
+    require_capability!("test_ptrace_skip_syscall", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            ptrace::traceme().unwrap();
+            raise(Signal::SIGSTOP).unwrap();
+            let path = b"/dev/null\0";
+            let ret = unsafe {
+                libc::syscall(
+                    libc::SYS_openat,
+                    libc::AT_FDCWD,
+                    path.as_ptr(),
+                    libc::O_RDONLY,
+                )
+            };
+            let ok = ret == -1 && Errno::last() == Errno::EPERM;
+            unsafe { libc::_exit(if ok { 0 } else { 1 }) };
+        }
+        Parent { child } => {
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGSTOP))
+            );
+            ptrace::setoptions(child, Options::PTRACE_O_TRACESYSGOOD).unwrap();
+
+            loop {
+                ptrace::syscall(child, None).unwrap();
+                assert_eq!(
+                    waitpid(child, None),
+                    Ok(WaitStatus::PtraceSyscall(child))
+                );
+                let regs = ptrace::getregs_portable(child).unwrap();
+                if regs.syscall_number() == libc::SYS_openat {
+                    break;
+                }
+            }
+            ptrace::skip_syscall(child, -libc::EPERM as i64).unwrap();
+            // Left at the exit stop, with the faked result
+            let regs = ptrace::getregs_portable(child).unwrap();
+            assert_eq!(regs.return_value(), -libc::EPERM as i64);
+
+            ptrace::cont(child, None).unwrap();
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+        }
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_wait_status_ptrace_event() {
+    use nix::sys::ptrace::{self, Event};
+    use nix::sys::signal::{raise, Signal};
+    use nix::sys::wait::{waitpid, WaitPidFlag, WaitStatus};
+    use nix::unistd::ForkResult::*;
+    use nix::unistd::{execv, fork, Pid};
+    use std::ffi::CString;
+
+    require_capability!("test_wait_status_ptrace_event", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    let path = CString::new("/bin/true").unwrap();
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            ptrace::traceme().unwrap();
+            raise(Signal::SIGSTOP).unwrap();
+            if let Ok(Child) = unsafe { fork() } {
+                unsafe { libc::_exit(0) };
+            }
+            execv(&path, &[&path]).unwrap();
+            unsafe { libc::_exit(1) };
+        }
+        Parent { child } => {
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGSTOP))
+            );
+            assert_eq!(
+                WaitStatus::Stopped(child, Signal::SIGSTOP).ptrace_event(),
+                None
+            );
+            ptrace::setoptions(
+                child,
+                Options::PTRACE_O_TRACEFORK
+                    | Options::PTRACE_O_TRACEEXEC
+                    | Options::PTRACE_O_TRACEEXIT,
+            )
+            .unwrap();
+
+            // Skip the SIGCHLD stop caused by the grandchild exiting
+            let next_event = || {
+                let mut sig = None;
+                loop {
+                    ptrace::cont(child, sig).unwrap();
+                    match waitpid(child, None).unwrap() {
+                        WaitStatus::Stopped(_, Signal::SIGCHLD) => {
+                            sig = Some(Signal::SIGCHLD)
+                        }
+                        status => return status,
+                    }
+                }
+            };
+
+            let status = next_event();
+            assert_eq!(
+                status.ptrace_event(),
+                Some((child, Signal::SIGTRAP, Event::PTRACE_EVENT_FORK))
+            );
+            // The grandchild is traced too; let it go
+            let grandchild =
+                Pid::from_raw(ptrace::getevent(child).unwrap() as libc::pid_t);
+            assert_eq!(
+                waitpid(grandchild, Some(WaitPidFlag::__WALL)),
+                Ok(WaitStatus::Stopped(grandchild, Signal::SIGSTOP))
+            );
+            ptrace::detach(grandchild, None).unwrap();
+
+            let status = next_event();
+            assert_eq!(
+                Event::from_wait_status(&status),
+                Some(Event::PTRACE_EVENT_EXEC)
+            );
+
+            let status = next_event();
+            assert_eq!(
+                status.ptrace_event(),
+                Some((child, Signal::SIGTRAP, Event::PTRACE_EVENT_EXIT))
+            );
+
+            assert_eq!(next_event(), WaitStatus::Exited(child, 0));
+        }
+    }
+}
+
+#[cfg(all(
+    target_os = "linux",
+    any(
+        all(
+            target_arch = "x86_64",
+            any(target_env = "gnu", target_env = "musl")
+        ),
+        all(target_arch = "x86", target_env = "gnu")
+    )
+))]
+#[test]
+fn test_ptrace_read_stack() {
+    use nix::sys::ptrace;
+    use nix::sys::signal::{kill, raise, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::fork;
+    use nix::unistd::ForkResult::*;
+
+    require_capability!("test_ptrace_read_stack", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            ptrace::traceme().unwrap();
+            raise(Signal::SIGSTOP).unwrap();
+            unsafe { libc::_exit(0) };
+        }
+        Parent { child } => {
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGSTOP))
+            );
+            let sp = ptrace::getregs_portable(child).unwrap().sp();
+
+            let stack = ptrace::read_stack(child, sp, 8).unwrap();
+            assert_eq!(stack.len(), 8);
+            let word = mem::size_of::<libc::c_long>() as u64;
+            for (i, w) in stack.iter().enumerate() {
+                let addr = (sp + i as u64 * word) as ptrace::AddressType;
+                let expected = ptrace::read(child, addr).unwrap();
+                assert_eq!(*w as libc::c_long, expected);
+            }
+            assert_eq!(ptrace::read_stack(child, sp, 0), Ok(vec![]));
+            // Nothing is mapped at the bottom of the address space
+            assert_eq!(ptrace::read_stack(child, 0, 1), Err(Errno::EFAULT));
+
+            kill(child, Signal::SIGKILL).unwrap();
+            waitpid(child, None).unwrap();
+        }
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_ptrace_read_struct() {
+    use nix::sys::signal::{kill, raise, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::fork;
+    use nix::unistd::ForkResult::*;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    struct Record {
+        id: u32,
+        flags: u16,
+        kind: u16,
+        offset: i64,
+    }
+
+    require_capability!("test_ptrace_read_struct", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    // The child's copy lives at the same address
+    let mut record = Record {
+        id: 0,
+        flags: 0,
+        kind: 0,
+        offset: 0,
+    };
+    let addr = &mut record as *mut Record;
+    let expected = Record {
+        id: 0xdead_beef,
+        flags: 0x1234,
+        kind: 7,
+        offset: -42,
+    };
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            unsafe { std::ptr::write_volatile(addr, expected) };
+            ptrace::traceme().unwrap();
+            raise(Signal::SIGSTOP).unwrap();
+            unsafe { libc::_exit(0) };
+        }
+        Parent { child } => {
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGSTOP))
+            );
+
+            let read: Record =
+                unsafe { ptrace::read_struct(child, addr as _) }.unwrap();
+            assert_eq!(read, expected);
+            // Four bytes at an odd offset
+            let bytes: [u8; 16] = unsafe { mem::transmute(expected) };
+            let unaligned: [u8; 4] = unsafe {
+                ptrace::read_struct(child, (addr as *mut u8).add(5) as _)
+            }
+            .unwrap();
+            assert_eq!(unaligned, bytes[5..9]);
+
+            kill(child, Signal::SIGKILL).unwrap();
+            assert!(matches!(
+                waitpid(child, None),
+                Ok(WaitStatus::Signaled(_, Signal::SIGKILL, _))
+            ));
+        }
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_ptrace_write_sub_word() {
+    use nix::sys::signal::{kill, raise, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::fork;
+    use nix::unistd::ForkResult::*;
+
+    #[repr(C, align(8))]
+    #[derive(Clone, Copy)]
+    struct Buf([u8; 16]);
+
+    require_capability!("test_ptrace_write_sub_word", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    // The child's copy lives at the same address
+    let mut buf = Buf([0; 16]);
+    let addr = &mut buf as *mut Buf as *mut u8;
+    let pattern = Buf(*b"abcdefghijklmnop");
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            unsafe { std::ptr::write_volatile(addr as *mut Buf, pattern) };
+            ptrace::traceme().unwrap();
+            raise(Signal::SIGSTOP).unwrap();
+            unsafe { libc::_exit(0) };
+        }
+        Parent { child } => {
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGSTOP))
+            );
+            let at =
+                |off: usize| unsafe { addr.add(off) } as ptrace::AddressType;
+
+            let mut expected = pattern.0;
+            ptrace::write_u8(child, at(3), b'X').unwrap();
+            expected[3] = b'X';
+            // Straddles a word boundary on both 32- and 64-bit
+            ptrace::write_u16(child, at(7), 0x5a5a).unwrap();
+            expected[7..9].copy_from_slice(&0x5a5au16.to_ne_bytes());
+            ptrace::write_u32(child, at(10), 0x0102_0304).unwrap();
+            expected[10..14].copy_from_slice(&0x0102_0304u32.to_ne_bytes());
+
+            let read: Buf =
+                unsafe { ptrace::read_struct(child, at(0)) }.unwrap();
+            assert_eq!(read.0, expected);
+            assert_eq!(ptrace::read_u8(child, at(2)), Ok(b'c'));
+            assert_eq!(ptrace::read_u8(child, at(3)), Ok(b'X'));
+            assert_eq!(ptrace::read_u16(child, at(7)), Ok(0x5a5a));
+            assert_eq!(ptrace::read_u32(child, at(10)), Ok(0x0102_0304));
+
+            kill(child, Signal::SIGKILL).unwrap();
+            assert!(matches!(
+                waitpid(child, None),
+                Ok(WaitStatus::Signaled(_, Signal::SIGKILL, _))
+            ));
+        }
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_ptrace_tracee_is_32bit_native() {
+    assert_eq!(
+        ptrace::tracee_is_32bit(getpid()),
+        Ok(cfg!(target_pointer_width = "32"))
+    );
+}
+
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+#[test]
+fn test_ptrace_tracee_is_32bit_compat() {
+    use nix::sys::signal::Signal;
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::ForkResult::*;
+    use nix::unistd::{execv, fork};
+    use std::ffi::CString;
+    use std::os::unix::fs::PermissionsExt;
+
+    // A static i386 program that calls exit(7)
+    const ELF32_EXIT: [u8; 96] = [
+        0x7f, 0x45, 0x4c, 0x46, 0x01, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00,
+        0x54, 0x80, 0x04, 0x08, 0x34, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x34, 0x00, 0x20, 0x00, 0x01, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x80, 0x04, 0x08, 0x00, 0x80, 0x04, 0x08, 0x60, 0x00, 0x00, 0x00,
+        0x60, 0x00, 0x00, 0x00, 0x05, 0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x00,
+        0xb8, 0x01, 0x00, 0x00, 0x00, 0xbb, 0x07, 0x00, 0x00, 0x00, 0xcd, 0x80,
+    ];
+
+    require_capability!("test_ptrace_tracee_is_32bit_compat", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    // Write the program while holding the fork lock, so that no child
+    // inherits a writable descriptor to it and makes exec fail with ETXTBSY
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("elf32");
+    std::fs::write(&path, ELF32_EXIT).unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))
+        .unwrap();
+    let path = CString::new(path.to_str().unwrap()).unwrap();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            ptrace::traceme().unwrap();
+            let _ = execv(&path, &[&path]);
+            unsafe { libc::_exit(100) };
+        }
+        Parent { child } => match waitpid(child, None).unwrap() {
+            WaitStatus::Exited(_, 100) => {
+                skip!("test_ptrace_tracee_is_32bit_compat requires IA-32 emulation. Skipping test.");
+            }
+            status => {
+                assert_eq!(status, WaitStatus::Stopped(child, Signal::SIGTRAP));
+                assert_eq!(ptrace::tracee_is_32bit(child), Ok(true));
+                ptrace::cont(child, None).unwrap();
+                assert_eq!(
+                    waitpid(child, None),
+                    Ok(WaitStatus::Exited(child, 7))
+                );
+            }
+        },
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_ptrace_fork_traced_sigmask() {
+    use nix::sys::signal::{SigSet, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::execv;
+    use nix::unistd::ForkResult::*;
+    use std::ffi::CString;
+
+    require_capability!("test_ptrace_fork_traced_sigmask", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    let mut mask = SigSet::empty();
+    mask.add(Signal::SIGALRM);
+
+    match unsafe { ptrace::fork_traced(Some(&mask)) }
+        .expect("Error: Fork Failed")
+    {
+        Child => {
+            let path = CString::new("/bin/true").unwrap();
+            let _ = execv(&path, &[&path]);
+            unsafe { libc::_exit(1) };
+        }
+        Parent { child } => {
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGTRAP))
+            );
+
+            // The mask is still in place after execve
+            let status =
+                std::fs::read_to_string(format!("/proc/{}/status", child))
+                    .unwrap();
+            let blocked = status
+                .lines()
+                .find_map(|l| l.strip_prefix("SigBlk:"))
+                .map(|v| u64::from_str_radix(v.trim(), 16).unwrap())
+                .unwrap();
+            assert_eq!(blocked, 1 << (Signal::SIGALRM as i32 - 1));
+
+            ptrace::cont(child, None).unwrap();
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use nix::errno::Errno;
+use nix::sys::quota::*;
+use nix::unistd::getuid;
+
+#[test]
+fn test_disk_quota_round_trip() {
+    let quota = DiskQuota {
+        blocks_hard_limit: Some(10000),
+        blocks_soft_limit: Some(8000),
+        inodes_soft_limit: Some(100),
+        inode_time_limit: Some(1_000_000),
+        ..Default::default()
+    };
+    let (dqblk, fields) = quota.to_dqblk();
+    assert_eq!(
+        fields,
+        QuotaValidFlags::QIF_BLIMITS
+            | QuotaValidFlags::QIF_ILIMITS
+            | QuotaValidFlags::QIF_ITIME
+    );
+    assert_eq!(dqblk.valid_fields(), fields);
+
+    // The unset half of the inode limits is encoded as "no limit"
+    let decoded = DiskQuota::from(&dqblk);
+    assert_eq!(
+        decoded,
+        DiskQuota {
+            inodes_hard_limit: Some(0),
+            ..quota
+        }
+    );
+}
+
+#[test]
+fn test_quotactl_get() {
+    require_capability!("test_quotactl_get", CAP_SYS_ADMIN);
+
+    // Find a block device mounted with user quotas
+    let mounts = std::fs::read_to_string("/proc/self/mounts").unwrap();
+    let dev = mounts.lines().find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let quota = fields
+            .get(3)?
+            .split(',')
+            .any(|opt| opt == "usrquota" || opt.starts_with("usrjquota"));
+        (quota && fields[0].starts_with("/dev/")).then_some(fields[0])
+    });
+    let dev = match dev {
+        Some(dev) => dev,
+        None => {
+            skip!("No file system with user quotas. Skipping test.");
+        }
+    };
+
+    let uid = getuid().as_raw() as libc::c_int;
+    let dqblk = match quotactl_get(QuotaType::USRQUOTA, dev, uid) {
+        // Mounted with quota accounting, but quotas aren't turned on
+        Err(Errno::ESRCH) => {
+            skip!("Quotas are not enabled on {}. Skipping test.", dev);
+        }
+        r => r.unwrap(),
+    };
+    let quota = DiskQuota::from(&dqblk);
+    assert!(quota.occupied_space.is_some());
+    assert!(quota.allocated_inodes.is_some());
+}
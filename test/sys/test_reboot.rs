@@ -0,0 +1,21 @@
+use nix::errno::Errno;
+use nix::sys::reboot::*;
+
+#[test]
+fn test_reboot_mode_constants() {
+    let modes = [
+        (RebootMode::RB_HALT_SYSTEM, libc::RB_HALT_SYSTEM),
+        (RebootMode::RB_KEXEC, libc::RB_KEXEC),
+        (RebootMode::RB_POWER_OFF, libc::RB_POWER_OFF),
+        (RebootMode::RB_AUTOBOOT, libc::RB_AUTOBOOT),
+        (RebootMode::RB_SW_SUSPEND, libc::RB_SW_SUSPEND),
+    ];
+    for (mode, raw) in modes {
+        assert_eq!(mode as libc::c_int, raw);
+        assert_eq!(RebootMode::try_from(raw), Ok(mode));
+    }
+    assert_eq!(
+        RebootMode::try_from(libc::RB_ENABLE_CAD),
+        Err(Errno::EINVAL)
+    );
+}
@@ -33,6 +33,32 @@ fn test_old_sigaction_flags() {
     let _flags = oact.flags();
 }
 
+#[test]
+fn test_sigaction_guard_restores() {
+    let _m = crate::SIGNAL_MTX.lock();
+
+    extern "C" fn handler(_: ::libc::c_int) {}
+    let ign =
+        SigAction::new(SigHandler::SigIgn, SaFlags::empty(), SigSet::empty());
+    let act = SigAction::new(
+        SigHandler::Handler(handler),
+        SaFlags::empty(),
+        SigSet::empty(),
+    );
+    let orig = unsafe { sigaction(SIGUSR2, &ign) }.unwrap();
+
+    {
+        let guard = unsafe { SigActionGuard::install(SIGUSR2, &act) }.unwrap();
+        assert_eq!(guard.signal(), SIGUSR2);
+        assert_eq!(guard.old_action().handler(), SigHandler::SigIgn);
+        let cur = unsafe { sigaction(SIGUSR2, &act) }.unwrap();
+        assert_eq!(cur.handler(), SigHandler::Handler(handler));
+    }
+
+    let cur = unsafe { sigaction(SIGUSR2, &orig) }.unwrap();
+    assert_eq!(cur.handler(), SigHandler::SigIgn);
+}
+
 #[test]
 fn test_sigprocmask_noop() {
     sigprocmask(SigmaskHow::SIG_BLOCK, None, None)
@@ -143,3 +169,61 @@ fn test_signal() {
     // Restore default signal handler
     unsafe { signal(Signal::SIGINT, SigHandler::SigDfl) }.unwrap();
 }
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn test_tgkill() {
+    use nix::sys::time::TimeSpec;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    let _m = crate::SIGNAL_MTX.lock();
+
+    let mut set = SigSet::empty();
+    set.add(Signal::SIGUSR2);
+
+    let spawn = move || {
+        let (tid_tx, tid_rx) = mpsc::channel();
+        let (go_tx, go_rx) = mpsc::channel::<Option<TimeSpec>>();
+        let handle = thread::spawn(move || {
+            set.thread_block().unwrap();
+            tid_tx.send(gettid()).unwrap();
+            let timeout = go_rx.recv().unwrap();
+            sigtimedwait(&set, timeout).map(|si| si.si_signo)
+        });
+        (tid_rx.recv().unwrap(), go_tx, handle)
+    };
+    let (target, target_go, target_handle) = spawn();
+    let (bystander, bystander_go, bystander_handle) = spawn();
+    assert_ne!(target, bystander);
+
+    tgkill(getpid(), target, Signal::SIGUSR2).unwrap();
+
+    target_go
+        .send(Some(TimeSpec::from(Duration::from_secs(5))))
+        .unwrap();
+    assert_eq!(
+        target_handle.join().unwrap(),
+        Ok(Signal::SIGUSR2 as libc::c_int)
+    );
+
+    bystander_go
+        .send(Some(TimeSpec::from(Duration::ZERO)))
+        .unwrap();
+    assert_eq!(bystander_handle.join().unwrap(), Err(Errno::EAGAIN));
+
+    // A thread outside of the given thread group is not found.  Any pid
+    // other than ours will do, such as that of a reaped child.
+    let other = {
+        let _f = crate::FORK_MTX.lock();
+        match unsafe { fork() }.unwrap() {
+            ForkResult::Child => unsafe { libc::_exit(0) },
+            ForkResult::Parent { child } => {
+                nix::sys::wait::waitpid(child, None).unwrap();
+                child
+            }
+        }
+    };
+    assert_eq!(tgkill(other, gettid(), None), Err(Errno::ESRCH));
+}
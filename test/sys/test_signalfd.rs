@@ -25,3 +25,113 @@ fn test_signalfd() {
     let signo = Signal::try_from(res.ssi_signo as i32).unwrap();
     assert_eq!(signo, signal::SIGUSR1);
 }
+
+#[test]
+fn test_signalfd_typed() {
+    use nix::sys::signal::{self, SigSet, Signal};
+    use nix::sys::signalfd::{SfdFlags, SignalFd};
+    use nix::unistd::{getpid, gettid, Uid};
+
+    let _m = crate::SIGNAL_MTX.lock();
+
+    let mut mask = SigSet::empty();
+    mask.add(signal::SIGUSR1);
+    mask.thread_block().unwrap();
+
+    let mut fd = SignalFd::with_flags(&mask, SfdFlags::SFD_NONBLOCK).unwrap();
+    assert!(fd.read_signal_typed().unwrap().is_none());
+
+    // Queue the signal for this thread, recording this process as the sender
+    signal::tgkill(getpid(), gettid(), Signal::SIGUSR1).unwrap();
+
+    let info = fd.read_signal_typed().unwrap().unwrap();
+    assert_eq!(info.signal(), Some(Signal::SIGUSR1));
+    assert_eq!(info.signo(), libc::SIGUSR1);
+    assert_eq!(info.sender_pid(), getpid());
+    assert_eq!(info.sender_uid(), Uid::current());
+    assert_eq!(info.child_status(), None);
+}
+
+#[test]
+fn test_signalfd_typed_realtime() {
+    use nix::sys::signal::SigSet;
+    use nix::sys::signalfd::{SfdFlags, SignalFd};
+    use std::mem::MaybeUninit;
+
+    let _m = crate::SIGNAL_MTX.lock();
+
+    // Real-time signals can only be put in the mask through a raw sigset_t
+    let rt = libc::SIGRTMIN();
+    let mask = unsafe {
+        let mut raw = MaybeUninit::<libc::sigset_t>::uninit();
+        libc::sigemptyset(raw.as_mut_ptr());
+        libc::sigaddset(raw.as_mut_ptr(), rt);
+        SigSet::from_sigset_t_unchecked(raw.assume_init())
+    };
+    mask.thread_block().unwrap();
+
+    let mut fd = SignalFd::with_flags(&mask, SfdFlags::SFD_NONBLOCK).unwrap();
+    assert_eq!(unsafe { libc::raise(rt) }, 0);
+
+    let info = fd.read_signal_typed().unwrap().unwrap();
+    assert_eq!(info.signal(), None);
+    assert_eq!(info.signo(), rt);
+
+    mask.thread_unblock().unwrap();
+}
+
+#[test]
+fn test_signal_epoll_reaps_children() {
+    use nix::errno::Errno;
+    use nix::sys::signal::{SigSet, Signal};
+    use nix::sys::signalfd::SignalEpoll;
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::{fork, ForkResult};
+
+    let _m = crate::SIGNAL_MTX.lock();
+    let _f = crate::FORK_MTX.lock();
+
+    let before = SigSet::thread_get_mask().unwrap();
+    let mut reactor = SignalEpoll::new().unwrap();
+    assert!(SigSet::thread_get_mask().unwrap().contains(Signal::SIGCHLD));
+
+    // A child the reactor doesn't own, whose status must be left alone
+    let unrelated = match unsafe { fork() }.unwrap() {
+        ForkResult::Child => unsafe { libc::_exit(5) },
+        ForkResult::Parent { child } => child,
+    };
+
+    let mut children = Vec::new();
+    for code in [3, 4] {
+        match unsafe { reactor.children_mut().fork() }.unwrap() {
+            ForkResult::Child => unsafe { libc::_exit(code) },
+            ForkResult::Parent { child } => children.push((child, code)),
+        }
+    }
+
+    let mut reaped = Vec::new();
+    while reaped.len() < children.len() {
+        let statuses = reactor.poll(5000).unwrap();
+        assert!(!statuses.is_empty(), "timed out waiting for children");
+        reaped.extend(statuses);
+    }
+    reaped.sort_by_key(|&(pid, _)| pid);
+    children.sort();
+    let expected: Vec<_> = children
+        .iter()
+        .map(|&(pid, code)| (pid, WaitStatus::Exited(pid, code)))
+        .collect();
+    assert_eq!(reaped, expected);
+
+    assert!(reactor.children().is_empty());
+
+    // No registered children left, so even an unbounded wait must not block
+    assert_eq!(reactor.poll(-1), Err(Errno::ECHILD));
+    assert_eq!(
+        waitpid(unrelated, None),
+        Ok(WaitStatus::Exited(unrelated, 5))
+    );
+
+    drop(reactor);
+    assert_eq!(SigSet::thread_get_mask().unwrap(), before);
+}
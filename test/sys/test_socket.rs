@@ -667,6 +667,42 @@ pub fn test_recvmsg_ebadf() {
     assert_eq!(r.err().unwrap(), Errno::EBADF);
 }
 
+#[cfg_attr(qemu, ignore)]
+#[test]
+pub fn test_send_recv_fds() {
+    use nix::sys::socket::{
+        recv_fds, send_fds, socketpair, AddressFamily, SockFlag, SockType,
+    };
+    use nix::unistd::{close, pipe, read, write};
+    use std::os::unix::io::{AsFd, AsRawFd, FromRawFd, OwnedFd};
+
+    let (fd1, fd2) = socketpair(
+        AddressFamily::Unix,
+        SockType::Stream,
+        None,
+        SockFlag::empty(),
+    )
+    .unwrap();
+    let (r, w) = pipe().unwrap();
+    let (r, w) = unsafe { (OwnedFd::from_raw_fd(r), OwnedFd::from_raw_fd(w)) };
+
+    send_fds(fd1, &[r.as_fd(), w.as_fd()]).unwrap();
+    drop(r);
+    let fds = recv_fds(fd2, 2).unwrap();
+    assert_eq!(fds.len(), 2);
+
+    // The received read end belongs to the same pipe
+    write(w.as_raw_fd(), b"world").unwrap();
+    let mut buf = [0u8; 5];
+    read(fds[0].as_raw_fd(), &mut buf).unwrap();
+    assert_eq!(&buf[..], b"world");
+
+    // Nothing left to receive once the peer is gone
+    close(fd1).unwrap();
+    assert!(recv_fds(fd2, 1).unwrap().is_empty());
+    close(fd2).unwrap();
+}
+
 // Disable the test on emulated platforms due to a bug in QEMU versions <
 // 2.12.0.  https://bugs.launchpad.net/qemu/+bug/1701808
 #[cfg_attr(qemu, ignore)]
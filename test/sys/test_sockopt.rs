@@ -70,6 +70,59 @@ pub fn test_local_peer_pid() {
     assert_eq!(pid, std::process::id() as _);
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+pub fn test_peer_credentials() {
+    use nix::sys::socket::socketpair;
+    use nix::unistd::{getpid, Gid, Uid};
+
+    let (fd1, _fd2) = socketpair(
+        AddressFamily::Unix,
+        SockType::Stream,
+        None,
+        SockFlag::empty(),
+    )
+    .unwrap();
+    let cred = getsockopt(fd1, sockopt::PeerCredentials).unwrap();
+    assert_eq!(cred.pid(), getpid().as_raw());
+    assert_eq!(Uid::from_raw(cred.uid()), Uid::current());
+    assert_eq!(Gid::from_raw(cred.gid()), Gid::current());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn test_peer_pidfd() {
+    use nix::errno::Errno;
+    use nix::sys::socket::socketpair;
+    use nix::unistd::getpid;
+    use std::os::unix::io::AsRawFd;
+
+    let (fd1, _fd2) = socketpair(
+        AddressFamily::Unix,
+        SockType::Stream,
+        None,
+        SockFlag::empty(),
+    )
+    .unwrap();
+    let pidfd = match getsockopt(fd1, sockopt::PeerPidfd) {
+        Err(Errno::ENOPROTOOPT) => skip!("SO_PEERPIDFD requires Linux 6.5"),
+        r => r.unwrap(),
+    };
+
+    // The kernel reports which process a pidfd refers to in its fdinfo
+    let fdinfo = std::fs::read_to_string(format!(
+        "/proc/self/fdinfo/{}",
+        pidfd.as_raw_fd()
+    ))
+    .unwrap();
+    let pid = fdinfo
+        .lines()
+        .find_map(|l| l.strip_prefix("Pid:"))
+        .unwrap()
+        .trim();
+    assert_eq!(pid, getpid().to_string());
+}
+
 #[cfg(target_os = "linux")]
 #[test]
 fn is_so_mark_functional() {
@@ -0,0 +1,44 @@
+use nix::errno::Errno;
+use nix::sys::statx::{statx, StatxFlags, StatxMask};
+use std::os::unix::io::AsRawFd;
+
+#[test]
+fn test_statx_size_and_btime() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let path = tempdir.path().join("file");
+    std::fs::write(&path, b"hello").unwrap();
+
+    let mask = StatxMask::STATX_BASIC_STATS | StatxMask::STATX_BTIME;
+    let stx = match statx(None, &path, StatxFlags::empty(), mask) {
+        Err(Errno::ENOSYS) => {
+            skip!("statx requires Linux 4.11 or later. Skipping test.");
+        }
+        r => r.unwrap(),
+    };
+    assert_eq!(stx.size(), Some(5));
+    assert!(stx.mask().contains(StatxMask::STATX_SIZE));
+    match stx.btime() {
+        // The file was created before it was last modified
+        Some(btime) => assert!(btime <= stx.mtime().unwrap()),
+        None => assert!(!stx.mask().contains(StatxMask::STATX_BTIME)),
+    }
+
+    // With AT_EMPTY_PATH, an empty path refers to dirfd itself
+    let file = std::fs::File::open(&path).unwrap();
+    let by_fd =
+        statx(Some(file.as_raw_fd()), "", StatxFlags::AT_EMPTY_PATH, mask)
+            .unwrap();
+    assert_eq!(by_fd.ino(), stx.ino());
+    assert_eq!(by_fd.dev(), stx.dev());
+
+    assert_eq!(
+        statx(
+            None,
+            &tempdir.path().join("missing"),
+            StatxFlags::empty(),
+            mask
+        )
+        .unwrap_err(),
+        Errno::ENOENT
+    );
+}
@@ -18,3 +18,13 @@ fn sysinfo_works() {
         info.swap_total()
     );
 }
+
+#[test]
+fn sysinfo_memory_and_uptime() {
+    let info = sysinfo().unwrap();
+
+    assert!(info.ram_total() > 0);
+    assert!(info.ram_unused() <= info.ram_total());
+    assert!(info.uptime() > std::time::Duration::ZERO);
+    assert!(info.process_count() > 0);
+}
@@ -105,3 +105,27 @@ fn test_local_flags() {
     let read = read(pty.master.as_raw_fd(), &mut buf).unwrap_err();
     assert_eq!(read, Errno::EAGAIN);
 }
+
+// Test switching a terminal to raw mode and back
+#[test]
+fn test_set_raw_mode() {
+    use nix::sys::termios::{cfmakeraw_owned, tcsetattr, SetArg};
+
+    // openpty uses ptname(3) internally
+    let _m = crate::PTSNAME_MTX.lock();
+
+    let pty = openpty(None, None).expect("openpty failed");
+    let orig = termios::set_raw_mode(&pty.slave).unwrap();
+    assert!(orig.local_flags.contains(LocalFlags::ICANON));
+
+    let raw = tcgetattr(&pty.slave).unwrap();
+    let expected = cfmakeraw_owned(&orig);
+    assert_eq!(raw.local_flags, expected.local_flags);
+    assert_eq!(raw.output_flags, expected.output_flags);
+    assert!(!raw
+        .local_flags
+        .intersects(LocalFlags::ICANON | LocalFlags::ECHO));
+
+    tcsetattr(&pty.slave, SetArg::TCSANOW, &orig).unwrap();
+    assert_eq!(tcgetattr(&pty.slave).unwrap(), orig);
+}
@@ -275,3 +275,209 @@ fn test_process_vm_readv() {
         }
     }
 }
+
+#[test]
+#[cfg(all(target_os = "linux", not(target_env = "uclibc")))]
+// qemu-user doesn't implement process_vm_readv/writev on most arches
+#[cfg_attr(qemu, ignore)]
+fn test_read_process_vm_many_iovecs() {
+    // More iovecs than a single process_vm_readv call accepts
+    const N: usize = 3000;
+
+    // A process may always read its own memory
+    let source: Vec<u8> = (0..2 * N).map(|i| i as u8).collect();
+    let remote_iov: Vec<RemoteIoVec> = (0..N)
+        .map(|i| RemoteIoVec {
+            base: source.as_ptr() as usize + 2 * i,
+            len: 1,
+        })
+        .collect();
+
+    // Scatter every other source byte into buffers of uneven sizes
+    let mut buf = vec![0u8; N];
+    let mut local_iov: Vec<IoSliceMut> =
+        buf.chunks_mut(3).map(IoSliceMut::new).collect();
+    assert!(local_iov.len() > 1024);
+
+    assert_eq!(
+        read_process_vm(getpid(), &mut local_iov, &remote_iov),
+        Ok(N)
+    );
+    let expected: Vec<u8> = (0..N).map(|i| (2 * i) as u8).collect();
+    assert_eq!(buf, expected);
+
+    // A single call rejects that many iovecs
+    let mut local_iov = [IoSliceMut::new(&mut buf)];
+    assert_eq!(
+        process_vm_readv(getpid(), &mut local_iov, &remote_iov),
+        Err(nix::errno::Errno::EINVAL)
+    );
+}
+
+#[test]
+#[cfg(all(target_os = "linux", not(target_env = "uclibc")))]
+// qemu-user doesn't implement process_vm_readv/writev on most arches
+#[cfg_attr(qemu, ignore)]
+fn test_read_process_vm_exact_guard_page() {
+    use nix::sys::mman::*;
+    use std::num::NonZeroUsize;
+    use std::os::unix::io::BorrowedFd;
+
+    let page = sysconf(SysconfVar::PAGE_SIZE).unwrap().unwrap() as usize;
+    let base = unsafe {
+        let ptr = mmap::<BorrowedFd>(
+            None,
+            NonZeroUsize::new(3 * page).unwrap(),
+            ProtFlags::PROT_READ | ProtFlags::PROT_WRITE,
+            MapFlags::MAP_PRIVATE | MapFlags::MAP_ANONYMOUS,
+            None,
+            0,
+        )
+        .unwrap();
+        std::ptr::write_bytes(ptr as *mut u8, 0xa5, 3 * page);
+        // Punch a hole in the middle
+        munmap((ptr as usize + page) as *mut _, page).unwrap();
+        ptr as usize
+    };
+
+    // An empty iovec first, to check that indices refer to remote_iov
+    let remote_iov = [
+        RemoteIoVec { base, len: 0 },
+        RemoteIoVec {
+            base,
+            len: 3 * page,
+        },
+    ];
+    let mut buf = vec![0u8; 3 * page];
+    let mut local_iov = [IoSliceMut::new(&mut buf)];
+    let err = read_process_vm_exact(getpid(), &mut local_iov, &remote_iov)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ProcessVmError::PartialTransfer {
+            bytes: page,
+            iov_index: 1,
+            iov_offset: page,
+            address: base + page,
+        }
+    );
+    assert_eq!(nix::errno::Errno::from(err), nix::errno::Errno::EFAULT);
+    assert!(buf[..page].iter().all(|&b| b == 0xa5));
+
+    // Resume past the gap
+    let remote_iov = [RemoteIoVec {
+        base: base + 2 * page,
+        len: page,
+    }];
+    let mut local_iov = [IoSliceMut::new(&mut buf[2 * page..])];
+    assert_eq!(
+        read_process_vm_exact(getpid(), &mut local_iov, &remote_iov),
+        Ok(page)
+    );
+    assert!(buf[2 * page..].iter().all(|&b| b == 0xa5));
+
+    // Starting right in the gap reads nothing
+    let remote_iov = [RemoteIoVec {
+        base: base + page,
+        len: page,
+    }];
+    let mut local_iov = [IoSliceMut::new(&mut buf)];
+    assert!(matches!(
+        read_process_vm_exact(getpid(), &mut local_iov, &remote_iov),
+        Err(ProcessVmError::PartialTransfer { bytes: 0, .. })
+    ));
+
+    unsafe {
+        munmap(base as *mut _, page).unwrap();
+        munmap((base + 2 * page) as *mut _, page).unwrap();
+    }
+}
+
+#[test]
+#[cfg(all(target_os = "linux", not(target_env = "uclibc")))]
+// qemu-user doesn't implement process_vm_readv/writev on most arches
+#[cfg_attr(qemu, ignore)]
+fn test_remote_mem() {
+    use crate::*;
+    use nix::sys::signal::*;
+    use nix::sys::wait::*;
+    use nix::unistd::ForkResult::*;
+    use std::io::{Read, Seek, SeekFrom};
+
+    #[repr(C)]
+    struct Header {
+        magic: u32,
+        len: u16,
+        flags: u16,
+        payload: [u8; 4],
+    }
+
+    require_capability!("test_remote_mem", CAP_SYS_PTRACE);
+    let _m = crate::FORK_MTX.lock();
+
+    let mut header = Header {
+        magic: 0,
+        len: 0,
+        flags: 0,
+        payload: [0; 4],
+    };
+
+    let (r, w) = pipe().unwrap();
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Parent { child } => {
+            close(w).unwrap();
+            // wait for child
+            read(r, &mut [0u8]).unwrap();
+            close(r).unwrap();
+
+            let mut mem = RemoteMem::new(child, &header as *const _ as usize);
+            let mut u32_buf = [0u8; 4];
+            let mut u16_buf = [0u8; 2];
+            let mut payload = [0u8; 4];
+
+            mem.read_exact(&mut u32_buf).unwrap();
+            let magic = u32::from_ne_bytes(u32_buf);
+            mem.read_exact(&mut u16_buf).unwrap();
+            let len = u16::from_ne_bytes(u16_buf);
+            mem.read_exact(&mut u16_buf).unwrap();
+            let flags = u16::from_ne_bytes(u16_buf);
+            mem.read_exact(&mut payload).unwrap();
+
+            // Re-read a field after seeking back
+            mem.seek(SeekFrom::Start(4)).unwrap();
+            mem.read_exact(&mut u16_buf).unwrap();
+            let len_again = u16::from_ne_bytes(u16_buf);
+            let end = mem.seek(SeekFrom::End(0));
+
+            kill(child, SIGTERM).unwrap();
+            waitpid(child, None).unwrap();
+
+            assert_eq!(magic, 0xdead_beef);
+            assert_eq!(len, 4);
+            assert_eq!(flags, 0x0102);
+            assert_eq!(payload, *b"nix!");
+            assert_eq!(len_again, 4);
+            assert!(end.is_err());
+        }
+        Child => {
+            let _ = close(r);
+            // The parent reads these behind the compiler's back
+            unsafe {
+                std::ptr::write_volatile(
+                    &mut header,
+                    Header {
+                        magic: 0xdead_beef,
+                        len: 4,
+                        flags: 0x0102,
+                        payload: *b"nix!",
+                    },
+                );
+            }
+            let _ = write(w, b"\0");
+            let _ = close(w);
+            loop {
+                pause();
+            }
+        }
+    }
+}
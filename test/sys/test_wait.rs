@@ -108,6 +108,45 @@ fn test_waitstatus_from_raw() {
     assert_eq!(WaitStatus::from_raw(pid, 0x7f7f), Err(Errno::EINVAL));
 }
 
+#[test]
+fn test_waitpid_target_process_group() {
+    let _m = crate::FORK_MTX.lock();
+
+    // A child in a process group of its own.  Both sides set it, so that it
+    // is in place whichever runs first.
+    let leader = match unsafe { fork() }.unwrap() {
+        Child => unsafe {
+            let _ = setpgid(Pid::from_raw(0), Pid::from_raw(0));
+            _exit(1)
+        },
+        Parent { child } => {
+            let _ = setpgid(child, child);
+            child
+        }
+    };
+    // And one in ours
+    let other = match unsafe { fork() }.unwrap() {
+        Child => unsafe { _exit(2) },
+        Parent { child } => child,
+    };
+
+    let group = WaitTarget::ProcessGroup(leader);
+    assert_eq!(
+        waitpid_target(group, None),
+        Ok(WaitStatus::Exited(leader, 1))
+    );
+    assert_eq!(waitpid_target(group, None), Err(Errno::ECHILD));
+    assert_eq!(
+        waitpid_target(WaitTarget::Pid(other), None),
+        Ok(WaitStatus::Exited(other, 2))
+    );
+
+    let init = WaitTarget::ProcessGroup(Pid::from_raw(1));
+    assert_eq!(waitpid_target(init, None), Err(Errno::EINVAL));
+    let zero = WaitTarget::Pid(Pid::from_raw(0));
+    assert_eq!(waitpid_target(zero, None), Err(Errno::EINVAL));
+}
+
 #[test]
 fn test_waitstatus_pid() {
     let _m = crate::FORK_MTX.lock();
@@ -140,6 +179,68 @@ fn test_waitid_pid() {
     }
 }
 
+#[test]
+#[cfg(any(
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "haiku",
+    all(target_os = "linux", not(target_env = "uclibc")),
+))]
+fn test_reap_managed() {
+    let _m = crate::FORK_MTX.lock();
+
+    let mut children = ManagedChildren::new();
+    let (rd, wr) = pipe().unwrap();
+
+    // An unmanaged child, which exits right away
+    let unmanaged = match unsafe { fork() }.unwrap() {
+        Child => unsafe { _exit(5) },
+        Parent { child } => child,
+    };
+    // A managed child, which exits once told to
+    let managed = match unsafe { children.fork() }.unwrap() {
+        Child => {
+            let mut buf = [0];
+            let _ = read(rd, &mut buf);
+            unsafe { _exit(3) }
+        }
+        Parent { child } => child,
+    };
+    close(rd).unwrap();
+    assert!(children.contains(managed));
+    assert!(!children.contains(unmanaged));
+
+    // Make sure the unmanaged child's status is pending
+    let peek = WaitPidFlag::WEXITED | WaitPidFlag::WNOWAIT;
+    waitid(Id::Pid(unmanaged), peek).unwrap();
+
+    assert_eq!(
+        children.reap_managed(WaitPidFlag::WNOHANG),
+        Ok(ManagedStatus::Managed(WaitStatus::StillAlive))
+    );
+    // The unmanaged child is reported, but not reaped
+    assert_eq!(
+        children.reap_managed(WaitPidFlag::empty()),
+        Ok(ManagedStatus::Unmanaged(WaitStatus::Exited(unmanaged, 5)))
+    );
+    assert_eq!(
+        waitpid(unmanaged, None),
+        Ok(WaitStatus::Exited(unmanaged, 5))
+    );
+
+    write(wr, b"x").unwrap();
+    assert_eq!(
+        children.reap_managed(WaitPidFlag::empty()),
+        Ok(ManagedStatus::Managed(WaitStatus::Exited(managed, 3)))
+    );
+    assert!(children.is_empty());
+    assert_eq!(
+        children.reap_managed(WaitPidFlag::empty()),
+        Err(Errno::ECHILD)
+    );
+    close(wr).unwrap();
+}
+
 #[cfg(any(target_os = "linux", target_os = "android"))]
 // FIXME: qemu-user doesn't implement ptrace on most arches
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
@@ -255,3 +356,39 @@ mod ptrace {
         }
     }
 }
+
+#[test]
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn test_wait4() {
+    const SIZE: usize = 64 * 1024 * 1024;
+
+    let _m = crate::FORK_MTX.lock();
+
+    // Allocate before forking, since allocation isn't async-signal-safe
+    let mut buf = vec![0u8; SIZE];
+
+    // Safe: Child only touches preallocated memory and calls `_exit`.
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            // Fault every page in, so that it counts towards the RSS
+            for i in (0..SIZE).step_by(4096) {
+                unsafe { std::ptr::write_volatile(&mut buf[i], 1) };
+            }
+            unsafe { _exit(12) }
+        }
+        Parent { child } => {
+            let (status, rusage) = wait4(child, None).unwrap();
+            assert_eq!(status, WaitStatus::Exited(child, 12));
+            assert!(rusage.ru_maxrss > 0);
+        }
+    }
+}
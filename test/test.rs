@@ -26,6 +26,8 @@ mod test_nix_path;
 #[cfg(target_os = "freebsd")]
 mod test_nmount;
 mod test_poll;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod test_proc;
 #[cfg(not(any(
     target_os = "redox",
     target_os = "fuchsia",
@@ -63,3 +63,29 @@ fn rewind() {
 fn ebadf() {
     assert_eq!(Dir::from_fd(-1).unwrap_err(), nix::Error::EBADF);
 }
+
+#[test]
+#[cfg(target_os = "linux")]
+fn getdents() {
+    use nix::dir::getdents;
+    use std::os::unix::io::{FromRawFd, OwnedFd};
+
+    let tmp = tempdir().unwrap();
+    File::create(tmp.path().join("foo")).unwrap();
+    std::fs::create_dir(tmp.path().join("bar")).unwrap();
+    let fd = nix::fcntl::open(tmp.path(), flags(), Mode::empty()).unwrap();
+    let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+    let mut entries: Vec<_> = getdents(&fd).map(|e| e.unwrap()).collect();
+    entries.sort_by(|a, b| a.file_name().cmp(b.file_name()));
+    let entry_names: Vec<_> = entries
+        .iter()
+        .map(|e| e.file_name().to_str().unwrap().to_owned())
+        .collect();
+    assert_eq!(&entry_names[..], &[".", "..", "bar", "foo"]);
+    assert!(&[Some(Type::Directory), None].contains(&entries[2].file_type()));
+    assert!(&[Some(Type::File), None].contains(&entries[3].file_type()));
+
+    // The descriptor's offset is now at the end of the directory
+    assert_eq!(getdents(&fd).count(), 0);
+}
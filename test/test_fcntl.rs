@@ -227,6 +227,16 @@ fn test_readlink() {
     );
 }
 
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn test_readlinkat_proc_self_exe() {
+    let dirfd = open("/proc/self", OFlag::O_DIRECTORY, Mode::empty()).unwrap();
+    let exe = readlinkat(dirfd, "exe").unwrap();
+    close(dirfd).unwrap();
+
+    assert_eq!(exe, std::env::current_exe().unwrap().into_os_string());
+}
+
 #[cfg(any(target_os = "linux", target_os = "android"))]
 mod linux_android {
     use libc::loff_t;
@@ -243,6 +253,98 @@ mod linux_android {
 
     use crate::*;
 
+    #[test]
+    fn test_name_to_handle_at() {
+        use nix::errno::Errno;
+        use nix::sys::stat::Mode;
+
+        require_capability!("test_name_to_handle_at", CAP_DAC_READ_SEARCH);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("before");
+        std::fs::write(&path, b"handle").unwrap();
+
+        let (handle, mount_id) = match name_to_handle_at(
+            None,
+            &path,
+            AtFlags::empty(),
+        ) {
+            Err(Errno::EOPNOTSUPP) => {
+                skip!(
+                    "File system doesn't support file handles. Skipping test."
+                );
+            }
+            r => r.unwrap(),
+        };
+        let (handle2, mount_id2) =
+            name_to_handle_at(None, &path, AtFlags::empty()).unwrap();
+        assert_eq!(handle, handle2);
+        assert_eq!(mount_id, mount_id2);
+
+        // The handle survives a rename
+        std::fs::rename(&path, dir.path().join("after")).unwrap();
+        let mount_fd =
+            open(dir.path(), OFlag::O_RDONLY, Mode::empty()).unwrap();
+        let fd = open_by_handle_at(mount_fd, &handle, OFlag::O_RDONLY).unwrap();
+        let mut buf = [0u8; 6];
+        assert_eq!(read(fd.as_raw_fd(), &mut buf), Ok(6));
+        assert_eq!(&buf, b"handle");
+
+        // And a persisted copy of it works just as well
+        let copy =
+            FileHandle::from_raw_parts(handle.handle_type(), handle.as_bytes());
+        open_by_handle_at(mount_fd, &copy, OFlag::O_RDONLY).unwrap();
+
+        close(mount_fd).unwrap();
+    }
+
+    #[test]
+    fn test_pipe_size() {
+        let (rd, wr) = pipe().unwrap();
+
+        let initial = get_pipe_size(rd).unwrap();
+        let new_size = set_pipe_size(wr, initial * 2).unwrap();
+        assert!(new_size >= initial * 2);
+        assert_eq!(get_pipe_size(rd).unwrap(), new_size);
+
+        close(rd).unwrap();
+        close(wr).unwrap();
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    // QEMU does not support openat2
+    #[cfg_attr(qemu, ignore)]
+    fn test_openat2() {
+        use nix::errno::Errno;
+        use nix::sys::stat::Mode;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("file"), b"abcd").unwrap();
+        std::os::unix::fs::symlink("file", dir.path().join("link")).unwrap();
+        let dirfd = open(dir.path(), OFlag::O_PATH, Mode::empty()).unwrap();
+
+        let how = OpenHow::new()
+            .flags(OFlag::O_RDONLY | OFlag::O_CLOEXEC)
+            .resolve(ResolveFlag::RESOLVE_NO_SYMLINKS);
+        let fd = match openat2(dirfd, "file", how) {
+            Err(Errno::ENOSYS) => {
+                close(dirfd).unwrap();
+                skip!("openat2 requires Linux 5.6 or later. Skipping test.");
+            }
+            r => r.unwrap(),
+        };
+        let mut buf = [0u8; 4];
+        assert_eq!(read(fd.as_raw_fd(), &mut buf), Ok(4));
+        assert_eq!(&buf, b"abcd");
+
+        assert_eq!(openat2(dirfd, "link", how).unwrap_err(), Errno::ELOOP);
+        // Without the restriction, the symlink is followed
+        openat2(dirfd, "link", OpenHow::new()).unwrap();
+
+        close(dirfd).unwrap();
+    }
+
     /// This test creates a temporary file containing the contents
     /// 'foobarbaz' and uses the `copy_file_range` call to transfer
     /// 3 bytes at offset 3 (`bar`) to another empty file at offset 0. The
@@ -279,6 +381,43 @@ mod linux_android {
         assert_eq!(from_offset, 6);
     }
 
+    /// With both offsets given, `copy_file_range` copies between explicit
+    /// positions, advances the offsets instead of the file positions, and
+    /// returns the number of bytes copied.
+    #[test]
+    // QEMU does not support copy_file_range. Skip under qemu
+    #[cfg_attr(qemu, ignore)]
+    fn test_copy_file_range_both_offsets() {
+        let mut tmp1 = tempfile().unwrap();
+        let mut tmp2 = tempfile().unwrap();
+
+        tmp1.write_all(b"0123456789").unwrap();
+        tmp2.write_all(b"abcdefghij").unwrap();
+
+        let mut off_in: loff_t = 2;
+        let mut off_out: loff_t = 4;
+        let res = copy_file_range(
+            tmp1.as_raw_fd(),
+            Some(&mut off_in),
+            tmp2.as_raw_fd(),
+            Some(&mut off_out),
+            5,
+        )
+        .unwrap();
+
+        assert_eq!(res, 5);
+        assert_eq!(off_in, 7);
+        assert_eq!(off_out, 9);
+        // The file positions are left alone
+        assert_eq!(tmp1.stream_position().unwrap(), 10);
+        assert_eq!(tmp2.stream_position().unwrap(), 10);
+
+        let mut res = String::new();
+        tmp2.rewind().unwrap();
+        tmp2.read_to_string(&mut res).unwrap();
+        assert_eq!(res, "abcd23456j");
+    }
+
     #[test]
     fn test_splice() {
         const CONTENTS: &[u8] = b"abcdef123456";
@@ -368,6 +507,41 @@ mod linux_android {
         assert_eq!(100, read(fd, &mut buf).unwrap());
     }
 
+    #[cfg(any(target_os = "linux"))]
+    #[test]
+    fn test_fallocate_punch_hole() {
+        use nix::errno::Errno;
+        use nix::sys::stat::fstat;
+
+        const LEN: usize = 1 << 20;
+
+        let mut tmp = NamedTempFile::new().unwrap();
+        tmp.write_all(&[0xa5; LEN]).unwrap();
+        tmp.as_file().sync_all().unwrap();
+
+        let fd = tmp.as_raw_fd();
+        let before = fstat(fd).unwrap();
+        let flags = FallocateFlags::FALLOC_FL_PUNCH_HOLE
+            | FallocateFlags::FALLOC_FL_KEEP_SIZE;
+        match fallocate(fd, flags, 0, LEN as libc::off_t / 2) {
+            Err(Errno::EOPNOTSUPP) => {
+                skip!(
+                    "File system doesn't support punching holes. Skipping test."
+                );
+            }
+            r => r.unwrap(),
+        }
+        let after = fstat(fd).unwrap();
+
+        assert_eq!(after.st_size, before.st_size);
+        assert!(after.st_blocks < before.st_blocks);
+
+        // The hole reads back as zeros
+        let mut buf = [0xffu8; 16];
+        assert_eq!(Ok(16), nix::sys::uio::pread(tmp.as_file(), &mut buf, 0));
+        assert_eq!(buf, [0; 16]);
+    }
+
     // The tests below are disabled for the listed targets
     // due to OFD locks not being available in the kernel/libc
     // versions used in the CI environment, probably because
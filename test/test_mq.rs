@@ -5,6 +5,7 @@ use std::str;
 use nix::errno::Errno;
 use nix::mqueue::{
     mq_attr_member_t, mq_close, mq_open, mq_receive, mq_send, mq_timedreceive,
+    mq_timedsend,
 };
 use nix::mqueue::{MQ_OFlag, MqAttr};
 use nix::sys::stat::Mode;
@@ -90,6 +91,47 @@ fn test_mq_timedreceive() {
     assert_eq!(msg_to_send, str::from_utf8(&buf[0..len]).unwrap());
 }
 
+#[test]
+fn test_mq_timedsend() {
+    const MSG_SIZE: mq_attr_member_t = 32;
+    // Room for a single message, so that the next send has to wait
+    let attr = MqAttr::new(0, 1, MSG_SIZE, 0);
+    let mq_name =
+        &CString::new(b"/a_nix_test_timedsend_queue".as_ref()).unwrap();
+
+    let oflag = MQ_OFlag::O_CREAT | MQ_OFlag::O_RDWR;
+    let mode = Mode::S_IWUSR | Mode::S_IRUSR | Mode::S_IRGRP | Mode::S_IROTH;
+    let r = mq_open(mq_name, oflag, mode, Some(&attr));
+    if let Err(Errno::ENOSYS) = r {
+        println!("message queues not supported or module not loaded?");
+        return;
+    };
+    let mqd = r.unwrap();
+    let deadline = || {
+        clock_gettime(ClockId::CLOCK_REALTIME).unwrap()
+            + TimeSpec::milliseconds(100)
+    };
+
+    mq_timedsend(&mqd, b"msg_1", 1, &deadline()).unwrap();
+    assert_eq!(
+        mq_timedsend(&mqd, b"msg_2", 1, &deadline()),
+        Err(Errno::ETIMEDOUT)
+    );
+
+    let mut buf = [0u8; 32];
+    let mut prio = 0u32;
+    let len = mq_timedreceive(&mqd, &mut buf, &mut prio, &deadline()).unwrap();
+    assert_eq!(&buf[..len], b"msg_1");
+    assert_eq!(prio, 1);
+    assert_eq!(
+        mq_timedreceive(&mqd, &mut buf, &mut prio, &deadline()),
+        Err(Errno::ETIMEDOUT)
+    );
+
+    mq_close(mqd).unwrap();
+    nix::mqueue::mq_unlink(mq_name).unwrap();
+}
+
 #[test]
 fn test_mq_getattr() {
     use nix::mqueue::mq_getattr;
@@ -189,6 +231,37 @@ fn test_mq_set_nonblocking() {
     mq_close(mqd).unwrap();
 }
 
+// FIXME: Fix failures for mips in QEMU
+#[test]
+#[cfg_attr(
+    all(qemu, any(target_arch = "mips", target_arch = "mips64")),
+    ignore
+)]
+fn test_mq_is_nonblocking() {
+    use nix::mqueue::{mq_getattr, mq_remove_nonblock, mq_set_nonblock};
+    const MSG_SIZE: mq_attr_member_t = 32;
+    let o_nonblock_bits = MQ_OFlag::O_NONBLOCK.bits() as mq_attr_member_t;
+    let initial_attr = MqAttr::new(0, 10, MSG_SIZE, 0);
+    assert!(!initial_attr.is_nonblocking());
+    assert!(MqAttr::new(o_nonblock_bits, 10, MSG_SIZE, 0).is_nonblocking());
+
+    let mq_name = &CString::new(b"/attr_test_is_nonblocking".as_ref()).unwrap();
+    let oflag = MQ_OFlag::O_CREAT | MQ_OFlag::O_WRONLY;
+    let mode = Mode::S_IWUSR | Mode::S_IRUSR | Mode::S_IRGRP | Mode::S_IROTH;
+    let r = mq_open(mq_name, oflag, mode, Some(&initial_attr));
+    if let Err(Errno::ENOSYS) = r {
+        println!("message queues not supported or module not loaded?");
+        return;
+    };
+    let mqd = r.unwrap();
+    mq_set_nonblock(&mqd).unwrap();
+    assert!(mq_getattr(&mqd).unwrap().is_nonblocking());
+    mq_remove_nonblock(&mqd).unwrap();
+    assert!(!mq_getattr(&mqd).unwrap().is_nonblocking());
+    mq_close(mqd).unwrap();
+    nix::mqueue::mq_unlink(mq_name).unwrap();
+}
+
 #[test]
 fn test_mq_unlink() {
     use nix::mqueue::mq_unlink;
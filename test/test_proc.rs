@@ -0,0 +1,149 @@
+use nix::proc::{cmdline, environ};
+use nix::unistd::getpid;
+
+#[test]
+fn test_cmdline() {
+    let args = cmdline(getpid()).unwrap();
+    assert_eq!(args, std::env::args_os().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_environ() {
+    // The environment may have been changed since, but it still has the
+    // right shape
+    for var in environ(getpid()).unwrap() {
+        assert!(var.to_str().map_or(true, |var| var.contains('=')));
+    }
+}
+
+#[cfg(all(
+    target_os = "linux",
+    target_arch = "x86_64",
+    any(target_env = "gnu", target_env = "musl")
+))]
+#[test]
+fn test_addr_to_module() {
+    use nix::proc::addr_to_module;
+    use nix::sys::ptrace;
+    use nix::sys::signal::{kill, raise, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::{fork, ForkResult};
+    use std::io::{Read, Seek, SeekFrom};
+
+    #[inline(never)]
+    extern "C" fn target() {}
+
+    require_capability!("test_addr_to_module", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        ForkResult::Child => {
+            ptrace::traceme().unwrap();
+            raise(Signal::SIGSTOP).unwrap();
+            unsafe { libc::_exit(0) };
+        }
+        ForkResult::Parent { child } => {
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGSTOP))
+            );
+
+            // The child stopped somewhere in a mapped file, most likely the
+            // C library
+            let ip = ptrace::getregs_portable(child).unwrap().ip();
+            assert!(addr_to_module(child, ip).unwrap().is_some());
+
+            // The child has the same layout as us
+            let addr = target as extern "C" fn() as u64;
+            let mapping = addr_to_module(child, addr).unwrap().unwrap();
+            assert!(mapping.start <= addr && addr < mapping.end);
+            assert_eq!(
+                mapping.path.canonicalize().unwrap(),
+                std::env::current_exe().unwrap().canonicalize().unwrap()
+            );
+
+            // The code at the offset in the file is what the child runs
+            let mut text = [0u8; 8];
+            let mut exe = std::fs::File::open(&mapping.path).unwrap();
+            exe.seek(SeekFrom::Start(mapping.file_offset)).unwrap();
+            exe.read_exact(&mut text).unwrap();
+            let word = ptrace::read(child, addr as ptrace::AddressType);
+            assert_eq!(word.unwrap().to_ne_bytes(), text);
+
+            // Nothing is mapped at address 0
+            assert_eq!(addr_to_module(child, 0), Ok(None));
+
+            kill(child, Signal::SIGKILL).unwrap();
+            let _ = waitpid(child, None);
+        }
+    }
+}
+
+#[test]
+fn test_status_self() {
+    use nix::proc::status;
+    use nix::unistd::{getegid, geteuid, getgid, getppid, getuid};
+
+    let st = status(getpid()).unwrap();
+    assert_eq!(st.tgid, getpid());
+    assert_eq!(st.ppid, Some(getppid()));
+    assert_eq!(st.uid.real, getuid());
+    assert_eq!(st.uid.effective, geteuid());
+    assert_eq!(st.gid.real, getgid());
+    assert_eq!(st.gid.effective, getegid());
+    // Effective capabilities are always permitted
+    assert_eq!(st.cap_eff.bits() & !st.cap_prm.bits(), 0);
+}
+
+#[test]
+fn test_status_tracer_pid() {
+    use nix::proc::{status, ProcState};
+    use nix::sys::ptrace;
+    use nix::sys::signal::{kill, raise, SigSet, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::{close, fork, gettid, pipe, read, write, ForkResult};
+
+    require_capability!("test_status_tracer_pid", CAP_SYS_PTRACE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    let (r, w) = pipe().unwrap();
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        ForkResult::Child => {
+            // Leave a blocked SIGUSR1 pending
+            let mut mask = SigSet::empty();
+            mask.add(Signal::SIGUSR1);
+            mask.thread_block().unwrap();
+            raise(Signal::SIGUSR1).unwrap();
+            write(w, b"x").unwrap();
+            loop {
+                std::thread::sleep(std::time::Duration::from_millis(10));
+            }
+        }
+        ForkResult::Parent { child } => {
+            let mut buf = [0u8; 1];
+            assert_eq!(read(r, &mut buf), Ok(1));
+            close(r).unwrap();
+            close(w).unwrap();
+            assert_eq!(status(child).unwrap().tracer_pid, None);
+
+            ptrace::attach(child).unwrap();
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGSTOP))
+            );
+            let st = status(child).unwrap();
+            // The tracer is this test's thread, not the whole process
+            assert_eq!(st.tracer_pid, Some(gettid()));
+            assert_eq!(st.state, ProcState::TracingStop);
+            let usr1 = 1 << (Signal::SIGUSR1 as i32 - 1);
+            assert_ne!(st.sig_blk & usr1, 0);
+            assert_ne!((st.sig_pnd | st.shd_pnd) & usr1, 0);
+
+            ptrace::detach(child, None).unwrap();
+            kill(child, Signal::SIGKILL).unwrap();
+            let _ = waitpid(child, None);
+        }
+    }
+}
@@ -274,3 +274,157 @@ fn test_forkpty() {
         }
     }
 }
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_forkpty_traced() {
+    use nix::pty::forkpty_traced;
+    use nix::sys::ptrace;
+    use nix::sys::signal::Signal;
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::execv;
+    use nix::unistd::ForkResult::*;
+    use std::ffi::CString;
+
+    require_capability!("test_forkpty_traced", CAP_SYS_PTRACE);
+    // forkpty calls openpty which uses ptname(3) internally.
+    let _m0 = crate::PTSNAME_MTX.lock();
+    // forkpty spawns a child process
+    let _m1 = crate::FORK_MTX.lock();
+
+    let pty = unsafe { forkpty_traced(None, None).unwrap() };
+    match pty.fork_result {
+        Child => {
+            let args = [
+                CString::new("/bin/sh").unwrap(),
+                CString::new("-c").unwrap(),
+                CString::new("echo traced").unwrap(),
+            ];
+            let _ = execv(&args[0], &args);
+            unsafe { _exit(1) };
+        }
+        Parent { child } => {
+            // The exec stops the tracee before the program runs
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Stopped(child, Signal::SIGTRAP))
+            );
+            ptrace::cont(child, None).unwrap();
+
+            let mut buf = [0u8; 8];
+            crate::read_exact(&pty.master, &mut buf);
+            assert_eq!(&buf, b"traced\r\n");
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+        }
+    }
+}
+
+#[test]
+fn test_set_foreground() {
+    use nix::sys::signal::{kill, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::ForkResult::*;
+    use nix::unistd::{
+        create_session_and_group, fork, getpid, set_foreground, setpgid,
+        tcgetpgrp, Pid,
+    };
+
+    // openpty uses ptname(3) internally
+    let _m0 = crate::PTSNAME_MTX.lock();
+    let _m1 = crate::FORK_MTX.lock();
+
+    let pty = openpty(None, None).unwrap();
+    let tty = pty.slave.as_raw_fd();
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            let ok = (|| {
+                let (sid, pgid) = create_session_and_group().ok()?;
+                if sid != getpid() || pgid != getpid() {
+                    return None;
+                }
+                // Make the pty the controlling terminal of the new session
+                if unsafe { libc::ioctl(tty, libc::TIOCSCTTY as _, 0) } != 0 {
+                    return None;
+                }
+                let tracee = match unsafe { fork() }.ok()? {
+                    Child => {
+                        let _ = setpgid(Pid::from_raw(0), Pid::from_raw(0));
+                        pause();
+                        unsafe { _exit(0) };
+                    }
+                    Parent { child } => child,
+                };
+                setpgid(tracee, tracee).ok()?;
+                set_foreground(tty, tracee).ok()?;
+                let handed_over = tcgetpgrp(tty) == Ok(tracee);
+                // Take the terminal back from the background
+                set_foreground(tty, pgid).ok()?;
+                let taken_back = tcgetpgrp(tty) == Ok(pgid);
+                kill(tracee, Signal::SIGKILL).ok()?;
+                waitpid(tracee, None).ok()?;
+                Some(handed_over && taken_back)
+            })();
+            unsafe { _exit(if ok == Some(true) { 0 } else { 1 }) };
+        }
+        Parent { child } => {
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+        }
+    }
+}
+
+#[test]
+fn test_tcsetpgrp_to_child_and_back() {
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::ForkResult::*;
+    use nix::unistd::{
+        fork, getpgrp, pipe, read, setpgid, setsid, tcgetpgrp, tcsetpgrp, Pid,
+    };
+
+    // openpty uses ptname(3) internally
+    let _m0 = crate::PTSNAME_MTX.lock();
+    let _m1 = crate::FORK_MTX.lock();
+
+    let pty = openpty(None, None).unwrap();
+    let tty = pty.slave.as_raw_fd();
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            let ok = (|| {
+                // Become a session leader with the pty as controlling terminal
+                setsid().ok()?;
+                if unsafe { libc::ioctl(tty, libc::TIOCSCTTY as _, 0) } != 0 {
+                    return None;
+                }
+                let leader = getpgrp();
+                if tcgetpgrp(tty) != Ok(leader) {
+                    return None;
+                }
+                let (rd, wr) = pipe().ok()?;
+                let child = match unsafe { fork() }.ok()? {
+                    Child => {
+                        let _ = setpgid(Pid::from_raw(0), Pid::from_raw(0));
+                        let mut buf = [0];
+                        let _ = read(rd, &mut buf);
+                        // The child is in the foreground now, so it can hand
+                        // the terminal back without getting SIGTTOU
+                        let ok = tcgetpgrp(tty) == Ok(getpgrp())
+                            && tcsetpgrp(tty, leader).is_ok();
+                        unsafe { _exit(if ok { 0 } else { 1 }) };
+                    }
+                    Parent { child } => child,
+                };
+                setpgid(child, child).ok()?;
+                tcsetpgrp(tty, child).ok()?;
+                write(wr, b"x").ok()?;
+                let status = waitpid(child, None).ok()?;
+                Some(
+                    status == WaitStatus::Exited(child, 0)
+                        && tcgetpgrp(tty) == Ok(leader),
+                )
+            })();
+            unsafe { _exit(if ok == Some(true) { 0 } else { 1 }) };
+        }
+        Parent { child } => {
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+        }
+    }
+}
@@ -32,3 +32,85 @@ pub fn test_resource_limits_nofile() {
     let (new_soft_limit, _) = getrlimit(Resource::RLIMIT_NOFILE).unwrap();
     assert_eq!(new_soft_limit, soft_limit);
 }
+
+#[test]
+#[cfg(not(any(
+    target_os = "redox",
+    target_os = "fuchsia",
+    target_os = "illumos",
+    target_os = "haiku"
+)))]
+pub fn test_setpriority_child() {
+    use nix::sys::resource::{getpriority, setpriority, PriorityWho};
+    use nix::sys::signal::{kill, Signal};
+    use nix::sys::wait::waitpid;
+    use nix::unistd::{fork, pause, ForkResult::*};
+
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => loop {
+            pause();
+        },
+        Parent { child } => {
+            let who = PriorityWho::Process(child);
+            let old = getpriority(who).unwrap();
+            // Raising the nice value needs no privileges
+            let new = (old + 5).min(19);
+            setpriority(who, new).unwrap();
+            assert_eq!(getpriority(who), Ok(new));
+
+            kill(child, Signal::SIGKILL).unwrap();
+            waitpid(child, None).unwrap();
+        }
+    }
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub fn test_prlimit_stack_child() {
+    use nix::sys::resource::{prlimit, set_tracee_stack_limit, RLIM_INFINITY};
+    use nix::sys::signal::{kill, Signal};
+    use nix::sys::wait::waitpid;
+    use nix::unistd::{fork, pause, ForkResult::*};
+
+    let _m = crate::FORK_MTX.lock();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => loop {
+            pause();
+        },
+        Parent { child } => {
+            let (soft, hard) =
+                prlimit(child, Resource::RLIMIT_STACK, None).unwrap();
+            assert_eq!(
+                getrlimit(Resource::RLIMIT_STACK),
+                Ok((soft, hard)),
+                "a forked child inherits its parent's limits"
+            );
+
+            // Lowering the soft limit needs no privileges
+            let target = if hard == RLIM_INFINITY {
+                4 << 20
+            } else {
+                hard.min(4 << 20)
+            };
+            set_tracee_stack_limit(child, target).unwrap();
+            assert_eq!(
+                prlimit(child, Resource::RLIMIT_STACK, None),
+                Ok((target, hard))
+            );
+            if hard != RLIM_INFINITY {
+                assert_eq!(
+                    set_tracee_stack_limit(child, hard + 1),
+                    Err(nix::errno::Errno::EINVAL)
+                );
+            }
+            // Our own limits are unaffected
+            assert_eq!(getrlimit(Resource::RLIMIT_STACK), Ok((soft, hard)));
+
+            kill(child, Signal::SIGKILL).unwrap();
+            waitpid(child, None).unwrap();
+        }
+    }
+}
@@ -37,3 +37,267 @@ fn test_sched_affinity() {
     // Finally, reset the initial CPU set
     sched_setaffinity(Pid::from_raw(0), &initial_affinity).unwrap();
 }
+
+#[test]
+fn test_cpuset_from_cpu() {
+    let cpuset = CpuSet::from_cpu(3).unwrap();
+    assert_eq!(cpuset.num_set(), 1);
+    assert!(cpuset.is_set(3).unwrap());
+    assert!(!cpuset.is_set(2).unwrap());
+
+    CpuSet::from_cpu(CpuSet::count()).unwrap_err();
+}
+
+#[test]
+fn test_cpuset_from_range() {
+    let cpuset = CpuSet::from_range(2..6).unwrap();
+    assert_eq!(cpuset.num_set(), 4);
+    for cpu in 0..8 {
+        assert_eq!(cpuset.is_set(cpu).unwrap(), (2..6).contains(&cpu));
+    }
+
+    assert_eq!(CpuSet::from_range(0..0).unwrap().num_set(), 0);
+    assert_eq!(
+        CpuSet::from_range(0..CpuSet::count()).unwrap().num_set(),
+        CpuSet::count()
+    );
+    CpuSet::from_range(0..CpuSet::count() + 1).unwrap_err();
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_setns_time_namespace_pidfd() {
+    use nix::errno::Errno;
+    use nix::sched::{setns, unshare, CloneFlags};
+    use nix::sys::pidfd::{pidfd_open, PidfdFlags};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::{fork, pipe, read, write, ForkResult};
+
+    skip_if_not_root!("test_setns_time_namespace_pidfd");
+
+    let _m = crate::FORK_MTX.lock();
+
+    let ns = |pid: &str| std::fs::read_link(format!("/proc/{}/ns/time", pid));
+
+    // Joining a time namespace requires a single-threaded caller
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        ForkResult::Child => {
+            let (rd, wr) = pipe().unwrap();
+            // Exit with 2 for a missing kernel feature or privilege, and
+            // with 100 plus the errno for any other failure
+            let unsupported = |e: Errno| match e {
+                Errno::ENOSYS | Errno::EINVAL | Errno::EPERM => 2,
+                e => 100 + e as i32,
+            };
+            let original = ns("self").ok();
+            if let Err(e) = unshare(CloneFlags::CLONE_NEWTIME) {
+                // Linux 5.6 or later is required
+                unsafe { libc::_exit(unsupported(e)) };
+            }
+            let child = match unsafe { fork() }.unwrap() {
+                ForkResult::Child => {
+                    let mut buf = [0];
+                    let _ = read(rd, &mut buf);
+                    unsafe { libc::_exit(0) };
+                }
+                ForkResult::Parent { child } => child,
+            };
+            let joined = match pidfd_open(child, PidfdFlags::empty()) {
+                Ok(pidfd) => setns(pidfd, CloneFlags::CLONE_NEWTIME),
+                Err(e) => Err(e),
+            };
+            let code = match joined {
+                Ok(()) => {
+                    let ours = ns("self").ok();
+                    let theirs = ns(&child.to_string()).ok();
+                    if ours.is_some() && ours == theirs && ours != original {
+                        0
+                    } else {
+                        1
+                    }
+                }
+                // Linux 5.8 or later is required
+                Err(e) => unsupported(e),
+            };
+            let _ = write(wr, b"x");
+            let _ = waitpid(child, None);
+            unsafe { libc::_exit(code) };
+        }
+        ForkResult::Parent { child } => match waitpid(child, None) {
+            Ok(WaitStatus::Exited(_, 2)) => {
+                skip!("setns with a pidfd and time namespaces require Linux 5.8 or later and privileges. Skipping test.");
+            }
+            status => assert_eq!(status, Ok(WaitStatus::Exited(child, 0))),
+        },
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_set_timens_offset() {
+    use nix::sched::{set_timens_offset, unshare, CloneFlags};
+    use nix::sys::time::{TimeSpec, TimeValLike};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::time::ClockId;
+    use nix::unistd::{fork, ForkResult};
+
+    skip_if_not_root!("test_set_timens_offset");
+
+    let _m = crate::FORK_MTX.lock();
+
+    const DAYS: i64 = 10;
+    let clock = ClockId::CLOCK_MONOTONIC;
+
+    // Unshare in a child, so that the test process's later children aren't
+    // affected
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        ForkResult::Child => {
+            if unshare(CloneFlags::CLONE_NEWTIME).is_err() {
+                // Linux 5.6 or later is required
+                unsafe { libc::_exit(2) };
+            }
+            let offset = TimeSpec::seconds(DAYS * 86400);
+            if set_timens_offset(None, clock, offset).is_err() {
+                unsafe { libc::_exit(1) };
+            }
+            let before = clock.now().unwrap();
+            match unsafe { fork() }.unwrap() {
+                ForkResult::Child => {
+                    // The grandchild sees the shifted clock
+                    let shift = clock.now().unwrap() - before;
+                    let ok = shift >= offset
+                        && shift < offset + TimeSpec::seconds(60);
+                    unsafe { libc::_exit(if ok { 0 } else { 1 }) };
+                }
+                ForkResult::Parent { child } => {
+                    let code = match waitpid(child, None) {
+                        Ok(WaitStatus::Exited(_, code)) => code,
+                        _ => 1,
+                    };
+                    // The offsets are frozen once a process is in the
+                    // namespace
+                    let frozen =
+                        set_timens_offset(None, clock, TimeSpec::seconds(0))
+                            .is_err();
+                    unsafe { libc::_exit(if frozen { code } else { 1 }) };
+                }
+            }
+        }
+        ForkResult::Parent { child } => match waitpid(child, None) {
+            Ok(WaitStatus::Exited(_, 2)) => {
+                skip!("Time namespaces require Linux 5.6 or later. Skipping test.");
+            }
+            status => assert_eq!(status, Ok(WaitStatus::Exited(child, 0))),
+        },
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_unshare_checked_uts() {
+    use nix::sched::{unshare_checked, CloneFlags};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::{fork, ForkResult};
+
+    skip_if_not_root!("test_unshare_checked_uts");
+
+    let _m = crate::FORK_MTX.lock();
+
+    let uts = || std::fs::read_link("/proc/self/ns/uts").unwrap();
+    let original = uts();
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        ForkResult::Child => {
+            let ok = unshare_checked(CloneFlags::CLONE_NEWUTS).is_ok()
+                && uts() != original;
+            unsafe { libc::_exit(if ok { 0 } else { 1 }) };
+        }
+        ForkResult::Parent { child } => {
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+        }
+    }
+    assert_eq!(uts(), original);
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_sched_setscheduler() {
+    use nix::sched::*;
+    use nix::sys::signal::{kill, Signal};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::{fork, ForkResult};
+
+    let _m = crate::FORK_MTX.lock();
+
+    let child = match unsafe { fork() }.expect("Error: Fork Failed") {
+        ForkResult::Child => loop {
+            std::thread::sleep(std::time::Duration::from_secs(1));
+        },
+        ForkResult::Parent { child } => child,
+    };
+    let check = || -> nix::Result<()> {
+        // Lowering the priority needs no privileges
+        sched_setscheduler(
+            child,
+            SchedPolicy::SCHED_BATCH,
+            &SchedParam::new(0),
+        )?;
+        assert_eq!(sched_getscheduler(child)?, SchedPolicy::SCHED_BATCH);
+        assert_eq!(sched_getparam(child)?.priority(), 0);
+        Ok(())
+    };
+    let res = check();
+    kill(child, Signal::SIGKILL).unwrap();
+    assert_eq!(
+        waitpid(child, None),
+        Ok(WaitStatus::Signaled(child, Signal::SIGKILL, false))
+    );
+    res.unwrap();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_sched_setscheduler_fifo() {
+    use nix::sched::*;
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::{fork, ForkResult, Pid};
+
+    require_capability!("test_sched_setscheduler_fifo", CAP_SYS_NICE);
+
+    let _m = crate::FORK_MTX.lock();
+
+    let min = sched_get_priority_min(SchedPolicy::SCHED_FIFO).unwrap();
+    let max = sched_get_priority_max(SchedPolicy::SCHED_FIFO).unwrap();
+    assert!((1..=max).contains(&min));
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        ForkResult::Child => {
+            let me = Pid::from_raw(0);
+            let ok = sched_setscheduler(
+                me,
+                SchedPolicy::SCHED_FIFO,
+                &SchedParam::new(min),
+            )
+            .is_ok()
+                && sched_getscheduler(me) == Ok(SchedPolicy::SCHED_FIFO)
+                && sched_getparam(me).map(|p| p.priority()) == Ok(min);
+            unsafe { libc::_exit(if ok { 0 } else { 1 }) };
+        }
+        ForkResult::Parent { child } => {
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+        }
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_getcpu() {
+    use nix::sched::getcpu;
+    use nix::unistd::{sysconf, SysconfVar};
+
+    // CPU numbers can have holes if some are offline, but never exceed the
+    // number of configured CPUs
+    let ncpus = sysconf(SysconfVar::_NPROCESSORS_CONF).unwrap().unwrap();
+    let (cpu, _node) = getcpu().unwrap();
+    assert!((cpu as libc::c_long) < ncpus, "{} >= {}", cpu, ncpus);
+}
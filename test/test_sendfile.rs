@@ -67,6 +67,37 @@ fn test_sendfile64_linux() {
     close(rd).unwrap();
 }
 
+#[cfg(target_os = "linux")]
+#[test]
+fn test_sendfile64_all_socket() {
+    use std::os::unix::net::UnixStream;
+
+    // More than fits in a socket buffer, so that a single call would send
+    // only part of it
+    const LEN: usize = 4 << 20;
+    let contents: Vec<u8> = (0..LEN).map(|i| i as u8).collect();
+    let mut tmp = tempfile().unwrap();
+    tmp.write_all(&contents).unwrap();
+
+    let (tx, mut rx) = UnixStream::pair().unwrap();
+    let reader = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        rx.read_to_end(&mut buf).unwrap();
+        buf
+    });
+
+    let mut offset: libc::off64_t = 1;
+    let res = sendfile64_all(&tx, &tmp, Some(&mut offset), LEN);
+    drop(tx);
+    let received = reader.join().unwrap();
+
+    // Copying stops at the end of the file
+    assert_eq!(res, Ok(LEN - 1));
+    assert_eq!(offset, LEN as libc::off64_t);
+    assert_eq!(received.len(), LEN - 1);
+    assert!(received == contents[1..]);
+}
+
 #[cfg(target_os = "freebsd")]
 #[test]
 fn test_sendfile_freebsd() {
@@ -121,6 +121,33 @@ fn test_fstatat() {
     assert_stat_results(result);
 }
 
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn test_fstatat_flags() {
+    use fcntl::AtFlags;
+
+    let tempdir = tempfile::tempdir().unwrap();
+    let filename = tempdir.path().join("foo.txt");
+    let file = File::create(&filename).unwrap();
+    symlink(&filename, tempdir.path().join("link")).unwrap();
+    let dirfd =
+        fcntl::open(tempdir.path(), fcntl::OFlag::empty(), Mode::empty())
+            .unwrap();
+
+    let st =
+        stat::fstatat(dirfd, "link", AtFlags::AT_SYMLINK_NOFOLLOW).unwrap();
+    assert_eq!(st.st_mode & S_IFMT, S_IFLNK);
+
+    let st =
+        stat::fstatat(file.as_raw_fd(), "", AtFlags::AT_EMPTY_PATH).unwrap();
+    assert_eq!(st.st_ino, stat::fstat(file.as_raw_fd()).unwrap().st_ino);
+
+    assert_eq!(
+        stat::fstatat(dirfd, "link", AtFlags::AT_REMOVEDIR).unwrap_err(),
+        Errno::EINVAL
+    );
+}
+
 #[test]
 #[cfg(not(any(target_os = "netbsd", target_os = "redox")))]
 fn test_stat_fstat_lstat() {
@@ -315,6 +342,109 @@ fn test_utimensat() {
     assert_times_eq(500, 800, &fs::metadata(&fullpath).unwrap());
 }
 
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn test_utimensat_flags() {
+    use fcntl::AtFlags;
+
+    let tempdir = tempfile::tempdir().unwrap();
+    let fullpath = tempdir.path().join("foo.txt");
+    let file = File::create(&fullpath).unwrap();
+    let linkpath = tempdir.path().join("link");
+    symlink(&fullpath, &linkpath).unwrap();
+    let dirfd =
+        fcntl::open(tempdir.path(), fcntl::OFlag::empty(), Mode::empty())
+            .unwrap();
+
+    // Only the link itself is changed
+    utimensat(
+        Some(dirfd),
+        "link",
+        &TimeSpec::seconds(12345),
+        &TimeSpec::seconds(678),
+        AtFlags::AT_SYMLINK_NOFOLLOW,
+    )
+    .unwrap();
+    assert_times_eq(12345, 678, &fs::symlink_metadata(&linkpath).unwrap());
+    assert_ne!(
+        fs::metadata(&fullpath).unwrap().modified().unwrap(),
+        UNIX_EPOCH + Duration::from_secs(678)
+    );
+
+    match utimensat(
+        Some(file.as_raw_fd()),
+        "",
+        &TimeSpec::seconds(500),
+        &TimeSpec::seconds(800),
+        AtFlags::AT_EMPTY_PATH,
+    ) {
+        // AT_EMPTY_PATH requires Linux 5.8 or later
+        Err(Errno::EINVAL | Errno::ENOENT) => (),
+        r => {
+            r.unwrap();
+            assert_times_eq(500, 800, &fs::metadata(&fullpath).unwrap());
+        }
+    }
+
+    assert_eq!(
+        utimensat(
+            Some(dirfd),
+            "link",
+            &TimeSpec::seconds(1),
+            &TimeSpec::seconds(1),
+            AtFlags::AT_REMOVEDIR,
+        ),
+        Err(Errno::EINVAL)
+    );
+}
+
+#[test]
+#[cfg(not(any(target_os = "redox", target_os = "haiku")))]
+fn test_utimensat_omit_now() {
+    use nix::sys::stat::UtimeSpec;
+    use std::time::SystemTime;
+
+    let tempdir = tempfile::tempdir().unwrap();
+    let fullpath = tempdir.path().join("foo.txt");
+    drop(File::create(&fullpath).unwrap());
+
+    utimensat(
+        None,
+        &fullpath,
+        &TimeSpec::seconds(12345),
+        &TimeSpec::seconds(678),
+        UtimensatFlags::FollowSymlink,
+    )
+    .unwrap();
+
+    // Only change the mtime
+    utimensat(
+        None,
+        &fullpath,
+        None,
+        TimeSpec::seconds(900),
+        UtimensatFlags::FollowSymlink,
+    )
+    .unwrap();
+    assert_times_eq(12345, 900, &fs::metadata(&fullpath).unwrap());
+
+    utimensat(
+        None,
+        &fullpath,
+        UtimeSpec::Omit,
+        UtimeSpec::Now,
+        UtimensatFlags::FollowSymlink,
+    )
+    .unwrap();
+    let attr = fs::metadata(&fullpath).unwrap();
+    assert_eq!(
+        attr.accessed().unwrap(),
+        UNIX_EPOCH + Duration::from_secs(12345)
+    );
+    let age = SystemTime::now().duration_since(attr.modified().unwrap());
+    assert!(age.map_or(true, |age| age < Duration::from_secs(60)));
+}
+
 #[test]
 #[cfg(not(target_os = "redox"))]
 fn test_mkdirat_success_path() {
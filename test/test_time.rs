@@ -14,6 +14,17 @@ pub fn test_clock_getres() {
     nix::time::clock_getres(ClockId::CLOCK_REALTIME).expect("assertion failed");
 }
 
+#[cfg(not(target_os = "redox"))]
+#[test]
+pub fn test_clock_getres_monotonic() {
+    use nix::sys::time::TimeValLike;
+
+    let res = nix::time::clock_getres(ClockId::CLOCK_MONOTONIC).unwrap();
+    assert!(res.num_nanoseconds() > 0);
+    // No clock ticks slower than once a second
+    assert!(res.num_nanoseconds() <= 1_000_000_000);
+}
+
 #[test]
 pub fn test_clock_gettime() {
     clock_gettime(ClockId::CLOCK_REALTIME).expect("assertion failed");
@@ -57,3 +68,11 @@ pub fn test_clock_id_pid_cpu_clock_id() {
         .unwrap()
         .unwrap();
 }
+
+#[test]
+pub fn test_clock_id_monotonic_now() {
+    let start = ClockId::monotonic_now().unwrap();
+    let end = ClockId::monotonic_now().unwrap();
+    assert!(end >= start);
+    assert!(end.checked_duration_since(start).is_some());
+}
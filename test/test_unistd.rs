@@ -260,6 +260,30 @@ fn test_setgroups() {
     setgroups(&old_groups).unwrap();
 }
 
+#[test]
+// `getgroups()` and `setgroups()` do not behave as expected on Apple platforms
+#[cfg(not(any(
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "redox",
+    target_os = "fuchsia",
+    target_os = "haiku"
+)))]
+fn test_setgroups_empty() {
+    skip_if_not_root!("test_setgroups_empty");
+
+    let _m = crate::GROUPS_MTX.lock();
+
+    let old_groups = getgroups().unwrap();
+
+    // Dropping all supplementary groups, as a launcher dropping privileges
+    // should
+    setgroups(&[]).unwrap();
+    assert!(getgroups().unwrap().is_empty());
+
+    setgroups(&old_groups).unwrap();
+}
+
 #[test]
 // `getgroups()` and `setgroups()` do not behave as expected on Apple platforms
 #[cfg(not(any(
@@ -422,6 +446,105 @@ cfg_if! {
     }
 }
 
+#[test]
+#[cfg(target_os = "linux")]
+fn test_fexecve_memfd() {
+    use nix::sys::memfd::{memfd_create, MemFdCreateFlag};
+    use std::ffi::CStr;
+
+    let sh = match fs::read("/bin/sh") {
+        Ok(sh) => sh,
+        Err(_) => {
+            skip!("test_fexecve_memfd requires /bin/sh. Skipping test.");
+        }
+    };
+    let name = CStr::from_bytes_with_nul(b"nix-fexecve\0").unwrap();
+    let memfd = memfd_create(name, MemFdCreateFlag::empty()).unwrap();
+    File::from(memfd.try_clone().unwrap())
+        .write_all(&sh)
+        .unwrap();
+
+    let args = [
+        CString::new("sh").unwrap(),
+        CString::new("-c").unwrap(),
+        CString::new("exit 42").unwrap(),
+    ];
+    let env: [CString; 0] = [];
+
+    let _m = crate::FORK_MTX.lock();
+    // Safe: Child only calls `fexecve` and `_exit`.
+    match unsafe { fork() }.unwrap() {
+        Child => {
+            let _ = fexecve(memfd.as_raw_fd(), &args, &env);
+            unsafe { _exit(1) };
+        }
+        Parent { child } => {
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 42)));
+        }
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_process_spawner() {
+    use std::os::unix::io::OwnedFd;
+
+    let (r, w) = pipe().unwrap();
+    let (r, w) = unsafe { (File::from_raw_fd(r), OwnedFd::from_raw_fd(w)) };
+
+    let _m = crate::FORK_MTX.lock();
+    let path = CString::new("/bin/true").unwrap();
+    let child = ProcessSpawner::new(&path)
+        .dup2(w.as_raw_fd(), libc::STDOUT_FILENO)
+        .close(r.as_raw_fd())
+        .setsid(true)
+        .spawn()
+        .unwrap();
+    drop(w);
+    // posix_spawn only returns once the child has started the program, so
+    // it is already the leader of its own session
+    assert_eq!(getsid(Some(child)), Ok(child));
+    assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+
+    // All write ends are closed, and `true` wrote nothing
+    let mut out = Vec::new();
+    std::io::Read::read_to_end(&mut &r, &mut out).unwrap();
+    assert!(out.is_empty());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_process_spawner_output() {
+    use std::os::unix::io::OwnedFd;
+
+    let (r, w) = pipe().unwrap();
+    let (r, w) = unsafe { (File::from_raw_fd(r), OwnedFd::from_raw_fd(w)) };
+
+    let _m = crate::FORK_MTX.lock();
+    let args = [
+        CString::new("sh").unwrap(),
+        CString::new("-c").unwrap(),
+        CString::new("echo $GREETING; exit 3").unwrap(),
+    ];
+    let child = ProcessSpawner::new(&args[0])
+        .args(&args)
+        .env(&[CString::new("GREETING=hello").unwrap()])
+        .search_path(true)
+        .dup2(w.as_raw_fd(), libc::STDOUT_FILENO)
+        .spawn()
+        .unwrap();
+    drop(w);
+    assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 3)));
+
+    let mut out = String::new();
+    std::io::Read::read_to_string(&mut &r, &mut out).unwrap();
+    assert_eq!(out, "hello\n");
+
+    // Failing to execute the program is reported by spawn
+    let missing = CString::new("/nonexistent").unwrap();
+    assert_eq!(ProcessSpawner::new(&missing).spawn(), Err(Errno::ENOENT));
+}
+
 #[cfg(any(target_os = "haiku", target_os = "linux", target_os = "openbsd"))]
 execve_test_factory!(test_execvpe, execvpe, &CString::new("sh").unwrap());
 
@@ -550,6 +673,37 @@ fn test_fchownat() {
     fchownat(None, "file", uid, gid, FchownatFlags::FollowSymlink).unwrap_err();
 }
 
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn test_fchownat_flags() {
+    use nix::fcntl::AtFlags;
+    use std::os::unix::fs::symlink;
+
+    let uid = Some(getuid());
+    let gid = Some(getgid());
+
+    let tempdir = tempdir().unwrap();
+    let path = tempdir.path().join("file");
+    let file = File::create(&path).unwrap();
+    symlink(&path, tempdir.path().join("link")).unwrap();
+    let dirfd = open(tempdir.path(), OFlag::empty(), Mode::empty()).unwrap();
+
+    fchownat(Some(dirfd), "link", uid, gid, AtFlags::AT_SYMLINK_NOFOLLOW)
+        .unwrap();
+    fchownat(Some(file.as_raw_fd()), "", uid, gid, AtFlags::AT_EMPTY_PATH)
+        .unwrap();
+    // Without AT_EMPTY_PATH, an empty path doesn't name anything
+    assert_eq!(
+        fchownat(Some(file.as_raw_fd()), "", uid, gid, AtFlags::empty()),
+        Err(Errno::ENOENT)
+    );
+    assert_eq!(
+        fchownat(Some(dirfd), "file", uid, gid, AtFlags::AT_REMOVEDIR),
+        Err(Errno::EINVAL)
+    );
+    close(dirfd).unwrap();
+}
+
 #[test]
 fn test_lseek() {
     const CONTENTS: &[u8] = b"abcdef123456";
@@ -708,6 +862,45 @@ fn test_getresgid() {
     assert_ne!(resgids.saved.as_raw(), libc::gid_t::MAX);
 }
 
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "openbsd"
+))]
+#[test]
+fn test_setresuid_setresgid_drop() {
+    skip_if_not_root!("test_setresuid_setresgid_drop");
+
+    let _m = crate::FORK_MTX.lock();
+
+    // Drop privileges in a child, so the test process keeps its own
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            let nobody = Uid::from_raw(65534);
+            let nogroup = Gid::from_raw(65534);
+            let ok = setresgid(nogroup, nogroup, None).is_ok()
+                && getresgid().map(|g| (g.real, g.effective, g.saved))
+                    == Ok((nogroup, nogroup, Gid::from_raw(0)))
+                // Keep the saved uid, so that root can be regained
+                && setresuid(nobody, nobody, None).is_ok()
+                && getresuid().map(|u| (u.real, u.effective, u.saved))
+                    == Ok((nobody, nobody, Uid::from_raw(0)))
+                && setresuid(None, Uid::from_raw(0), None).is_ok()
+                && geteuid().is_root()
+                // Now drop it for good
+                && setresuid(nobody, nobody, nobody).is_ok()
+                && setresuid(None, Uid::from_raw(0), None)
+                    == Err(Errno::EPERM);
+            unsafe { _exit(if ok { 0 } else { 1 }) };
+        }
+        Parent { child } => {
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+        }
+    }
+}
+
 // Test that we can create a pair of pipes.  No need to verify that they pass
 // data; that's the domain of the OS, not nix.
 #[test]
@@ -749,6 +942,80 @@ fn test_pipe2() {
     assert!(f1.contains(FdFlag::FD_CLOEXEC));
 }
 
+#[cfg(target_os = "linux")]
+#[test]
+fn test_close_range() {
+    use nix::fcntl::{fcntl, FcntlArg, FdFlag};
+
+    // Use high descriptor numbers that no other test will be using
+    const FIRST: RawFd = 900;
+    let f = tempfile().unwrap();
+    for fd in FIRST..FIRST + 4 {
+        dup2(f.as_raw_fd(), fd).unwrap();
+    }
+
+    let first = FIRST as libc::c_uint;
+    if close_range(first, first + 2, CloseRangeFlags::empty())
+        == Err(Errno::ENOSYS)
+    {
+        for fd in FIRST..FIRST + 4 {
+            close(fd).unwrap();
+        }
+        skip!("close_range requires Linux 5.9 or later. Skipping test.");
+    }
+    for fd in FIRST..FIRST + 3 {
+        assert_eq!(fcntl(fd, FcntlArg::F_GETFD), Err(Errno::EBADF));
+    }
+
+    // The next descriptor was outside the range, and CLOSE_RANGE_CLOEXEC
+    // merely marks it close-on-exec
+    let last = FIRST + 3;
+    match close_range(
+        first + 3,
+        first + 3,
+        CloseRangeFlags::CLOSE_RANGE_CLOEXEC,
+    ) {
+        Err(Errno::EINVAL) => (), // Before Linux 5.11
+        r => {
+            r.unwrap();
+            let flags = fcntl(last, FcntlArg::F_GETFD).unwrap();
+            assert!(
+                FdFlag::from_bits_truncate(flags).contains(FdFlag::FD_CLOEXEC)
+            );
+        }
+    }
+    close(last).unwrap();
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_mark_fds_cloexec_above() {
+    use nix::fcntl::{fcntl, FcntlArg, FdFlag};
+
+    // Whether fd has the close-on-exec flag set
+    fn cloexec(fd: RawFd) -> bool {
+        let flags = fcntl(fd, FcntlArg::F_GETFD).unwrap();
+        FdFlag::from_bits_truncate(flags).contains(FdFlag::FD_CLOEXEC)
+    }
+
+    // Don't let a concurrent fork and exec see the descriptors change
+    let _m = crate::FORK_MTX.lock();
+
+    let f = tempfile().unwrap();
+    dup2(f.as_raw_fd(), 920).unwrap();
+    dup2(f.as_raw_fd(), 921).unwrap();
+    assert!(!cloexec(920));
+    assert!(!cloexec(921));
+
+    // Descriptors up to the threshold are left alone
+    mark_fds_cloexec_above(920).unwrap();
+    assert!(!cloexec(920));
+    assert!(cloexec(921));
+
+    close(920).unwrap();
+    close(921).unwrap();
+}
+
 #[test]
 #[cfg(not(any(target_os = "redox", target_os = "fuchsia")))]
 fn test_truncate() {
@@ -908,6 +1175,43 @@ fn test_linkat_file() {
     assert!(newfilepath.exists());
 }
 
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn test_linkat_flags() {
+    use nix::fcntl::AtFlags;
+
+    let tempdir = tempdir().unwrap();
+    let oldfilepath = tempdir.path().join("foo.txt");
+    let file = File::create(oldfilepath).unwrap();
+    let dirfd =
+        fcntl::open(tempdir.path(), fcntl::OFlag::empty(), stat::Mode::empty())
+            .unwrap();
+
+    // linkat never follows symlinks by default, so rejects the flag
+    assert_eq!(
+        linkat(
+            Some(dirfd),
+            "foo.txt",
+            Some(dirfd),
+            "bar.txt",
+            AtFlags::AT_SYMLINK_NOFOLLOW,
+        ),
+        Err(Errno::EINVAL)
+    );
+
+    require_capability!("test_linkat_flags", CAP_DAC_READ_SEARCH);
+    linkat(
+        Some(file.as_raw_fd()),
+        "",
+        Some(dirfd),
+        "baz.txt",
+        AtFlags::AT_EMPTY_PATH,
+    )
+    .unwrap();
+    assert!(tempdir.path().join("baz.txt").exists());
+    close(dirfd).unwrap();
+}
+
 #[test]
 #[cfg(not(any(target_os = "redox", target_os = "haiku")))]
 fn test_linkat_olddirfd_none() {
@@ -1391,3 +1695,76 @@ fn test_eaccess_file_exists() {
     eaccess(&path, AccessFlags::R_OK | AccessFlags::W_OK)
         .expect("assertion failed");
 }
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_sethostname_setdomainname_in_uts_namespace() {
+    use nix::sched::{unshare, CloneFlags};
+    use nix::sys::utsname::uname;
+
+    skip_if_not_root!("test_sethostname_setdomainname_in_uts_namespace");
+
+    let _m = crate::FORK_MTX.lock();
+
+    let hostname = gethostname().unwrap();
+
+    // Change the names in a child's own UTS namespace, leaving the system's
+    // alone
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            let ok = unshare(CloneFlags::CLONE_NEWUTS).is_ok()
+                && sethostname("nix-test-host").is_ok()
+                && setdomainname("nix-test-domain").is_ok()
+                && gethostname().map(|h| h == "nix-test-host") == Ok(true)
+                && uname().map(|u| u.domainname() == "nix-test-domain")
+                    == Ok(true);
+            unsafe { _exit(if ok { 0 } else { 1 }) };
+        }
+        Parent { child } => {
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+        }
+    }
+    assert_eq!(gethostname().unwrap(), hostname);
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_getrandom() {
+    let mut buf = [0u8; 256];
+    assert_eq!(getrandom(&mut buf, GetRandomFlags::empty()), Ok(buf.len()));
+    // The odds of 256 random bytes all being zero are negligible
+    assert!(buf.iter().any(|&b| b != 0));
+
+    let mut other = [0u8; 256];
+    getrandom(&mut other, GetRandomFlags::GRND_NONBLOCK).unwrap();
+    assert_ne!(buf, other);
+
+    assert_eq!(getrandom(&mut [], GetRandomFlags::empty()), Ok(0));
+}
+
+#[test]
+fn test_is_alive() {
+    use nix::sys::signal::{kill, Signal};
+
+    let _m = crate::FORK_MTX.lock();
+
+    assert_eq!(is_alive(getpid()), Ok(true));
+    assert_eq!(is_alive(Pid::from_raw(0)), Err(Errno::EINVAL));
+
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            pause();
+            unsafe { _exit(0) };
+        }
+        Parent { child } => {
+            assert_eq!(is_alive(child), Ok(true));
+            kill(child, Signal::SIGKILL).unwrap();
+            assert!(matches!(
+                waitpid(child, None),
+                Ok(WaitStatus::Signaled(_, Signal::SIGKILL, _))
+            ));
+            // Reaped, so gone for good, barring pid reuse
+            assert_eq!(is_alive(child), Ok(false));
+        }
+    }
+}